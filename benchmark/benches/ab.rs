@@ -0,0 +1,202 @@
+// An A/B benchmark of the standard `as` conversion against the conversions of this crate. It fights the noise described in the criterion benchmarks and does not use criterion, so that it can take its own command line flags:
+//
+// cargo bench --package benchmark --bench ab -- --pin 2 --warmup 1000 --rounds 50 f32_to_i32
+//
+// --pin <core>: Run on this CPU core only. The scheduler cannot move the benchmark between cores with different caches or clock speeds. Only supported on Linux.
+// --warmup <milliseconds>: Spin on conversions before measuring, so that the CPU has raised its clock frequency when the measurement starts. Defaults to 500.
+// --rounds <rounds>: The number of rounds. Defaults to 30.
+// <filter>: Only run the conversions whose name contains the filter.
+//
+// Every round measures both conversions directly after each other and alternates which one goes first. Slow changes like the clock frequency or other load on the machine then affect both sides equally. The reported numbers are medians over the rounds, which ignore outlier rounds.
+//
+// The benchmark measures the target module that the crate selects. Do not enable the `force-default` feature of this crate, which makes both sides the standard conversion.
+
+use fast_float_to_integer as ffti;
+use std::{
+    hint::black_box,
+    ops::BitXor,
+    time::{Duration, Instant},
+};
+
+struct Options {
+    pin: Option<usize>,
+    warmup: Duration,
+    rounds: usize,
+    filter: Option<String>,
+}
+
+const USAGE: &str =
+    "usage: ab [--pin <core>] [--warmup <milliseconds>] [--rounds <rounds>] [filter]";
+
+fn parse_options() -> Result<Options, String> {
+    let mut options = Options {
+        pin: None,
+        warmup: Duration::from_millis(500),
+        rounds: 30,
+        filter: None,
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| -> Result<usize, String> {
+            let value = args.next().ok_or(format!("{name} needs a value"))?;
+            value
+                .parse()
+                .map_err(|_| format!("{name} needs a number, not {value}"))
+        };
+        match arg.as_str() {
+            "--pin" => options.pin = Some(value("--pin")?),
+            "--warmup" => options.warmup = Duration::from_millis(value("--warmup")? as u64),
+            "--rounds" => options.rounds = value("--rounds")?.max(1),
+            // Cargo passes this flag to every benchmark binary.
+            "--bench" => (),
+            _ if arg.starts_with('-') => return Err(format!("unknown flag {arg}")),
+            _ => options.filter = Some(arg),
+        }
+    }
+    Ok(options)
+}
+
+#[cfg(target_os = "linux")]
+fn pin(core: usize) -> Result<(), String> {
+    if core >= libc::CPU_SETSIZE as usize {
+        return Err(format!("core {core} does not fit in a CPU set"));
+    }
+    // SAFETY: The set is initialized by CPU_ZERO before use and sched_setaffinity only reads it.
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_ZERO(&mut set);
+        libc::CPU_SET(core, &mut set);
+        if libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) != 0 {
+            return Err(format!(
+                "pinning to core {core} failed: {}",
+                std::io::Error::last_os_error()
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn pin(_core: usize) -> Result<(), String> {
+    Err("pinning is only supported on Linux".to_owned())
+}
+
+// See the criterion benchmarks for why we create a dependency between the converted numbers.
+fn measure<Float, Integer>(convert: impl Fn(Float) -> Integer, floats: &[Float]) -> Duration
+where
+    Float: Copy,
+    Integer: Copy + Default + BitXor<Output = Integer>,
+{
+    let start = Instant::now();
+    let mut result = Integer::default();
+    for float in black_box(floats) {
+        result = result ^ convert(*float);
+    }
+    black_box(result);
+    start.elapsed()
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(f64::total_cmp);
+    values[values.len() / 2]
+}
+
+fn run<Float, Integer>(
+    options: &Options,
+    name: &str,
+    standard: impl Fn(Float) -> Integer,
+    fast: impl Fn(Float) -> Integer,
+    floats: &[Float],
+) where
+    Float: Copy,
+    Integer: Copy + Default + BitXor<Output = Integer>,
+{
+    if options
+        .filter
+        .as_ref()
+        .is_some_and(|filter| !name.contains(filter.as_str()))
+    {
+        return;
+    }
+
+    let start = Instant::now();
+    while start.elapsed() < options.warmup {
+        measure(&standard, floats);
+        measure(&fast, floats);
+    }
+
+    let nanos_per_element = |duration: Duration| duration.as_nanos() as f64 / floats.len() as f64;
+    let mut standard_nanos = Vec::with_capacity(options.rounds);
+    let mut fast_nanos = Vec::with_capacity(options.rounds);
+    let mut speedups = Vec::with_capacity(options.rounds);
+    for round in 0..options.rounds {
+        let (standard_time, fast_time) = if round % 2 == 0 {
+            let standard_time = measure(&standard, floats);
+            (standard_time, measure(&fast, floats))
+        } else {
+            let fast_time = measure(&fast, floats);
+            (measure(&standard, floats), fast_time)
+        };
+        standard_nanos.push(nanos_per_element(standard_time));
+        fast_nanos.push(nanos_per_element(fast_time));
+        speedups.push(standard_time.as_secs_f64() / fast_time.as_secs_f64());
+    }
+    println!(
+        "{name}: standard {:.3} ns/element, fast {:.3} ns/element, speedup {:.2}",
+        median(&mut standard_nanos),
+        median(&mut fast_nanos),
+        median(&mut speedups),
+    );
+}
+
+// The inputs are in range, so that both conversions give the same results.
+macro_rules! run {
+    ($options:expr, $name:ident, $Float:ty, $Integer:ty) => {
+        let floats: Vec<$Float> = (0..10_000).map(|i| (i % 100) as $Float + 0.5).collect();
+        run(
+            $options,
+            stringify!($name),
+            |float: $Float| float as $Integer,
+            ffti::$name,
+            &floats,
+        );
+    };
+}
+
+fn main() {
+    let options = match parse_options() {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{error}\n{USAGE}");
+            std::process::exit(2);
+        }
+    };
+    if let Some(core) = options.pin {
+        if let Err(error) = pin(core) {
+            eprintln!("{error}");
+            std::process::exit(1);
+        }
+    }
+
+    run! {&options, f32_to_i8, f32, i8}
+    run! {&options, f32_to_u8, f32, u8}
+    run! {&options, f32_to_i16, f32, i16}
+    run! {&options, f32_to_u16, f32, u16}
+    run! {&options, f32_to_i32, f32, i32}
+    run! {&options, f32_to_u32, f32, u32}
+    run! {&options, f32_to_i64, f32, i64}
+    run! {&options, f32_to_u64, f32, u64}
+    run! {&options, f32_to_i128, f32, i128}
+    run! {&options, f32_to_u128, f32, u128}
+
+    run! {&options, f64_to_i8, f64, i8}
+    run! {&options, f64_to_u8, f64, u8}
+    run! {&options, f64_to_i16, f64, i16}
+    run! {&options, f64_to_u16, f64, u16}
+    run! {&options, f64_to_i32, f64, i32}
+    run! {&options, f64_to_u32, f64, u32}
+    run! {&options, f64_to_i64, f64, i64}
+    run! {&options, f64_to_u64, f64, u64}
+    run! {&options, f64_to_i128, f64, i128}
+    run! {&options, f64_to_u128, f64, u128}
+}
@@ -1,15 +1,20 @@
 // Unfortunately, these benchmarks are noisy. There are significant differences in the measured performance based on random code permutation or running the benchmarks at different times or on different machines. The same function benchmarked twice can appear to have very different performance.
 //
 // We've changed some of the criterion settings to help with this, but the problem persists. It would be nice to have a more real world benchmark.
+//
+// The `ab` benchmark in this directory reduces the noise with core pinning, warmup and interleaved measurement of the standard and the fast conversions.
 
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use fast_float_to_integer as ffti;
 use std::{hint::black_box, time::Duration};
 
 // We create a dependency between the converted numbers so that compiler or CPU cannot skip the computation.
 macro_rules! create_benchmark {
-    ($c:ident, $name:literal, $function:path, $Float:ty) => {
-        let floats = [0 as $Float; 1_000];
+    ($c:ident, $name:expr, $function:expr, $Float:ty) => {
+        create_benchmark! {$c, $name, $function, $Float, 0 as $Float}
+    };
+    ($c:ident, $name:expr, $function:expr, $Float:ty, $value:expr) => {
+        let floats = [$value; 1_000];
         $c.bench_function($name, |b| {
             b.iter(|| {
                 let mut result = 0;
@@ -23,6 +28,19 @@ macro_rules! create_benchmark {
     };
 }
 
+// Every conversion is paired with the unsafe `to_int_unchecked`, which the crate's documentation claims to compile to the same assembly. The inputs are 0, which is in range, so the unsafe conversion is sound. A gap between the pair indicates that a conversion is slower than it should be.
+macro_rules! create_benchmarks {
+    ($c:ident, $name:ident, $Float:ty, $Integer:ty) => {
+        create_benchmark! {$c, concat!(stringify!($name), "_optimized"), ffti::$name, $Float}
+        create_benchmark! {
+            $c,
+            concat!(stringify!($name), "_unchecked"),
+            |float: $Float| unsafe { float.to_int_unchecked::<$Integer>() },
+            $Float
+        }
+    };
+}
+
 pub fn benchmark(c: &mut Criterion) {
     let mut group = c.benchmark_group("complex");
     group
@@ -31,28 +49,215 @@ pub fn benchmark(c: &mut Criterion) {
         .warm_up_time(Duration::from_secs_f32(0.1))
         .nresamples(1);
 
-    create_benchmark! {group, "f32_to_i8_optimized", ffti::f32_to_i8, f32}
-    create_benchmark! {group, "f32_to_u8_optimized", ffti::f32_to_u8, f32}
-    create_benchmark! {group, "f32_to_i16_optimized", ffti::f32_to_i16, f32}
-    create_benchmark! {group, "f32_to_u16_optimized", ffti::f32_to_u16, f32}
-    create_benchmark! {group, "f32_to_i32_optimized", ffti::f32_to_i32, f32}
-    create_benchmark! {group, "f32_to_u32_optimized", ffti::f32_to_u32, f32}
-    create_benchmark! {group, "f32_to_i64_optimized", ffti::f32_to_i64, f32}
-    create_benchmark! {group, "f32_to_u64_optimized", ffti::f32_to_u64, f32}
-    create_benchmark! {group, "f32_to_i128_optimized", ffti::f32_to_i128, f32}
-    create_benchmark! {group, "f32_to_u128_optimized", ffti::f32_to_u128, f32}
-
-    create_benchmark! {group, "f64_to_i8_optimized", ffti::f64_to_i8, f64}
-    create_benchmark! {group, "f64_to_u8_optimized", ffti::f64_to_u8, f64}
-    create_benchmark! {group, "f64_to_i16_optimized", ffti::f64_to_i16, f64}
-    create_benchmark! {group, "f64_to_u16_optimized", ffti::f64_to_u16, f64}
-    create_benchmark! {group, "f64_to_i32_optimized", ffti::f64_to_i32, f64}
-    create_benchmark! {group, "f64_to_u32_optimized", ffti::f64_to_u32, f64}
-    create_benchmark! {group, "f64_to_i64_optimized", ffti::f64_to_i64, f64}
-    create_benchmark! {group, "f64_to_u64_optimized", ffti::f64_to_u64, f64}
-    create_benchmark! {group, "f64_to_i128_optimized", ffti::f64_to_i128, f64}
-    create_benchmark! {group, "f64_to_u128_optimized", ffti::f64_to_u128, f64}
-}
-
-criterion_group!(benches, benchmark);
+    create_benchmarks! {group, f32_to_i8, f32, i8}
+    create_benchmarks! {group, f32_to_u8, f32, u8}
+    create_benchmarks! {group, f32_to_i16, f32, i16}
+    create_benchmarks! {group, f32_to_u16, f32, u16}
+    create_benchmarks! {group, f32_to_i32, f32, i32}
+    create_benchmarks! {group, f32_to_u32, f32, u32}
+    create_benchmarks! {group, f32_to_i64, f32, i64}
+    create_benchmarks! {group, f32_to_u64, f32, u64}
+    create_benchmarks! {group, f32_to_i128, f32, i128}
+    create_benchmarks! {group, f32_to_u128, f32, u128}
+
+    create_benchmarks! {group, f64_to_i8, f64, i8}
+    create_benchmarks! {group, f64_to_u8, f64, u8}
+    create_benchmarks! {group, f64_to_i16, f64, i16}
+    create_benchmarks! {group, f64_to_u16, f64, u16}
+    create_benchmarks! {group, f64_to_i32, f64, i32}
+    create_benchmarks! {group, f64_to_u32, f64, u32}
+    create_benchmarks! {group, f64_to_i64, f64, i64}
+    create_benchmarks! {group, f64_to_u64, f64, u64}
+    create_benchmarks! {group, f64_to_i128, f64, i128}
+    create_benchmarks! {group, f64_to_u128, f64, u128}
+}
+
+// The 128 bit conversions have no hardware instruction on most targets. The standard conversion calls into compiler_builtins, whose cost depends on the magnitude of the input. Every target uses the standard conversion for them, so there is no optimized series. These are the baseline numbers with small and huge inputs that an optimized implementation has to beat.
+macro_rules! create_128_bit_benchmarks {
+    ($c:ident, $name:ident, $Float:ty, $Integer:ty, $huge:expr) => {
+        for (magnitude, value) in [("small", 1.5 as $Float), ("huge", $huge)] {
+            create_benchmark! {
+                $c,
+                format!("{}_{magnitude}_standard", stringify!($name)),
+                |float: $Float| float as $Integer,
+                $Float,
+                value
+            }
+        }
+    };
+}
+
+// The saturating conversions are paired with the standard conversion, which also saturates. The inputs are out of range to exercise the saturation.
+macro_rules! create_saturating_benchmarks {
+    ($c:ident, $name:ident, $Float:ty, $Integer:ty) => {
+        create_benchmark! {
+            $c,
+            concat!(stringify!($name), "_optimized"),
+            ffti::$name,
+            $Float,
+            -1e30 as $Float
+        }
+        create_benchmark! {
+            $c,
+            concat!(stringify!($name), "_standard"),
+            |float: $Float| float as $Integer,
+            $Float,
+            -1e30 as $Float
+        }
+    };
+}
+
+pub fn benchmark_128_bit(c: &mut Criterion) {
+    let mut group = c.benchmark_group("128_bit");
+    group
+        .sample_size(10_000)
+        .measurement_time(Duration::from_secs_f32(1.0))
+        .warm_up_time(Duration::from_secs_f32(0.1))
+        .nresamples(1);
+
+    create_128_bit_benchmarks! {group, f32_to_i128, f32, i128, -1e38}
+    create_128_bit_benchmarks! {group, f32_to_u128, f32, u128, 3e38}
+    create_128_bit_benchmarks! {group, f64_to_i128, f64, i128, -1e38}
+    create_128_bit_benchmarks! {group, f64_to_u128, f64, u128, 3e38}
+}
+
+pub fn benchmark_saturating(c: &mut Criterion) {
+    let mut group = c.benchmark_group("saturating");
+    group
+        .sample_size(10_000)
+        .measurement_time(Duration::from_secs_f32(1.0))
+        .warm_up_time(Duration::from_secs_f32(0.1))
+        .nresamples(1);
+
+    create_saturating_benchmarks! {group, f32_to_i8_clamp_nearest, f32, i8}
+    create_saturating_benchmarks! {group, f32_to_u8_clamp_nearest, f32, u8}
+    create_saturating_benchmarks! {group, f32_to_i16_clamp_nearest, f32, i16}
+    create_saturating_benchmarks! {group, f32_to_u16_clamp_nearest, f32, u16}
+    create_saturating_benchmarks! {group, f32_to_i32_clamp_nearest, f32, i32}
+    create_saturating_benchmarks! {group, f32_to_u32_clamp_nearest, f32, u32}
+    create_saturating_benchmarks! {group, f32_to_i64_clamp_nearest, f32, i64}
+    create_saturating_benchmarks! {group, f32_to_u64_clamp_nearest, f32, u64}
+    create_saturating_benchmarks! {group, f32_to_i128_clamp_nearest, f32, i128}
+    create_saturating_benchmarks! {group, f32_to_u128_clamp_nearest, f32, u128}
+
+    create_saturating_benchmarks! {group, f64_to_i8_clamp_nearest, f64, i8}
+    create_saturating_benchmarks! {group, f64_to_u8_clamp_nearest, f64, u8}
+    create_saturating_benchmarks! {group, f64_to_i16_clamp_nearest, f64, i16}
+    create_saturating_benchmarks! {group, f64_to_u16_clamp_nearest, f64, u16}
+    create_saturating_benchmarks! {group, f64_to_i32_clamp_nearest, f64, i32}
+    create_saturating_benchmarks! {group, f64_to_u32_clamp_nearest, f64, u32}
+    create_saturating_benchmarks! {group, f64_to_i64_clamp_nearest, f64, i64}
+    create_saturating_benchmarks! {group, f64_to_u64_clamp_nearest, f64, u64}
+    create_saturating_benchmarks! {group, f64_to_i128_clamp_nearest, f64, i128}
+    create_saturating_benchmarks! {group, f64_to_u128_clamp_nearest, f64, u128}
+}
+
+// The slice conversions are fast enough that large buffers are limited by memory bandwidth instead of the conversion. We sweep the size of the source buffer from 4 KiB to 256 MiB in steps of 4, which covers the L1, L2 and L3 caches of common CPUs and main memory. The throughput in bytes of the source buffer drops at the size where the buffers stop fitting into a cache level. The standard conversion is the baseline. When both are equally fast the conversion is memory-bound.
+//
+// `convert_slice_prefetched` prefetches the source ahead of the conversion. Compare it with the optimized conversion at the sizes that do not fit into the caches to see whether software prefetching helps on the machine.
+//
+// The crate writes the output with regular stores. Non-temporal stores bypass the cache and could help past the size where the conversion is memory-bound. That size is what this benchmark measures, so a threshold for such stores should be derived from its results on the target machine, instead of being a hardcoded constant.
+pub fn benchmark_buffer_size(c: &mut Criterion) {
+    let mut group = c.benchmark_group("buffer_size");
+    group
+        .sample_size(10)
+        .measurement_time(Duration::from_secs_f32(1.0))
+        .warm_up_time(Duration::from_secs_f32(0.1))
+        .nresamples(1);
+
+    let mut bytes = 4 << 10;
+    while bytes <= 256 << 20 {
+        let len = bytes / std::mem::size_of::<f32>();
+        let src: Vec<f32> = (0..len).map(|i| i as f32).collect();
+        let mut dst = vec![0i32; len];
+        group.throughput(Throughput::Bytes(bytes as u64));
+        group.bench_with_input(
+            BenchmarkId::new("f32_to_i32_optimized", bytes),
+            &src,
+            |b, src| {
+                b.iter(|| {
+                    ffti::convert_slice(black_box(src.as_slice()), black_box(dst.as_mut_slice()))
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("f32_to_i32_prefetched", bytes),
+            &src,
+            |b, src| {
+                b.iter(|| {
+                    ffti::convert_slice_prefetched(
+                        black_box(src.as_slice()),
+                        black_box(dst.as_mut_slice()),
+                    )
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("f32_to_i32_standard", bytes),
+            &src,
+            |b, src| {
+                b.iter(|| {
+                    for (src, dst) in black_box(src.as_slice())
+                        .iter()
+                        .zip(black_box(dst.as_mut_slice()))
+                    {
+                        *dst = *src as i32;
+                    }
+                })
+            },
+        );
+        bytes *= 4;
+    }
+}
+
+// The f32 and f64 to u64 conversions on x86_64 subtract a constant that the compiler loads from memory. In cold code this load can miss the cache. Every iteration reads an eviction buffer that is larger than the L2 cache of common CPUs before one conversion, so the conversion runs with a cold constant. The `evict` benchmark only reads the buffer and is the baseline that the conversions are compared to.
+//
+// Run the benchmark once with and once without the `register-constants` feature to compare the two ways of building the constant:
+//
+// cargo bench --package benchmark --features register-constants -- cold_u64
+pub fn benchmark_cold_u64(c: &mut Criterion) {
+    let mut group = c.benchmark_group("cold_u64");
+    group
+        .sample_size(1_000)
+        .measurement_time(Duration::from_secs_f32(1.0))
+        .warm_up_time(Duration::from_secs_f32(0.1))
+        .nresamples(1);
+
+    const EVICT_BYTES: usize = 4 << 20;
+    const CACHE_LINE: usize = 64;
+    let evict_buffer = vec![1u8; EVICT_BYTES];
+    let evict = || {
+        let mut sum = 0u8;
+        for byte in black_box(evict_buffer.as_slice())
+            .iter()
+            .step_by(CACHE_LINE)
+        {
+            sum = sum.wrapping_add(*byte);
+        }
+        sum
+    };
+
+    group.bench_function("evict", |b| b.iter(|| black_box(evict())));
+    group.bench_function("f32_to_u64_optimized", |b| {
+        b.iter(|| {
+            black_box(evict());
+            black_box(ffti::f32_to_u64(black_box(1.5)))
+        })
+    });
+    group.bench_function("f64_to_u64_optimized", |b| {
+        b.iter(|| {
+            black_box(evict());
+            black_box(ffti::f64_to_u64(black_box(1.5)))
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    benchmark,
+    benchmark_128_bit,
+    benchmark_saturating,
+    benchmark_buffer_size,
+    benchmark_cold_u64
+);
 criterion_main!(benches);
@@ -0,0 +1,57 @@
+// Use every conversion once. black_box prevents the compiler from removing the conversions.
+
+use std::hint::black_box;
+
+macro_rules! convert {
+    ($name:ident, $Float:ty, $Integer:ty) => {
+        let float: $Float = black_box(0.);
+        #[cfg(feature = "standard")]
+        black_box(float as $Integer);
+        #[cfg(not(feature = "standard"))]
+        black_box(fast_float_to_integer::$name(float));
+    };
+}
+
+macro_rules! convert_slice {
+    ($Float:ty, $Integer:ty) => {
+        let src: [$Float; 64] = black_box([0.; 64]);
+        let mut dst: [$Integer; 64] = [0; 64];
+        #[cfg(feature = "standard")]
+        for (src, dst) in src.iter().zip(dst.iter_mut()) {
+            *dst = *src as $Integer;
+        }
+        #[cfg(not(feature = "standard"))]
+        fast_float_to_integer::convert_slice(&src, &mut dst);
+        black_box(dst);
+    };
+}
+
+fn main() {
+    convert! {f32_to_i8, f32, i8}
+    convert! {f32_to_u8, f32, u8}
+    convert! {f32_to_i16, f32, i16}
+    convert! {f32_to_u16, f32, u16}
+    convert! {f32_to_i32, f32, i32}
+    convert! {f32_to_u32, f32, u32}
+    convert! {f32_to_i64, f32, i64}
+    convert! {f32_to_u64, f32, u64}
+    convert! {f32_to_i128, f32, i128}
+    convert! {f32_to_u128, f32, u128}
+
+    convert! {f64_to_i8, f64, i8}
+    convert! {f64_to_u8, f64, u8}
+    convert! {f64_to_i16, f64, i16}
+    convert! {f64_to_u16, f64, u16}
+    convert! {f64_to_i32, f64, i32}
+    convert! {f64_to_u32, f64, u32}
+    convert! {f64_to_i64, f64, i64}
+    convert! {f64_to_u64, f64, u64}
+    convert! {f64_to_i128, f64, i128}
+    convert! {f64_to_u128, f64, u128}
+
+    // The slice functions with packed kernels.
+    convert_slice! {f32, u8}
+    convert_slice! {f32, u16}
+    convert_slice! {f32, i32}
+    convert_slice! {f64, i32}
+}
@@ -0,0 +1,113 @@
+// Analysis of sample data to help choosing between the conversion variants.
+
+use core::ops::Add;
+
+use crate::FastFrom;
+
+/// How the values of a sample buffer are distributed with respect to the range of an integer type. Created by [`analyze`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DistributionReport {
+    /// The number of analyzed values.
+    pub total: usize,
+    /// The number of values that are in range.
+    pub in_range: usize,
+    /// The number of values that are in range but would be out of range if they were twice as large. These values have little headroom.
+    pub near_threshold: usize,
+    /// The number of values that are out of range, excluding NaN.
+    pub out_of_range: usize,
+    /// The number of NaN values.
+    pub nan: usize,
+}
+
+/// Which conversion variant suits the analyzed data. See [`DistributionReport::recommendation`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Recommendation {
+    /// Every value is in range. The fast conversions like [`f32_to_i32`](crate::f32_to_i32) give the same results as the standard conversion.
+    Fast,
+    /// Some values are out of range but none are NaN. The `clamp_nearest` conversions like [`f32_to_i32_clamp_nearest`](crate::f32_to_i32_clamp_nearest) give the same results as the standard conversion.
+    ClampNearest,
+    /// Some values are NaN. Only the standard conversion or [`saturating_convert_slice`](crate::saturating_convert_slice) give defined results.
+    Saturating,
+}
+
+impl DistributionReport {
+    /// The fraction of values that are in range. 0 if there are no values.
+    pub fn fraction_in_range(&self) -> f64 {
+        self.fraction(self.in_range)
+    }
+
+    /// The fraction of values that are near the threshold. 0 if there are no values.
+    pub fn fraction_near_threshold(&self) -> f64 {
+        self.fraction(self.near_threshold)
+    }
+
+    /// The fraction of values that are out of range, excluding NaN. 0 if there are no values.
+    pub fn fraction_out_of_range(&self) -> f64 {
+        self.fraction(self.out_of_range)
+    }
+
+    /// The fraction of values that are NaN. 0 if there are no values.
+    pub fn fraction_nan(&self) -> f64 {
+        self.fraction(self.nan)
+    }
+
+    /// The cheapest conversion variant that gives the same results as the standard conversion for the analyzed values.
+    ///
+    /// The recommendation only holds for the sample. If your real data can contain other values, take that into account. Values near the threshold are a hint that the real data might exceed the range.
+    pub fn recommendation(&self) -> Recommendation {
+        if self.nan > 0 {
+            Recommendation::Saturating
+        } else if self.out_of_range > 0 {
+            Recommendation::ClampNearest
+        } else {
+            Recommendation::Fast
+        }
+    }
+
+    fn fraction(&self, count: usize) -> f64 {
+        if self.total == 0 {
+            0.
+        } else {
+            count as f64 / self.total as f64
+        }
+    }
+}
+
+/// Analyze how the values of a sample buffer are distributed with respect to the range of the integer type.
+///
+/// ```
+/// use fast_float_to_integer::{analyze, Recommendation};
+///
+/// let report = analyze::<f32, u8>(&[1.0, 200.0, 300.0]);
+/// assert_eq!(report.in_range, 2);
+/// assert_eq!(report.near_threshold, 1);
+/// assert_eq!(report.out_of_range, 1);
+/// assert_eq!(report.recommendation(), Recommendation::ClampNearest);
+/// ```
+pub fn analyze<Float, Integer>(src: &[Float]) -> DistributionReport
+where
+    Float: Copy + PartialEq + Add<Output = Float>,
+    Integer: FastFrom<Float>,
+{
+    let mut report = DistributionReport {
+        total: src.len(),
+        ..Default::default()
+    };
+    for float in src.iter().copied() {
+        // Only NaN is not equal to itself.
+        #[allow(clippy::eq_op)]
+        let is_nan = float != float;
+        if is_nan {
+            report.nan += 1;
+        } else if Integer::in_range(float) {
+            report.in_range += 1;
+            if !Integer::in_range(float + float) {
+                report.near_threshold += 1;
+            }
+        } else {
+            report.out_of_range += 1;
+        }
+    }
+    report
+}
@@ -0,0 +1,51 @@
+// Conversions to the integer types of the arbitrary-int crate, like the 12 bit fields of protocols and hardware registers.
+
+use arbitrary_int::traits::Integer;
+
+use crate::*;
+
+/// Evaluating the constant fails at compile time if the values of the type do not fit in i64, which the conversions clamp in.
+struct Width<T>(core::marker::PhantomData<T>);
+
+impl<T: Integer> Width<T> {
+    const FITS_I64: () = assert!(
+        T::BITS < 64 || (T::BITS == 64 && T::IS_SIGNED),
+        "the values of the type do not fit in i64"
+    );
+}
+
+macro_rules! create_function {
+    ($name:ident, $Float:ty, $i32_clamp_nearest:ident, $i64_clamp_nearest:ident) => {
+        #[doc = concat!("Convert the input ", stringify!($Float), " value to an integer type of the arbitrary-int crate like `u12` or `i4` with saturation.")]
+        ///
+        /// Out of range inputs, including infinities, convert to the minimum or maximum of the type. If the input is NaN, then the result is unspecified but a valid value. Otherwise, the result is the same as the standard `as` conversion to a primitive integer of the same width. Types whose values do not fit in i64, like `u64`, are a compile time error.
+        ///
+        /// Widths up to 31 bits convert through i32, which is faster than i64 on 32 bit targets.
+        ///
+        /// ```
+        /// use arbitrary_int::{i4, u12};
+        ///
+        #[doc = concat!("assert_eq!(fast_float_to_integer::", stringify!($name), "::<u12>(1e6), u12::new(4095));")]
+        #[doc = concat!("assert_eq!(fast_float_to_integer::", stringify!($name), "::<u12>(100.5), u12::new(100));")]
+        #[doc = concat!("assert_eq!(fast_float_to_integer::", stringify!($name), "::<i4>(-100.), i4::new(-8));")]
+        /// ```
+        #[cfg_attr(feature = "show-asm", inline(never))]
+        #[cfg_attr(not(feature = "show-asm"), inline(always))]
+        pub fn $name<T: Integer>(float: $Float) -> T {
+            #[allow(clippy::let_unit_value)]
+            let () = Width::<T>::FITS_I64;
+            // The bounds are constants after inlining.
+            let (min, max) = (T::MIN.as_i64(), T::MAX.as_i64());
+            let integer = if T::BITS <= 31 {
+                ($i32_clamp_nearest(float) as i64).clamp(min, max)
+            } else {
+                $i64_clamp_nearest(float).clamp(min, max)
+            };
+            // The value is in range, so masking does not change it.
+            T::masked_new(integer)
+        }
+    };
+}
+
+create_function! {f32_to_arbitrary_int, f32, f32_to_i32_clamp_nearest, f32_to_i64_clamp_nearest}
+create_function! {f64_to_arbitrary_int, f64, f64_to_i32_clamp_nearest, f64_to_i64_clamp_nearest}
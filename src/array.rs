@@ -0,0 +1,61 @@
+// Conversions of small arrays like the vectors and colors of game math. The functions for fixed sizes are unrolled by hand. LLVM does not always fully unroll the loop of `convert_array` at opt-level 2, which leaves a loop with a branch for two or three elements. See `generated assembly/*/array`.
+
+use crate::*;
+
+/// Convert every element of the array.
+///
+/// This is `floats.map(Integer::fast_from)`. For the sizes 2, 3, 4 and 8 prefer the unrolled functions like [`f32_to_i32_x4`], which never contain a loop.
+///
+/// ```
+/// let integers: [i32; 3] = fast_float_to_integer::convert_array([1.5f32, -2.5, 3.0]);
+/// assert_eq!(integers, [1, -2, 3]);
+/// ```
+#[inline]
+pub fn convert_array<Float, Integer, const N: usize>(floats: [Float; N]) -> [Integer; N]
+where
+    Integer: FastFrom<Float>,
+{
+    floats.map(Integer::fast_from)
+}
+
+macro_rules! create_functions {
+    ($convert:ident, $Float:ty, $Integer:ty, $x2:ident, $x3:ident, $x4:ident, $x8:ident) => {
+        create_functions! {@function $convert, $Float, $Integer, $x2, 2, [0 1]}
+        create_functions! {@function $convert, $Float, $Integer, $x3, 3, [0 1 2]}
+        create_functions! {@function $convert, $Float, $Integer, $x4, 4, [0 1 2 3]}
+        create_functions! {@function $convert, $Float, $Integer, $x8, 8, [0 1 2 3 4 5 6 7]}
+    };
+    (@function $convert:ident, $Float:ty, $Integer:ty, $name:ident, $len:literal, [$($index:literal)*]) => {
+        #[doc = concat!("Convert every element of the array like [`", stringify!($convert), "`].")]
+        ///
+        /// The conversions are unrolled, so there is no loop.
+        #[cfg_attr(feature = "show-asm", inline(never))]
+        #[cfg_attr(not(any(feature = "show-asm", feature = "small-code")), inline(always))]
+        pub fn $name(floats: [$Float; $len]) -> [$Integer; $len] {
+            // The public function is not inlined with the show-asm feature.
+            [$(active_target::implementation::$convert(floats[$index])),*]
+        }
+    };
+}
+
+create_functions! {f32_to_i8, f32, i8, f32_to_i8_x2, f32_to_i8_x3, f32_to_i8_x4, f32_to_i8_x8}
+create_functions! {f32_to_u8, f32, u8, f32_to_u8_x2, f32_to_u8_x3, f32_to_u8_x4, f32_to_u8_x8}
+create_functions! {f32_to_i16, f32, i16, f32_to_i16_x2, f32_to_i16_x3, f32_to_i16_x4, f32_to_i16_x8}
+create_functions! {f32_to_u16, f32, u16, f32_to_u16_x2, f32_to_u16_x3, f32_to_u16_x4, f32_to_u16_x8}
+create_functions! {f32_to_i32, f32, i32, f32_to_i32_x2, f32_to_i32_x3, f32_to_i32_x4, f32_to_i32_x8}
+create_functions! {f32_to_u32, f32, u32, f32_to_u32_x2, f32_to_u32_x3, f32_to_u32_x4, f32_to_u32_x8}
+create_functions! {f32_to_i64, f32, i64, f32_to_i64_x2, f32_to_i64_x3, f32_to_i64_x4, f32_to_i64_x8}
+create_functions! {f32_to_u64, f32, u64, f32_to_u64_x2, f32_to_u64_x3, f32_to_u64_x4, f32_to_u64_x8}
+create_functions! {f32_to_i128, f32, i128, f32_to_i128_x2, f32_to_i128_x3, f32_to_i128_x4, f32_to_i128_x8}
+create_functions! {f32_to_u128, f32, u128, f32_to_u128_x2, f32_to_u128_x3, f32_to_u128_x4, f32_to_u128_x8}
+
+create_functions! {f64_to_i8, f64, i8, f64_to_i8_x2, f64_to_i8_x3, f64_to_i8_x4, f64_to_i8_x8}
+create_functions! {f64_to_u8, f64, u8, f64_to_u8_x2, f64_to_u8_x3, f64_to_u8_x4, f64_to_u8_x8}
+create_functions! {f64_to_i16, f64, i16, f64_to_i16_x2, f64_to_i16_x3, f64_to_i16_x4, f64_to_i16_x8}
+create_functions! {f64_to_u16, f64, u16, f64_to_u16_x2, f64_to_u16_x3, f64_to_u16_x4, f64_to_u16_x8}
+create_functions! {f64_to_i32, f64, i32, f64_to_i32_x2, f64_to_i32_x3, f64_to_i32_x4, f64_to_i32_x8}
+create_functions! {f64_to_u32, f64, u32, f64_to_u32_x2, f64_to_u32_x3, f64_to_u32_x4, f64_to_u32_x8}
+create_functions! {f64_to_i64, f64, i64, f64_to_i64_x2, f64_to_i64_x3, f64_to_i64_x4, f64_to_i64_x8}
+create_functions! {f64_to_u64, f64, u64, f64_to_u64_x2, f64_to_u64_x3, f64_to_u64_x4, f64_to_u64_x8}
+create_functions! {f64_to_i128, f64, i128, f64_to_i128_x2, f64_to_i128_x3, f64_to_i128_x4, f64_to_i128_x8}
+create_functions! {f64_to_u128, f64, u128, f64_to_u128_x2, f64_to_u128_x3, f64_to_u128_x4, f64_to_u128_x8}
@@ -0,0 +1,81 @@
+// Conversions of audio buffers. Audio samples are f32 in [-1, 1] and many audio devices take interleaved i16 samples.
+
+use crate::kernel::{self, StereoKernel};
+
+/// The lane count of the stereo kernel. The SSE2 kernel has four lanes. The scalar kernel works with any lane count.
+const LANES: usize = 4;
+
+/// Scaling by i16::MAX maps [-1, 1] to [-32767, 32767].
+const SCALE: f32 = i16::MAX as f32;
+
+/// Scale and convert the frames in one pass with the stereo kernel. `prepare` maps each input sample to the value that is converted.
+#[inline(always)]
+fn convert_stereo(left: &[f32], right: &[f32], out: &mut [i16], prepare: impl Fn(f32) -> f32) {
+    assert_eq!(left.len(), right.len(), "slices have different lengths");
+    assert_eq!(
+        out.len(),
+        left.len() * 2,
+        "output slice is not twice as long as input slices"
+    );
+    let mut left = left.chunks_exact(LANES);
+    let mut right = right.chunks_exact(LANES);
+    let mut out = out.chunks_exact_mut(2 * LANES);
+    for ((left, right), out) in (&mut left).zip(&mut right).zip(&mut out) {
+        let left: [f32; LANES] = core::array::from_fn(|index| prepare(left[index]));
+        let right: [f32; LANES] = core::array::from_fn(|index| prepare(right[index]));
+        let frames =
+            <kernel::StereoF32ToI16 as StereoKernel<LANES>>::convert_interleaved(left, right);
+        for (out, frame) in out.chunks_exact_mut(2).zip(frames) {
+            out.copy_from_slice(&frame);
+        }
+    }
+    // The remainder goes through the kernel too. The scalar conversion can give different results for out of range samples, like on x86_64 where it converts through i64, and then the result of a sample would depend on its position.
+    let (left, right) = (left.remainder(), right.remainder());
+    let left: [f32; LANES] =
+        core::array::from_fn(|index| left.get(index).map_or(0., |sample| prepare(*sample)));
+    let right: [f32; LANES] =
+        core::array::from_fn(|index| right.get(index).map_or(0., |sample| prepare(*sample)));
+    let frames = <kernel::StereoF32ToI16 as StereoKernel<LANES>>::convert_interleaved(left, right);
+    for (out, frame) in out.into_remainder().chunks_exact_mut(2).zip(frames) {
+        out.copy_from_slice(&frame);
+    }
+}
+
+/// Convert the samples of a stereo signal to interleaved i16 samples.
+///
+/// `out[2 * i]` is the converted `left[i]` and `out[2 * i + 1]` is the converted `right[i]`. Every sample is clamped to [-1, 1], multiplied by 32767 and converted like [`f32_to_i16`](crate::f32_to_i16). If a sample is NaN, then its result is unspecified.
+///
+/// Scaling, clamping, converting and interleaving happen in one pass. On x86 with SSE2 the interleaving uses the unpack instructions. On aarch64 with NEON it uses the ST2 instruction.
+///
+/// # Panics
+///
+/// Panics if `left` and `right` have different lengths or if `out` is not twice as long as them.
+///
+/// ```
+/// let left = [0.5f32, -1.5];
+/// let right = [-0.5f32, 1.0];
+/// let mut out = [0i16; 4];
+/// fast_float_to_integer::convert_stereo_f32_to_i16_interleaved(&left, &right, &mut out);
+/// assert_eq!(out, [16383, -16383, -32767, 32767]);
+/// ```
+#[inline]
+pub fn convert_stereo_f32_to_i16_interleaved(left: &[f32], right: &[f32], out: &mut [i16]) {
+    // NaN passes through the clamping. It is unspecified, so that does not matter.
+    convert_stereo(left, right, out, |sample| sample.clamp(-1., 1.) * SCALE)
+}
+
+/// Like [`convert_stereo_f32_to_i16_interleaved`] but without clamping.
+///
+/// If a scaled sample is out of range of i16, then its result is unspecified. It is the same at every position in the slices. Use this when the samples are known to be in [-1, 1].
+///
+/// # Panics
+///
+/// Panics if `left` and `right` have different lengths or if `out` is not twice as long as them.
+#[inline]
+pub fn convert_stereo_f32_to_i16_interleaved_unclamped(
+    left: &[f32],
+    right: &[f32],
+    out: &mut [i16],
+) {
+    convert_stereo(left, right, out, |sample| sample * SCALE)
+}
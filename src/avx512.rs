@@ -0,0 +1,41 @@
+// Conversions with AVX-512 for applications that detect the CPU at runtime.
+//
+// The target modules only use AVX-512 when the whole crate is compiled with the target feature. Applications that ship one binary for many CPUs detect the feature at runtime and call into functions annotated with `#[target_feature]`. Conversions that are inlined into such a function can use the feature, but the safe conversions of this crate are compiled without it. The functions here are annotated themselves so that they get the better instructions independent of how the crate is compiled.
+//
+// The avx512f target feature and the intrinsics are stable since Rust 1.89, later than the minimum supported Rust version of the crate. That is why they are behind the `avx512` feature.
+
+#![allow(clippy::incompatible_msrv)]
+
+use core::arch::x86_64::{
+    _mm_cvttsd_u32, _mm_cvttsd_u64, _mm_cvttss_u32, _mm_cvttss_u64, _mm_set_sd, _mm_set_ss,
+};
+
+macro_rules! create_function {
+    ($name:ident, $Float:ty, $Integer:ty, $set:ident, $convert:ident) => {
+        #[doc = concat!("Convert ", stringify!($Float), " to ", stringify!($Integer), " with the AVX-512 instructions that convert to unsigned integers directly.")]
+        ///
+        /// If the input is out of range of the output type, then the result is unspecified.
+        ///
+        /// Call this function from your own function with `#[target_feature(enable = "avx512f")]`, so that it is inlined without a call.
+        ///
+        /// # Safety
+        ///
+        /// The CPU must support the avx512f target feature, for example as checked by `is_x86_feature_detected!("avx512f")`.
+        #[target_feature(enable = "avx512f")]
+        #[inline]
+        pub unsafe fn $name(float: $Float) -> $Integer {
+            // For u8 and u16 we clamp the u32 result like the target module does with the avx512f target feature. For u32 and u64 the clamp does nothing and compiles to nothing.
+            $convert($set(float)).min(<$Integer>::MAX as _) as $Integer
+        }
+    };
+}
+
+create_function! {f32_to_u8_avx512, f32, u8, _mm_set_ss, _mm_cvttss_u32}
+create_function! {f32_to_u16_avx512, f32, u16, _mm_set_ss, _mm_cvttss_u32}
+create_function! {f32_to_u32_avx512, f32, u32, _mm_set_ss, _mm_cvttss_u32}
+create_function! {f32_to_u64_avx512, f32, u64, _mm_set_ss, _mm_cvttss_u64}
+
+create_function! {f64_to_u8_avx512, f64, u8, _mm_set_sd, _mm_cvttsd_u32}
+create_function! {f64_to_u16_avx512, f64, u16, _mm_set_sd, _mm_cvttsd_u32}
+create_function! {f64_to_u32_avx512, f64, u32, _mm_set_sd, _mm_cvttsd_u32}
+create_function! {f64_to_u64_avx512, f64, u64, _mm_set_sd, _mm_cvttsd_u64}
@@ -0,0 +1,31 @@
+//! The target specific implementations.
+//!
+//! Every module that is available for the compilation target is here, independent of which one the crate uses. The modules have the same functions as the crate root, the kernels of the target in their `kernel` module and their own `behavior` and `is_specialized` functions. This module is not covered by semver.
+
+/// The standard `as` operator conversion. Always available.
+pub mod default {
+    pub use crate::target_default::{behavior, implementation::*, is_specialized, kernel};
+}
+
+/// The conversions from the IEEE 754 bit pattern with integer arithmetic that the `soft-float` feature uses. Always available.
+pub mod soft_float {
+    pub use crate::software::soft_float::*;
+}
+
+/// Available with `target_arch = "x86_64", target_feature = "sse"`.
+#[cfg(all(target_arch = "x86_64", target_feature = "sse"))]
+pub mod x86_64_sse {
+    pub use crate::target_x86_64_sse::{behavior, implementation::*, is_specialized, kernel};
+}
+
+/// Available with `target_arch = "x86", target_feature = "sse"`.
+#[cfg(all(target_arch = "x86", target_feature = "sse"))]
+pub mod x86_sse {
+    pub use crate::target_x86_sse::{behavior, implementation::*, is_specialized, kernel};
+}
+
+/// Available with `target_arch = "aarch64", target_feature = "neon"`.
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+pub mod aarch64_neon {
+    pub use crate::target_aarch64_neon::{behavior, implementation::*, is_specialized, kernel};
+}
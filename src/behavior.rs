@@ -0,0 +1,125 @@
+// Machine readable description of what the conversions do outside of the range of the output type.
+
+/// Identifies one of the conversion functions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Conversion {
+    F32ToI8,
+    F32ToU8,
+    F32ToI16,
+    F32ToU16,
+    F32ToI32,
+    F32ToU32,
+    F32ToI64,
+    F32ToU64,
+    F32ToI128,
+    F32ToU128,
+    F64ToI8,
+    F64ToU8,
+    F64ToI16,
+    F64ToU16,
+    F64ToI32,
+    F64ToU32,
+    F64ToI64,
+    F64ToU64,
+    F64ToI128,
+    F64ToU128,
+}
+
+impl Conversion {
+    /// Every conversion in the order of the declaration.
+    pub const ALL: [Conversion; 20] = [
+        Conversion::F32ToI8,
+        Conversion::F32ToU8,
+        Conversion::F32ToI16,
+        Conversion::F32ToU16,
+        Conversion::F32ToI32,
+        Conversion::F32ToU32,
+        Conversion::F32ToI64,
+        Conversion::F32ToU64,
+        Conversion::F32ToI128,
+        Conversion::F32ToU128,
+        Conversion::F64ToI8,
+        Conversion::F64ToU8,
+        Conversion::F64ToI16,
+        Conversion::F64ToU16,
+        Conversion::F64ToI32,
+        Conversion::F64ToU32,
+        Conversion::F64ToI64,
+        Conversion::F64ToU64,
+        Conversion::F64ToI128,
+        Conversion::F64ToU128,
+    ];
+}
+
+/// What a conversion function does for inputs outside of the range of the output type.
+///
+/// This describes the conversion functions like [`f32_to_i64`](crate::f32_to_i64) for the active target of the current build. The documentation of the functions only promises an unspecified value. This is what you actually get.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Behavior {
+    /// The result for finite and infinite inputs that are out of range.
+    pub out_of_range: OutOfRange,
+    /// The result for NaN.
+    pub nan: Nan,
+}
+
+/// The result of a conversion for inputs that are out of range. See [`Behavior`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutOfRange {
+    /// An arbitrary value that can depend on the input.
+    Unspecified,
+    /// The minimal/maximal value of the output type like the standard `as` operator.
+    Saturating,
+    /// Always this value, converted to i128.
+    Sentinel(i128),
+}
+
+/// The result of a conversion for NaN. See [`Behavior`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Nan {
+    /// An arbitrary value.
+    Unspecified,
+    /// 0 like the standard `as` operator.
+    Zero,
+    /// Always this value, converted to i128.
+    Sentinel(i128),
+}
+
+impl Behavior {
+    /// The behavior of the standard `as` operator.
+    // Unused with the portable-deterministic feature.
+    #[allow(dead_code)]
+    pub(crate) const SATURATING: Behavior = Behavior {
+        out_of_range: OutOfRange::Saturating,
+        nan: Nan::Zero,
+    };
+}
+
+/// Describe what the conversion does for inputs outside of the range of the output type with the active target of the current build.
+///
+/// ```
+/// use fast_float_to_integer::{behavior, Conversion, OutOfRange};
+///
+/// let behavior = behavior(Conversion::F32ToI64);
+/// if behavior.out_of_range == OutOfRange::Saturating {
+///     assert_eq!(fast_float_to_integer::f32_to_i64(1e30), i64::MAX);
+/// }
+/// ```
+pub const fn behavior(conversion: Conversion) -> Behavior {
+    crate::active_target::behavior(conversion)
+}
+
+/// Whether the active target of the current build has a specialized implementation of the conversion or of its rounding conversions like [`f32_to_i32_floor`](crate::f32_to_i32_floor).
+///
+/// If this is false, then the conversion is not faster than the standard `as` operator. It usually is the standard `as` operator. Because this is a const fn, you can use it to select an alternative algorithm at compile time.
+///
+/// ```
+/// use fast_float_to_integer::{is_specialized, Conversion};
+///
+/// // The 128 bit conversions use the standard conversion on every target.
+/// const SPECIALIZED: bool = is_specialized(Conversion::F32ToU128);
+/// assert!(!SPECIALIZED);
+/// ```
+pub const fn is_specialized(conversion: Conversion) -> bool {
+    crate::active_target::is_specialized(conversion)
+}
@@ -0,0 +1,135 @@
+// Conversions from the IEEE 754 bit pattern of a float. Data from network protocols or memory mapped sensor registers often arrives as integers.
+//
+// With an FPU, moving the bits into a float register and converting is the fastest. Without an FPU, the float conversion is a call into compiler_builtins and the standard `as` operator adds more calls for the comparisons of the saturation. The soft-float feature decodes the bits with integer arithmetic instead.
+
+use core::{iter::FusedIterator, marker::PhantomData};
+
+use crate::*;
+
+/// Convert from the IEEE 754 bit pattern of a float to this integer type like the `*_bits_to_*` functions.
+///
+/// `i64::fast_from_bits(bits)` is the same as `f32_bits_to_i64(bits)` if `bits` is a u32 and the same as `f64_bits_to_i64(bits)` if `bits` is a u64. The trait exists so that you can write code that is generic over the conversions, like [`convert_bits`]. It is implemented for every such function and cannot be implemented outside of this crate.
+pub trait FastFromBits<Bits>: sealed::FromBits<Bits> + IntType + Copy {
+    /// Convert the float with the IEEE 754 bit pattern `bits` to this integer type.
+    fn fast_from_bits(bits: Bits) -> Self;
+}
+
+macro_rules! create_function {
+    ($name:ident, $convert:ident, $Float:ty, $Bits:ty, $Integer:ty) => {
+        /// Convert the float with the IEEE 754 bit pattern `bits` to the output integer type.
+        ///
+        #[doc = concat!("The result is the same as [`", stringify!($convert), "`] of `", stringify!($Float), "::from_bits(bits)`. If the float is out of range of the output type, then the result is unspecified.")]
+        #[inline(always)]
+        pub fn $name(bits: $Bits) -> $Integer {
+            if cfg!(feature = "soft-float") {
+                crate::software::soft_float::$name(bits)
+            } else {
+                $convert(<$Float>::from_bits(bits))
+            }
+        }
+
+        impl sealed::FromBits<$Bits> for $Integer {}
+
+        impl FastFromBits<$Bits> for $Integer {
+            #[inline(always)]
+            fn fast_from_bits(bits: $Bits) -> Self {
+                $name(bits)
+            }
+        }
+    };
+}
+
+create_function! {f32_bits_to_i8, f32_to_i8, f32, u32, i8}
+create_function! {f32_bits_to_u8, f32_to_u8, f32, u32, u8}
+create_function! {f32_bits_to_i16, f32_to_i16, f32, u32, i16}
+create_function! {f32_bits_to_u16, f32_to_u16, f32, u32, u16}
+create_function! {f32_bits_to_i32, f32_to_i32, f32, u32, i32}
+create_function! {f32_bits_to_u32, f32_to_u32, f32, u32, u32}
+create_function! {f32_bits_to_i64, f32_to_i64, f32, u32, i64}
+create_function! {f32_bits_to_u64, f32_to_u64, f32, u32, u64}
+create_function! {f32_bits_to_i128, f32_to_i128, f32, u32, i128}
+create_function! {f32_bits_to_u128, f32_to_u128, f32, u32, u128}
+
+create_function! {f64_bits_to_i8, f64_to_i8, f64, u64, i8}
+create_function! {f64_bits_to_u8, f64_to_u8, f64, u64, u8}
+create_function! {f64_bits_to_i16, f64_to_i16, f64, u64, i16}
+create_function! {f64_bits_to_u16, f64_to_u16, f64, u64, u16}
+create_function! {f64_bits_to_i32, f64_to_i32, f64, u64, i32}
+create_function! {f64_bits_to_u32, f64_to_u32, f64, u64, u32}
+create_function! {f64_bits_to_i64, f64_to_i64, f64, u64, i64}
+create_function! {f64_bits_to_u64, f64_to_u64, f64, u64, u64}
+create_function! {f64_bits_to_i128, f64_to_i128, f64, u64, i128}
+create_function! {f64_bits_to_u128, f64_to_u128, f64, u64, u128}
+
+/// An iterator that converts the IEEE 754 bit patterns of another iterator. Returned by [`convert_bits`].
+#[derive(Clone, Debug)]
+pub struct ConvertedBits<Source, Integer> {
+    source: Source,
+    _marker: PhantomData<fn() -> Integer>,
+}
+
+/// Convert an iterator of IEEE 754 bit patterns to integers like the `*_bits_to_*` functions.
+///
+/// The bit patterns are u32 for f32 and u64 for f64. Every element is converted when the iterator reaches it, so a streaming decoder can read packed float fields from a bitstream and convert them without a buffer of floats. With the `soft-float` feature the bits are decoded with integer arithmetic like in [`f32_bits_to_i32`].
+///
+/// If a float is out of range of the output type, then its result is unspecified.
+///
+/// ```
+/// let fields = [1.5f32, -2.5, 300.0].map(f32::to_bits);
+/// let integers: Vec<i16> = fast_float_to_integer::convert_bits(fields).collect();
+/// assert_eq!(integers, [1, -2, 300]);
+/// ```
+#[inline]
+pub fn convert_bits<Integer, Source>(source: Source) -> ConvertedBits<Source::IntoIter, Integer>
+where
+    Source: IntoIterator,
+    Integer: FastFromBits<Source::Item>,
+{
+    ConvertedBits {
+        source: source.into_iter(),
+        _marker: PhantomData,
+    }
+}
+
+impl<Source, Integer> Iterator for ConvertedBits<Source, Integer>
+where
+    Source: Iterator,
+    Integer: FastFromBits<Source::Item>,
+{
+    type Item = Integer;
+
+    #[inline]
+    fn next(&mut self) -> Option<Integer> {
+        self.source.next().map(Integer::fast_from_bits)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.source.size_hint()
+    }
+}
+
+impl<Source, Integer> DoubleEndedIterator for ConvertedBits<Source, Integer>
+where
+    Source: DoubleEndedIterator,
+    Integer: FastFromBits<Source::Item>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Integer> {
+        self.source.next_back().map(Integer::fast_from_bits)
+    }
+}
+
+impl<Source, Integer> ExactSizeIterator for ConvertedBits<Source, Integer>
+where
+    Source: ExactSizeIterator,
+    Integer: FastFromBits<Source::Item>,
+{
+}
+
+impl<Source, Integer> FusedIterator for ConvertedBits<Source, Integer>
+where
+    Source: FusedIterator,
+    Integer: FastFromBits<Source::Item>,
+{
+}
@@ -0,0 +1,105 @@
+// Slice conversions that report every out of range element instead of stopping at the first. Data validation jobs need the full report of a column in one pass.
+
+use crate::{FastFrom, FloatType};
+
+/// Convert `src` into `dst` and pass the offset of each chunk of 64 elements together with a bitmap of its out of range elements to `f`.
+#[inline(always)]
+fn convert_checked_chunks<Float, Integer>(
+    src: &[Float],
+    dst: &mut [Integer],
+    mut f: impl FnMut(usize, u64),
+) where
+    Float: FloatType,
+    Integer: FastFrom<Float>,
+{
+    assert_eq!(src.len(), dst.len(), "slices have different lengths");
+    // The chunks have 64 elements so that the bits of a chunk fit into a u64 and the chunks start at the beginning of a byte.
+    for (index, (src, dst)) in src.chunks(64).zip(dst.chunks_mut(64)).enumerate() {
+        // The loop has no branches, so the compiler can compare several elements at once and extract the comparison results as a mask.
+        let mut invalid = 0u64;
+        for (bit, float) in src.iter().enumerate() {
+            invalid |= (!Integer::in_range(*float) as u64) << bit;
+        }
+        Integer::convert_slice(src, dst);
+        f(index * 64, invalid);
+    }
+}
+
+/// Convert a slice and mark every element that is out of range of the output type or NaN in a bitmap. Return the number of such elements.
+///
+/// The elements are converted like [`convert_slice`](crate::convert_slice). The results of the marked elements are unspecified. Element `i` has bit `i % 8` of `invalid[i / 8]`, which is the least significant bit first layout of Apache Arrow. The bits after the last element in the last byte are 0. The bytes after that are left untouched.
+///
+/// Unlike stopping at the first invalid element, this reports every invalid element of the slice in one pass.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths or if `invalid` is too short to have a bit for every element.
+///
+/// ```
+/// let src = [1.5f32, 300.0, f32::NAN, -3.0];
+/// let mut dst = [0u8; 4];
+/// let mut invalid = [0u8; 1];
+/// let count = fast_float_to_integer::checked_convert_slice_bitmap(&src, &mut dst, &mut invalid);
+/// assert_eq!(count, 3);
+/// assert_eq!(invalid, [0b1110]);
+/// assert_eq!(dst[0], 1);
+/// ```
+#[inline]
+pub fn checked_convert_slice_bitmap<Float, Integer>(
+    src: &[Float],
+    dst: &mut [Integer],
+    invalid: &mut [u8],
+) -> usize
+where
+    Float: FloatType,
+    Integer: FastFrom<Float>,
+{
+    assert!(invalid.len() >= (src.len() + 7) / 8, "bitmap is too short");
+    let mut count = 0;
+    let len = src.len();
+    convert_checked_chunks(src, dst, |offset, bits| {
+        count += bits.count_ones() as usize;
+        let bytes = ((len - offset).min(64) + 7) / 8;
+        invalid[offset / 8..][..bytes].copy_from_slice(&bits.to_le_bytes()[..bytes]);
+    });
+    count
+}
+
+/// Convert a slice and append the index of every element that is out of range of the output type or NaN to `invalid`. Return whether every element was in range.
+///
+/// The elements are converted like [`convert_slice`](crate::convert_slice). The results of the appended elements are unspecified. The indices are appended in increasing order. [`checked_convert_slice_bitmap`] does the same without allocating.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+///
+/// ```
+/// let src = [1.5f64, 1e10, 2.0, f64::NEG_INFINITY];
+/// let mut dst = [0i32; 4];
+/// let mut invalid = Vec::new();
+/// let valid = fast_float_to_integer::checked_convert_slice_indices(&src, &mut dst, &mut invalid);
+/// assert!(!valid);
+/// assert_eq!(invalid, [1, 3]);
+/// assert_eq!((dst[0], dst[2]), (1, 2));
+/// ```
+#[cfg(feature = "std")]
+#[inline]
+pub fn checked_convert_slice_indices<Float, Integer>(
+    src: &[Float],
+    dst: &mut [Integer],
+    invalid: &mut Vec<usize>,
+) -> bool
+where
+    Float: FloatType,
+    Integer: FastFrom<Float>,
+{
+    let previous_len = invalid.len();
+    convert_checked_chunks(src, dst, |offset, mut bits| {
+        while bits != 0 {
+            invalid.push(offset + bits.trailing_zeros() as usize);
+            // Clear the lowest set bit.
+            bits &= bits - 1;
+        }
+    });
+    invalid.len() == previous_len
+}
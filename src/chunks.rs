@@ -0,0 +1,110 @@
+// Conversion of chunks that arrive one after another from a slow source like a file or a decompressor.
+
+use std::{
+    sync::mpsc::{self, Receiver},
+    thread::{self, JoinHandle},
+};
+
+use crate::FastFrom;
+
+/// An iterator over the converted chunks of a source iterator. Created by [`convert_chunks`] and [`convert_chunks_in_background`].
+pub struct ConvertedChunks<Source, Integer> {
+    inner: Inner<Source, Integer>,
+}
+
+enum Inner<Source, Integer> {
+    Inline(Source),
+    Background {
+        receiver: Receiver<Vec<Integer>>,
+        thread: Option<JoinHandle<()>>,
+    },
+}
+
+/// Convert the chunks of `source` like [`convert_slice`](crate::convert_slice) when the iterator is advanced.
+///
+/// Every chunk is converted on the calling thread. See [`convert_chunks_in_background`] for a version that overlaps the conversion with your processing.
+///
+/// ```
+/// let source = vec![vec![1.5f32, -2.5], vec![3.0]].into_iter();
+/// let chunks: Vec<Vec<i32>> = fast_float_to_integer::convert_chunks(source).collect();
+/// assert_eq!(chunks, [vec![1, -2], vec![3]]);
+/// ```
+pub fn convert_chunks<Float, Integer, Source>(source: Source) -> ConvertedChunks<Source, Integer>
+where
+    Float: Copy,
+    Integer: FastFrom<Float>,
+    Source: Iterator<Item = Vec<Float>>,
+{
+    ConvertedChunks {
+        inner: Inner::Inline(source),
+    }
+}
+
+/// Like [`convert_chunks`] but `source` is read and converted on a background thread.
+///
+/// The thread works on the next chunk while you process the current one. This double buffering hides the time of the conversion and of reading a slow source behind your processing. At most one converted chunk waits for you, so the memory use stays bounded.
+///
+/// If the source panics, then the panic is resumed on your thread when the iterator reaches the point of the panic.
+///
+/// ```
+/// let source = (0..4).map(|chunk| vec![chunk as f64 + 0.5; 3]);
+/// let chunks: Vec<Vec<u8>> = fast_float_to_integer::convert_chunks_in_background(source).collect();
+/// assert_eq!(chunks, [[0; 3], [1; 3], [2; 3], [3; 3]]);
+/// ```
+pub fn convert_chunks_in_background<Float, Integer, Source>(
+    source: Source,
+) -> ConvertedChunks<Source, Integer>
+where
+    Float: Copy,
+    Integer: FastFrom<Float> + Send + 'static,
+    Source: Iterator<Item = Vec<Float>> + Send + 'static,
+{
+    // A capacity of 1 lets the thread finish one chunk ahead while it converts the next.
+    let (sender, receiver) = mpsc::sync_channel(1);
+    let thread = thread::spawn(move || {
+        for chunk in source {
+            let mut converted = vec![Integer::ZERO; chunk.len()];
+            Integer::convert_slice(&chunk, &mut converted);
+            if sender.send(converted).is_err() {
+                // The iterator has been dropped.
+                break;
+            }
+        }
+    });
+    ConvertedChunks {
+        inner: Inner::Background {
+            receiver,
+            thread: Some(thread),
+        },
+    }
+}
+
+impl<Float, Integer, Source> Iterator for ConvertedChunks<Source, Integer>
+where
+    Float: Copy,
+    Integer: FastFrom<Float>,
+    Source: Iterator<Item = Vec<Float>>,
+{
+    type Item = Vec<Integer>;
+
+    fn next(&mut self) -> Option<Vec<Integer>> {
+        match &mut self.inner {
+            Inner::Inline(source) => {
+                let chunk = source.next()?;
+                let mut converted = vec![Integer::ZERO; chunk.len()];
+                Integer::convert_slice(&chunk, &mut converted);
+                Some(converted)
+            }
+            Inner::Background { receiver, thread } => match receiver.recv() {
+                Ok(converted) => Some(converted),
+                Err(_) => {
+                    // The thread has finished. Forward a panic of the source.
+                    if let Some(Err(panic)) = thread.take().map(JoinHandle::join) {
+                        std::panic::resume_unwind(panic);
+                    }
+                    None
+                }
+            },
+        }
+    }
+}
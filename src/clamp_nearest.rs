@@ -0,0 +1,51 @@
+// Conversions that saturate like the standard `as` operator but leave NaN unspecified. Handling NaN is a significant part of the cost of the `as` operator.
+
+use crate::*;
+
+macro_rules! create_function {
+    ($name:ident, $convert:ident, $Float:ty, $Integer:ty) => {
+        /// Convert the input floating point value to the output integer type with saturation.
+        ///
+        /// Out of range inputs, including infinities, convert to the nearest representable value of the output type like the standard `as` operator. If the input is NaN, then the result is unspecified. Otherwise, the result is the same as the standard `as` conversion.
+        #[cfg_attr(feature = "show-asm", inline(never))]
+        #[cfg_attr(not(feature = "show-asm"), inline(always))]
+        pub fn $name(float: $Float) -> $Integer {
+            // MIN is 0 or a negative power of two, which is exactly representable.
+            let min = <$Integer>::MIN as $Float;
+            // see FastFrom::in_range
+            let upper_exclusive = <$Integer>::MAX as $Float + 1.;
+
+            // Clamping the input handles too small inputs. For too large inputs we cannot clamp the input because MAX is not representable for some conversions. The comparisons are false for NaN.
+            // The public function is not inlined with the show-asm feature.
+            let integer =
+                active_target::implementation::$convert(if float < min { min } else { float });
+            if float >= upper_exclusive {
+                <$Integer>::MAX
+            } else {
+                integer
+            }
+        }
+    };
+}
+
+create_function! {f32_to_i8_clamp_nearest, f32_to_i8, f32, i8}
+create_function! {f32_to_u8_clamp_nearest, f32_to_u8, f32, u8}
+create_function! {f32_to_i16_clamp_nearest, f32_to_i16, f32, i16}
+create_function! {f32_to_u16_clamp_nearest, f32_to_u16, f32, u16}
+create_function! {f32_to_i32_clamp_nearest, f32_to_i32, f32, i32}
+create_function! {f32_to_u32_clamp_nearest, f32_to_u32, f32, u32}
+create_function! {f32_to_i64_clamp_nearest, f32_to_i64, f32, i64}
+create_function! {f32_to_u64_clamp_nearest, f32_to_u64, f32, u64}
+create_function! {f32_to_i128_clamp_nearest, f32_to_i128, f32, i128}
+create_function! {f32_to_u128_clamp_nearest, f32_to_u128, f32, u128}
+
+create_function! {f64_to_i8_clamp_nearest, f64_to_i8, f64, i8}
+create_function! {f64_to_u8_clamp_nearest, f64_to_u8, f64, u8}
+create_function! {f64_to_i16_clamp_nearest, f64_to_i16, f64, i16}
+create_function! {f64_to_u16_clamp_nearest, f64_to_u16, f64, u16}
+create_function! {f64_to_i32_clamp_nearest, f64_to_i32, f64, i32}
+create_function! {f64_to_u32_clamp_nearest, f64_to_u32, f64, u32}
+create_function! {f64_to_i64_clamp_nearest, f64_to_i64, f64, i64}
+create_function! {f64_to_u64_clamp_nearest, f64_to_u64, f64, u64}
+create_function! {f64_to_i128_clamp_nearest, f64_to_i128, f64, i128}
+create_function! {f64_to_u128_clamp_nearest, f64_to_u128, f64, u128}
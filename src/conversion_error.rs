@@ -0,0 +1,65 @@
+// The difference between the input and the converted value. This quantifies the truncation error.
+
+use crate::*;
+
+macro_rules! create_function {
+    ($name:ident, $convert:ident, $Float:ty) => {
+        #[doc = concat!("Return the difference between the input and the input converted with [`", stringify!($convert), "`].")]
+        ///
+        /// The result is 0 if the conversion is exact. Otherwise, it is the fractional part that the conversion truncates, which has the sign of the input. Converting the integer back to float is exact because it came from a float.
+        ///
+        /// If the input value is out of range of the output type, then the result is unspecified.
+        #[inline(always)]
+        pub fn $name(float: $Float) -> $Float {
+            float - $convert(float) as $Float
+        }
+    };
+}
+
+create_function! {conversion_error_f32_to_i8, f32_to_i8, f32}
+create_function! {conversion_error_f32_to_u8, f32_to_u8, f32}
+create_function! {conversion_error_f32_to_i16, f32_to_i16, f32}
+create_function! {conversion_error_f32_to_u16, f32_to_u16, f32}
+create_function! {conversion_error_f32_to_i32, f32_to_i32, f32}
+create_function! {conversion_error_f32_to_u32, f32_to_u32, f32}
+create_function! {conversion_error_f32_to_i64, f32_to_i64, f32}
+create_function! {conversion_error_f32_to_u64, f32_to_u64, f32}
+create_function! {conversion_error_f32_to_i128, f32_to_i128, f32}
+create_function! {conversion_error_f32_to_u128, f32_to_u128, f32}
+
+create_function! {conversion_error_f64_to_i8, f64_to_i8, f64}
+create_function! {conversion_error_f64_to_u8, f64_to_u8, f64}
+create_function! {conversion_error_f64_to_i16, f64_to_i16, f64}
+create_function! {conversion_error_f64_to_u16, f64_to_u16, f64}
+create_function! {conversion_error_f64_to_i32, f64_to_i32, f64}
+create_function! {conversion_error_f64_to_u32, f64_to_u32, f64}
+create_function! {conversion_error_f64_to_i64, f64_to_i64, f64}
+create_function! {conversion_error_f64_to_u64, f64_to_u64, f64}
+create_function! {conversion_error_f64_to_i128, f64_to_i128, f64}
+create_function! {conversion_error_f64_to_u128, f64_to_u128, f64}
+
+macro_rules! create_with_fract_function {
+    ($name:ident, $convert:ident, $Float:ty, $Integer:ty) => {
+        #[doc = concat!("Return the input converted with [`", stringify!($convert), "`] and the fractional part that the conversion truncates.")]
+        ///
+        /// Interpolation like texture sampling and splines needs both parts. The fractional part is computed like the conversion error functions, with one conversion of the integer back to float and one subtraction. The conversion back is exact. The fractional part has the sign of the input and its magnitude is less than 1.
+        ///
+        /// If the input value is out of range of the output type, then the results are unspecified.
+        ///
+        /// ```
+        #[doc = concat!("let (integer, fract) = fast_float_to_integer::", stringify!($name), "(2.75);")]
+        /// assert_eq!(integer, 2);
+        /// assert_eq!(fract, 0.75);
+        /// ```
+        #[inline(always)]
+        pub fn $name(float: $Float) -> ($Integer, $Float) {
+            let integer = $convert(float);
+            (integer, float - integer as $Float)
+        }
+    };
+}
+
+create_with_fract_function! {f32_to_i32_with_fract, f32_to_i32, f32, i32}
+create_with_fract_function! {f32_to_u32_with_fract, f32_to_u32, f32, u32}
+create_with_fract_function! {f64_to_i32_with_fract, f64_to_i32, f64, i32}
+create_with_fract_function! {f64_to_u32_with_fract, f64_to_u32, f64, u32}
@@ -0,0 +1,299 @@
+// A builder that combines the options of the conversions: the rounding direction, the behavior for out of range inputs and a scale factor. The options are types, so that every combination compiles to its own code like the functions with the options in their name.
+
+use crate::{sealed, FastFrom, FloatType, Guarantee, Unspecified};
+
+/// A rounding direction of [`Convert`].
+///
+/// The trait is implemented for [`Truncate`], [`Floor`], [`Ceil`], [`Round`] and [`RoundTiesEven`] and cannot be implemented outside of this crate.
+pub trait Rounding: sealed::Rounding {}
+
+/// Round toward zero like the standard `as` operator.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Truncate;
+
+/// Round toward negative infinity like [`f32_to_i32_floor`](crate::f32_to_i32_floor).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Floor;
+
+/// Round toward positive infinity like [`f32_to_i32_ceil`](crate::f32_to_i32_ceil).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Ceil;
+
+/// Round to the nearest integer with ties away from zero like [`f32_to_i32_round`](crate::f32_to_i32_round).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Round;
+
+/// Round to the nearest integer with ties to even like [`f32_to_i32_round_ties_even`](crate::f32_to_i32_round_ties_even).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RoundTiesEven;
+
+impl sealed::Rounding for Truncate {
+    #[inline(always)]
+    fn round<Float: FloatType>(float: Float) -> Float {
+        // The conversions truncate.
+        float
+    }
+
+    #[inline(always)]
+    fn unspecified<Float: FloatType, Integer: FastFrom<Float>>(float: Float) -> Integer {
+        Integer::fast_from(float)
+    }
+
+    #[inline(always)]
+    fn unspecified_slice<Float: FloatType, Integer: FastFrom<Float>>(
+        src: &[Float],
+        dst: &mut [Integer],
+    ) {
+        Integer::convert_slice(src, dst);
+    }
+
+    #[inline(always)]
+    fn saturating_slice<Float: FloatType, Integer: FastFrom<Float>>(
+        src: &[Float],
+        dst: &mut [Integer],
+    ) {
+        Integer::saturating_convert_slice(src, dst);
+    }
+}
+
+impl sealed::Rounding for RoundTiesEven {
+    #[inline(always)]
+    fn round<Float: FloatType>(float: Float) -> Float {
+        sealed::Float::round_ties_even(float)
+    }
+
+    #[inline(always)]
+    fn unspecified<Float: FloatType, Integer: FastFrom<Float>>(float: Float) -> Integer {
+        Integer::round_ties_even_from(float)
+    }
+
+    #[inline(always)]
+    fn unspecified_slice<Float: FloatType, Integer: FastFrom<Float>>(
+        src: &[Float],
+        dst: &mut [Integer],
+    ) {
+        Integer::round_ties_even_convert_slice(src, dst);
+    }
+}
+
+// The directions without packed kernels convert element by element.
+macro_rules! implement_rounding {
+    ($($Rounding:ty: $round:ident, $convert:ident;)*) => {
+        $(
+            impl sealed::Rounding for $Rounding {
+                #[inline(always)]
+                fn round<Float: FloatType>(float: Float) -> Float {
+                    sealed::Float::$round(float)
+                }
+
+                #[inline(always)]
+                fn unspecified<Float: FloatType, Integer: FastFrom<Float>>(float: Float) -> Integer {
+                    Integer::$convert(float)
+                }
+            }
+        )*
+    };
+}
+
+implement_rounding! {
+    Floor: floor, floor_from;
+    Ceil: ceil, ceil_from;
+    Round: round, round_from;
+}
+
+impl Rounding for Truncate {}
+impl Rounding for Floor {}
+impl Rounding for Ceil {}
+impl Rounding for Round {}
+impl Rounding for RoundTiesEven {}
+
+/// The scale factor of [`Convert`].
+///
+/// The trait is implemented for [`Unscaled`] and [`Scaled`] and cannot be implemented outside of this crate.
+pub trait Scaling: sealed::Scaling {}
+
+/// The input is converted without multiplying it.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Unscaled;
+
+/// The input is multiplied by the factor before it is rounded and converted. Create it with [`Convert::scale`].
+#[derive(Clone, Copy, Debug)]
+pub struct Scaled(f64);
+
+impl sealed::Scaling for Unscaled {
+    #[inline(always)]
+    fn apply<Float: FloatType>(self, float: Float) -> Float {
+        float
+    }
+
+    #[inline(always)]
+    fn convert_slice<R, G, Float, Integer>(self, src: &[Float], dst: &mut [Integer])
+    where
+        R: Rounding,
+        G: Guarantee,
+        Float: FloatType,
+        Integer: FastFrom<Float>,
+    {
+        G::convert_rounded_slice::<R, Float, Integer>(src, dst);
+    }
+}
+
+impl sealed::Scaling for Scaled {
+    #[inline(always)]
+    fn apply<Float: FloatType>(self, float: Float) -> Float {
+        float * Float::from_f64(self.0)
+    }
+
+    #[inline(always)]
+    fn convert_slice<R, G, Float, Integer>(self, src: &[Float], dst: &mut [Integer])
+    where
+        R: Rounding,
+        G: Guarantee,
+        Float: FloatType,
+        Integer: FastFrom<Float>,
+    {
+        // The products go through a buffer on the stack, so that the conversion still uses the packed kernels. The buffer is small enough to stay in the L1 cache.
+        const BLOCK: usize = 256;
+
+        let factor = Float::from_f64(self.0);
+        let mut scaled = [Float::ZERO; BLOCK];
+        for (src, dst) in src.chunks(BLOCK).zip(dst.chunks_mut(BLOCK)) {
+            let scaled = &mut scaled[..src.len()];
+            for (product, float) in scaled.iter_mut().zip(src) {
+                *product = *float * factor;
+            }
+            G::convert_rounded_slice::<R, Float, Integer>(scaled, dst);
+        }
+    }
+}
+
+impl Scaling for Unscaled {}
+impl Scaling for Scaled {}
+
+/// A conversion with the rounding direction `R`, the guarantee `G` for out of range inputs and NaN and the scale factor `S`.
+///
+/// Start with [`Convert::new`], which truncates with unspecified results for out of range inputs like [`FastFrom::fast_from`], and change the options with the builder methods. The options are types, so that the conversion methods compile to the same code as the functions with the options in their name, like [`f32_to_i32_floor`](crate::f32_to_i32_floor). The value only holds the scale factor and is cheap to copy.
+///
+/// The conversion methods are generic over the float and the integer type. [`slice`](Self::slice) uses the packed kernels of [`convert_slice`](crate::convert_slice) if there are any for the options.
+///
+/// ```
+/// use fast_float_to_integer::{Convert, Floor, Saturate};
+///
+/// let convert = Convert::new().rounding(Floor).overflow(Saturate).scale(256.0);
+///
+/// let integer: i16 = convert.one(-1.001f32);
+/// assert_eq!(integer, -257);
+/// assert_eq!(convert.one::<f32, i16>(1000.), i16::MAX);
+///
+/// let src = [0.5f32, -0.5, 1.0, f32::NAN];
+/// let mut dst = [0u8; 4];
+/// convert.slice(&src, &mut dst);
+/// assert_eq!(dst, [128, 0, 255, 0]);
+///
+/// let integers: Vec<i32> = convert.iter(&src).collect();
+/// assert_eq!(integers, [128, -128, 256, 0]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Convert<R = Truncate, G = Unspecified, S = Unscaled> {
+    rounding: R,
+    overflow: G,
+    scale: S,
+}
+
+impl Convert {
+    /// Create the conversion that truncates and has unspecified results for out of range inputs.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            rounding: Truncate,
+            overflow: Unspecified,
+            scale: Unscaled,
+        }
+    }
+}
+
+impl Default for Convert {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R, G, S> Convert<R, G, S>
+where
+    R: Rounding,
+    G: Guarantee,
+    S: Scaling,
+{
+    /// Round in the direction of `rounding` instead.
+    ///
+    /// If the rounded value is out of range of the output type, then the result depends on the guarantee like for truncated values.
+    #[inline(always)]
+    pub fn rounding<R2: Rounding>(self, rounding: R2) -> Convert<R2, G, S> {
+        Convert {
+            rounding,
+            overflow: self.overflow,
+            scale: self.scale,
+        }
+    }
+
+    /// Convert out of range inputs and NaN with the guarantee of `overflow` instead, like [`convert_with`](crate::convert_with).
+    #[inline(always)]
+    pub fn overflow<G2: Guarantee>(self, overflow: G2) -> Convert<R, G2, S> {
+        Convert {
+            rounding: self.rounding,
+            overflow,
+            scale: self.scale,
+        }
+    }
+
+    /// Multiply the input by `factor` before rounding and converting it.
+    ///
+    /// The factor is converted to the float type of the input with `as`. The multiplication is a single float multiplication like in [`f32_to_i32_scaled_const`](crate::f32_to_i32_scaled_const).
+    #[inline(always)]
+    pub fn scale(self, factor: f64) -> Convert<R, G, Scaled> {
+        Convert {
+            rounding: self.rounding,
+            overflow: self.overflow,
+            scale: Scaled(factor),
+        }
+    }
+
+    /// Convert the input floating point value.
+    #[inline(always)]
+    pub fn one<Float, Integer>(&self, float: Float) -> Integer
+    where
+        Float: FloatType,
+        Integer: FastFrom<Float>,
+    {
+        G::convert_rounded::<R, Float, Integer>(sealed::Scaling::apply(self.scale, float))
+    }
+
+    /// Convert the floats in `src` and write the results to `dst`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices have different lengths.
+    #[inline]
+    pub fn slice<Float, Integer>(&self, src: &[Float], dst: &mut [Integer])
+    where
+        Float: FloatType,
+        Integer: FastFrom<Float>,
+    {
+        assert_eq!(src.len(), dst.len(), "slices have different lengths");
+        sealed::Scaling::convert_slice::<R, G, Float, Integer>(self.scale, src, dst);
+    }
+
+    /// Convert the floats in `src` lazily.
+    #[inline]
+    pub fn iter<'a, Float, Integer>(
+        &'a self,
+        src: &'a [Float],
+    ) -> impl DoubleEndedIterator<Item = Integer> + ExactSizeIterator + 'a
+    where
+        Float: FloatType,
+        Integer: FastFrom<Float>,
+    {
+        src.iter().map(move |float| self.one(*float))
+    }
+}
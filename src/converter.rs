@@ -0,0 +1,159 @@
+// Conversion handles that carry what they guarantee for out of range inputs in their type. Libraries can require a minimum guarantee from their callers and the compiler checks it.
+
+use core::{fmt, marker::PhantomData};
+
+use crate::{
+    convert_with, sealed, Deterministic, FastFrom, FloatType, Rounding, Saturate, Unspecified, Wrap,
+};
+
+/// A policy whose conversion returns the integer type itself, so that it can be the guarantee of a [`Converter`].
+///
+/// The trait is implemented for [`Unspecified`], [`Deterministic`], [`Saturate`] and [`Wrap`] and cannot be implemented outside of this crate.
+pub trait Guarantee: sealed::Guarantee {}
+
+// The rounded conversions use the methods of the rounding direction that have the behavior of the policy.
+macro_rules! implement_guarantee {
+    ($($Policy:ty: $rounded:ident, $rounded_slice:ident;)*) => {
+        $(
+            impl sealed::Guarantee for $Policy {
+                #[inline(always)]
+                fn convert<Float: FloatType, Integer: FastFrom<Float>>(float: Float) -> Integer {
+                    convert_with::<Self, Float, Integer>(float)
+                }
+
+                #[inline(always)]
+                fn convert_rounded<R, Float, Integer>(float: Float) -> Integer
+                where
+                    R: Rounding,
+                    Float: FloatType,
+                    Integer: FastFrom<Float>,
+                {
+                    <R as sealed::Rounding>::$rounded(float)
+                }
+
+                #[inline(always)]
+                fn convert_rounded_slice<R, Float, Integer>(src: &[Float], dst: &mut [Integer])
+                where
+                    R: Rounding,
+                    Float: FloatType,
+                    Integer: FastFrom<Float>,
+                {
+                    <R as sealed::Rounding>::$rounded_slice(src, dst)
+                }
+            }
+
+            impl Guarantee for $Policy {}
+        )*
+    };
+}
+
+implement_guarantee! {
+    Unspecified: unspecified, unspecified_slice;
+    Deterministic: saturating, saturating_slice;
+    Saturate: saturating, saturating_slice;
+    Wrap: wrapping, wrapping_slice;
+}
+
+/// The guarantee `Self` includes the guarantee `G`.
+///
+/// Every guarantee includes itself and [`Unspecified`]. [`Saturate`] and [`Wrap`] include [`Deterministic`] because they specify every result. [`Saturate`] and [`Wrap`] do not include each other.
+///
+/// The trait cannot be implemented outside of this crate.
+pub trait AtLeast<G: Guarantee>: Guarantee {}
+
+impl<G: Guarantee> AtLeast<Unspecified> for G {}
+impl AtLeast<Deterministic> for Deterministic {}
+impl AtLeast<Deterministic> for Saturate {}
+impl AtLeast<Deterministic> for Wrap {}
+impl AtLeast<Saturate> for Saturate {}
+impl AtLeast<Wrap> for Wrap {}
+
+/// A conversion from `Float` to `Integer` with the guarantee `G` for out of range inputs and NaN.
+///
+/// The handle is zero sized. Its conversion is the one of [`convert_with`] with `G` as the policy. Code that accepts a converter can require a minimum guarantee with an [`AtLeast`] bound, so that callers cannot pass a weaker converter by accident. [`weaken`](Self::weaken) turns a converter into one with a guarantee that it includes.
+///
+/// ```
+/// use fast_float_to_integer::{AtLeast, Converter, Deterministic, Saturate, Unspecified};
+///
+/// // The results must be the same on every machine, for example for a replay.
+/// fn replay<G: AtLeast<Deterministic>>(converter: Converter<f32, i32, G>, inputs: &[f32]) -> i64 {
+///     inputs.iter().map(|&float| converter.convert(float) as i64).sum()
+/// }
+///
+/// assert_eq!(replay(Converter::<f32, i32, Saturate>::new(), &[1.5, -2.5, f32::NAN]), -1);
+/// assert_eq!(replay(Converter::<f32, i32, Deterministic>::new(), &[1.5, 2.5]), 3);
+/// // Does not compile because Unspecified does not include Deterministic:
+/// // replay(Converter::<f32, i32, Unspecified>::new(), &[1.5]);
+///
+/// let unspecified: Converter<f32, i32, Unspecified> = Converter::<f32, i32, Saturate>::new().weaken();
+/// assert_eq!(unspecified.convert(1.5), 1);
+/// ```
+pub struct Converter<Float, Integer, G> {
+    _marker: PhantomData<fn(Float, G) -> Integer>,
+}
+
+impl<Float, Integer, G> Converter<Float, Integer, G>
+where
+    Float: FloatType,
+    Integer: FastFrom<Float>,
+    G: Guarantee,
+{
+    /// Create the converter.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+
+    /// Convert the input floating point value with the guarantee of this converter.
+    #[inline(always)]
+    pub fn convert(self, float: Float) -> Integer {
+        <G as sealed::Guarantee>::convert(float)
+    }
+
+    /// Turn this converter into one with a weaker guarantee that this one includes.
+    #[inline(always)]
+    pub fn weaken<H>(self) -> Converter<Float, Integer, H>
+    where
+        G: AtLeast<H>,
+        H: Guarantee,
+    {
+        Converter::new()
+    }
+}
+
+// Implemented by hand because the derives would require the type parameters to implement the traits.
+
+impl<Float, Integer, G> Clone for Converter<Float, Integer, G> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Float, Integer, G> Copy for Converter<Float, Integer, G> {}
+
+impl<Float, Integer, G> Default for Converter<Float, Integer, G>
+where
+    Float: FloatType,
+    Integer: FastFrom<Float>,
+    G: Guarantee,
+{
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Float, Integer, G> fmt::Debug for Converter<Float, Integer, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Converter<{}, {}, {}>",
+            core::any::type_name::<Float>(),
+            core::any::type_name::<Integer>(),
+            core::any::type_name::<G>()
+        )
+    }
+}
@@ -0,0 +1,15 @@
+//! Conversions for code that must not leak the input through timing.
+//!
+//! On the targets with a specialized implementation, x86 and x86_64 with SSE and aarch64 with NEON, the conversions in this module compile to straight-line code. They have no branches and only access memory at constant addresses. This makes them suitable for code that handles secret derived floats like the noise of differential privacy. The xtask checks the generated assembly of these conversions for branches.
+//!
+//! On other targets the conversions are the same functions but without the guarantee. Check [`is_specialized`](crate::is_specialized) if you need it.
+//!
+//! The guarantee only covers the conversion. The compiler can still introduce branches in your code around it. The 128 bit conversions call into compiler_builtins, which branches on the input, so they are not in this module. Neither are the 64 bit conversions, which use the standard conversion on x86. On x86_64 and aarch64 they are straight-line code too, which the xtask checks.
+
+pub use crate::{
+    f32_to_i16, f32_to_i32, f32_to_i8, f32_to_u16, f32_to_u32, f32_to_u8, f64_to_i16, f64_to_i32,
+    f64_to_i8, f64_to_u16, f64_to_u32, f64_to_u8,
+};
+
+#[cfg(not(target_arch = "x86"))]
+pub use crate::{f32_to_i64, f32_to_u64, f64_to_i64, f64_to_u64};
@@ -0,0 +1,53 @@
+// Conversion fused with decimal formatting for exporters that quantize floats before they serialize them, like telemetry and CSV writers.
+
+use std::io::{self, Write};
+
+/// The number of values that are converted before they are formatted. The integers of one chunk live on the stack.
+const CHUNK: usize = 64;
+
+/// The length of the decimal representation of `i128::MIN`, the longest of all integer types, and a separator.
+const MAX_LEN: usize = 41;
+
+/// Convert `src` with `convert` and write the decimal representation of the integers to `writer`, each followed by `separator`.
+///
+/// The values are converted and formatted in chunks, so there is no intermediate column of integers. Pass the conversion that you want to quantize with, like [`f64_to_i64`](crate::f64_to_i64) to truncate or [`f64_to_i64_round`](crate::f64_to_i64_round) to round. The integers are formatted with `itoa`. Every chunk is one call to `write_all`, so an unbuffered writer like a file does not need a [`BufWriter`](std::io::BufWriter).
+///
+/// The separator follows every value including the last one. Pass `b'\n'` for one value per line or `b','` and write the line endings yourself.
+///
+/// ```
+/// let mut csv = Vec::new();
+/// fast_float_to_integer::write_decimal(&[1.5, -2.5, 1e15], fast_float_to_integer::f64_to_i64_round, b'\n', &mut csv).unwrap();
+/// assert_eq!(csv, b"2\n-3\n1000000000000000\n");
+/// ```
+pub fn write_decimal<Float, Integer, Writer>(
+    src: &[Float],
+    mut convert: impl FnMut(Float) -> Integer,
+    separator: u8,
+    writer: &mut Writer,
+) -> io::Result<()>
+where
+    Float: Copy,
+    Integer: itoa::Integer + Copy + Default,
+    Writer: Write + ?Sized,
+{
+    let mut integers = [Integer::default(); CHUNK];
+    let mut text = [0u8; CHUNK * MAX_LEN];
+    let mut formatter = itoa::Buffer::new();
+    for src in src.chunks(CHUNK) {
+        let integers = &mut integers[..src.len()];
+        // Converting the whole chunk first keeps the conversion loop free of the formatting code, so the compiler can vectorize it.
+        for (integer, float) in integers.iter_mut().zip(src) {
+            *integer = convert(*float);
+        }
+        let mut len = 0;
+        for integer in integers.iter() {
+            let decimal = formatter.format(*integer).as_bytes();
+            text[len..len + decimal.len()].copy_from_slice(decimal);
+            len += decimal.len();
+            text[len] = separator;
+            len += 1;
+        }
+        writer.write_all(&text[..len])?;
+    }
+    Ok(())
+}
@@ -0,0 +1,62 @@
+// Exact powers of two. Scale factors, fixed point formats and thresholds of the conversions are powers of two, and they are only correct if the float is exactly the power. Computing them with `powi` is not available in core and multiplying in a loop is not possible in a const fn before Rust 1.82. Building the bit pattern directly is exact and works in both.
+
+use crate::FloatType;
+
+macro_rules! create_function {
+    ($name:ident, $Float:ty, $Bits:ty) => {
+        #[doc = concat!("Return 2^exponent as ", stringify!($Float), ".")]
+        ///
+        /// Negative exponents are supported, including the ones that need a subnormal result. The result is exact.
+        ///
+        /// # Panics
+        ///
+        #[doc = concat!("Panics if 2^exponent is not exactly representable as ", stringify!($Float), ", which means it is larger than the largest finite or smaller than the smallest positive subnormal value. In a const context like a `const` item the panic is a compile time error.")]
+        ///
+        /// ```
+        #[doc = concat!("const SCALE: ", stringify!($Float), " = fast_float_to_integer::", stringify!($name), "(-3);")]
+        /// assert_eq!(SCALE, 0.125);
+        /// ```
+        #[inline]
+        pub const fn $name(exponent: i32) -> $Float {
+            const MANTISSA_BITS: i32 = <$Float>::MANTISSA_DIGITS as i32 - 1;
+            // The exponent of the smallest subnormal value.
+            const MIN: i32 = <$Float>::MIN_EXP - 1 - MANTISSA_BITS;
+            const MAX: i32 = <$Float>::MAX_EXP - 1;
+            assert!(
+                MIN <= exponent && exponent <= MAX,
+                "the power of two is not representable"
+            );
+            let bits: $Bits = if exponent >= <$Float>::MIN_EXP - 1 {
+                // Normal values have an implicit leading mantissa bit. Only the biased exponent is set.
+                ((exponent - (<$Float>::MIN_EXP - 2)) as $Bits) << MANTISSA_BITS
+            } else {
+                // Subnormal values have exponent bits 0. The value is the mantissa times the smallest subnormal value.
+                1 << (exponent - MIN)
+            };
+            // `from_bits` is only const since Rust 1.83. Every bit pattern is a valid float.
+            #[allow(unknown_lints, unnecessary_transmutes)]
+            unsafe {
+                core::mem::transmute::<$Bits, $Float>(bits)
+            }
+        }
+    };
+}
+
+create_function! {exact_pow2_f32, f32, u32}
+create_function! {exact_pow2_f64, f64, u64}
+
+/// Return 2^exponent as `Float`.
+///
+/// This is [`exact_pow2_f32`] or [`exact_pow2_f64`] for code that is generic over the float type. Use those directly in const contexts.
+///
+/// # Panics
+///
+/// Panics if 2^exponent is not exactly representable as `Float`.
+///
+/// ```
+/// assert_eq!(fast_float_to_integer::exact_pow2::<f64>(40), (1u64 << 40) as f64);
+/// ```
+#[inline]
+pub fn exact_pow2<Float: FloatType>(exponent: i32) -> Float {
+    Float::exact_pow2(exponent)
+}
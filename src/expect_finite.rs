@@ -0,0 +1,61 @@
+// Conversions that panic on NaN. NaN usually indicates corrupted data while out of range values are merely clipped, so these functions only check for NaN.
+
+use crate::*;
+
+// The panic is in separate functions to keep the inlined conversions small.
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn panic_nan_f32(float: f32) -> ! {
+    panic!("input is NaN with bits {:#010x}", float.to_bits())
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn panic_nan_f64(float: f64) -> ! {
+    panic!("input is NaN with bits {:#018x}", float.to_bits())
+}
+
+macro_rules! create_function {
+    ($name:ident, $convert:ident, $panic:ident, $Float:ty, $Integer:ty) => {
+        /// Convert the input floating point value to the output integer type and panic if the input is NaN.
+        ///
+        /// If the input value is out of range of the output type, then the result is unspecified. Otherwise, the result is the same as the standard `as` conversion.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the input is NaN, in debug and release builds. The panic message contains the bits of the NaN.
+        #[inline(always)]
+        #[track_caller]
+        pub fn $name(float: $Float) -> $Integer {
+            if float.is_nan() {
+                $panic(float);
+            }
+            $convert(float)
+        }
+    };
+}
+
+create_function! {f32_to_i8_expect_finite, f32_to_i8, panic_nan_f32, f32, i8}
+create_function! {f32_to_u8_expect_finite, f32_to_u8, panic_nan_f32, f32, u8}
+create_function! {f32_to_i16_expect_finite, f32_to_i16, panic_nan_f32, f32, i16}
+create_function! {f32_to_u16_expect_finite, f32_to_u16, panic_nan_f32, f32, u16}
+create_function! {f32_to_i32_expect_finite, f32_to_i32, panic_nan_f32, f32, i32}
+create_function! {f32_to_u32_expect_finite, f32_to_u32, panic_nan_f32, f32, u32}
+create_function! {f32_to_i64_expect_finite, f32_to_i64, panic_nan_f32, f32, i64}
+create_function! {f32_to_u64_expect_finite, f32_to_u64, panic_nan_f32, f32, u64}
+create_function! {f32_to_i128_expect_finite, f32_to_i128, panic_nan_f32, f32, i128}
+create_function! {f32_to_u128_expect_finite, f32_to_u128, panic_nan_f32, f32, u128}
+
+create_function! {f64_to_i8_expect_finite, f64_to_i8, panic_nan_f64, f64, i8}
+create_function! {f64_to_u8_expect_finite, f64_to_u8, panic_nan_f64, f64, u8}
+create_function! {f64_to_i16_expect_finite, f64_to_i16, panic_nan_f64, f64, i16}
+create_function! {f64_to_u16_expect_finite, f64_to_u16, panic_nan_f64, f64, u16}
+create_function! {f64_to_i32_expect_finite, f64_to_i32, panic_nan_f64, f64, i32}
+create_function! {f64_to_u32_expect_finite, f64_to_u32, panic_nan_f64, f64, u32}
+create_function! {f64_to_i64_expect_finite, f64_to_i64, panic_nan_f64, f64, i64}
+create_function! {f64_to_u64_expect_finite, f64_to_u64, panic_nan_f64, f64, u64}
+create_function! {f64_to_i128_expect_finite, f64_to_i128, panic_nan_f64, f64, i128}
+create_function! {f64_to_u128_expect_finite, f64_to_u128, panic_nan_f64, f64, u128}
@@ -0,0 +1,35 @@
+// Block conversions to the q7, q15 and q31 fixed point formats of CMSIS-DSP. A qN value is a signed integer that represents the value divided by 2^N, so the formats cover [-1, 1).
+
+use crate::*;
+
+macro_rules! create_function {
+    ($name:ident, $cmsis:literal, $Integer:ty, $fraction_bits:literal) => {
+        #[doc = concat!("Convert a block of f32 values to the q", stringify!($fraction_bits), " fixed point format like CMSIS-DSP's `", $cmsis, "` with `ARM_MATH_ROUNDING`.")]
+        ///
+        #[doc = concat!("Every value is multiplied by 2^", stringify!($fraction_bits), ", rounded to the nearest integer with ties away from zero and saturated to the range of ", stringify!($Integer), ". Like in CMSIS-DSP the rounding adds ±0.5 in f32 arithmetic. If a value is NaN, then its result is unspecified.")]
+        ///
+        /// # Panics
+        ///
+        /// Panics if the slices have different lengths.
+        #[inline]
+        pub fn $name(src: &[f32], dst: &mut [$Integer]) {
+            const SCALE: f32 = exact_pow2_f32($fraction_bits);
+
+            assert_eq!(src.len(), dst.len(), "slices have different lengths");
+            // The rounded values go through a buffer on the stack like in `Convert::slice` with a scale factor, so that the saturating conversion uses the packed kernels.
+            let mut rounded = [0f32; crate::slice::CHUNK];
+            for (src, dst) in src.chunks(rounded.len()).zip(dst.chunks_mut(rounded.len())) {
+                let rounded = &mut rounded[..src.len()];
+                for (rounded, float) in rounded.iter_mut().zip(src) {
+                    let scaled = *float * SCALE;
+                    *rounded = if *float > 0. { scaled + 0.5 } else { scaled - 0.5 };
+                }
+                <$Integer as sealed::Sealed<f32>>::saturating_convert_slice(rounded, dst);
+            }
+        }
+    };
+}
+
+create_function! {float_to_q7, "arm_float_to_q7", i8, 7}
+create_function! {float_to_q15, "arm_float_to_q15", i16, 15}
+create_function! {float_to_q31, "arm_float_to_q31", i32, 31}
@@ -0,0 +1,54 @@
+// Conversions of world coordinates to the integer coordinates of a grid, like the tiles or voxels of a game world. The cell size is known at compile time like the scale of the scaled conversions.
+
+use crate::{scaled::Scale, sealed::Float, *};
+
+macro_rules! create_function {
+    ($floor:ident, $round:ident, $convert:ident, $Float:ty, $SCALE:ident) => {
+        /// Convert a world coordinate to the coordinate of the grid cell that contains it. The cells are `CELL_NUMERATOR / CELL_DENOMINATOR` units wide and cell 0 starts at 0.
+        ///
+        /// This divides by the cell size and rounds towards negative infinity, so negative coordinates end up in negative cells instead of cell 0. The division is a multiplication with the reciprocal of the cell size, which is computed at compile time. For cell sizes that are powers of two the product is exact. Using a zero cell size or a numerator or denominator that is not exactly representable in the float type is a compile time error.
+        ///
+        /// If the result is out of range of i32, then it is unspecified.
+        ///
+        /// ```
+        #[doc = concat!("use fast_float_to_integer::", stringify!($floor), ";")]
+        ///
+        /// // Cells that are 16 units wide.
+        #[doc = concat!("assert_eq!(", stringify!($floor), "::<16, 1>(33.5), 2);")]
+        #[doc = concat!("assert_eq!(", stringify!($floor), "::<16, 1>(-0.5), -1);")]
+        /// // Cells that are half a unit wide.
+        #[doc = concat!("assert_eq!([1.2, -1.2].map(", stringify!($floor), "::<1, 2>), [2, -3]);")]
+        /// ```
+        #[inline(always)]
+        pub fn $floor<const CELL_NUMERATOR: i64, const CELL_DENOMINATOR: i64>(
+            float: $Float,
+        ) -> i32 {
+            let scaled = float * Scale::<CELL_DENOMINATOR, CELL_NUMERATOR>::$SCALE;
+            let truncated = $convert(scaled);
+            // Truncation rounds negative values with a fraction up. They are one cell too high. The wrapping subtraction cannot panic for out of range values.
+            truncated.wrapping_sub((truncated as $Float > scaled) as i32)
+        }
+
+        /// Like the floor variant but rounds to the nearest cell coordinate with ties to even.
+        ///
+        /// This is the coordinate of the grid point that is closest to the input, for example to snap positions to the grid.
+        ///
+        /// ```
+        #[doc = concat!("use fast_float_to_integer::", stringify!($round), ";")]
+        ///
+        #[doc = concat!("assert_eq!(", stringify!($round), "::<16, 1>(23.9), 1);")]
+        #[doc = concat!("assert_eq!(", stringify!($round), "::<16, 1>(-24.1), -2);")]
+        /// ```
+        #[inline(always)]
+        pub fn $round<const CELL_NUMERATOR: i64, const CELL_DENOMINATOR: i64>(
+            float: $Float,
+        ) -> i32 {
+            let scaled = float * Scale::<CELL_DENOMINATOR, CELL_NUMERATOR>::$SCALE;
+            // The inherent method needs the standard library.
+            $convert(Float::round_ties_even(scaled))
+        }
+    };
+}
+
+create_function! {f32_to_grid_floor, f32_to_grid_round, f32_to_i32, f32, F32}
+create_function! {f64_to_grid_floor, f64_to_grid_round, f64_to_i32, f64, F64}
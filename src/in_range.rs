@@ -0,0 +1,68 @@
+use core::marker::PhantomData;
+
+use crate::FastFrom;
+
+/// A float that is known to be in range of the integer type.
+///
+/// The only ways to create the type are the checked [`InRange::new`] and the unsafe [`InRange::new_unchecked`]. Because of that, [`InRange::convert`] can use [`to_int_unchecked`](f32::to_int_unchecked) on every target, including the targets where the other conversions fall back to the standard `as` operator. You pay for the validation once and convert for free afterwards.
+///
+/// ```
+/// use fast_float_to_integer::InRange;
+///
+/// let scale = InRange::<f32, i32>::new(255.9).unwrap();
+/// assert_eq!(scale.convert(), 255);
+/// assert!(InRange::<f32, u8>::new(256.0).is_none());
+/// assert!(InRange::<f32, u8>::new(f32::NAN).is_none());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct InRange<Float, Integer> {
+    float: Float,
+    integer: PhantomData<fn() -> Integer>,
+}
+
+impl<Float, Integer> InRange<Float, Integer>
+where
+    Float: Copy,
+    Integer: FastFrom<Float>,
+{
+    /// Returns `None` if the float is out of range of the integer type.
+    ///
+    /// A float is in range if it truncated towards zero is between the minimal and maximal value of the integer type. NaN and infinities are out of range.
+    #[inline]
+    pub fn new(float: Float) -> Option<Self> {
+        if Integer::in_range(float) {
+            Some(Self {
+                float,
+                integer: PhantomData,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Create the type without checking the range.
+    ///
+    /// # Safety
+    ///
+    /// The float must be in range of the integer type like in [`InRange::new`].
+    #[inline]
+    pub const unsafe fn new_unchecked(float: Float) -> Self {
+        Self {
+            float,
+            integer: PhantomData,
+        }
+    }
+
+    /// The float.
+    #[inline]
+    pub fn get(self) -> Float {
+        self.float
+    }
+
+    /// Convert the float to the integer type. The result is the same as the standard `as` conversion.
+    #[inline(always)]
+    pub fn convert(self) -> Integer {
+        // SAFETY: The constructors ensure that the float is in range.
+        unsafe { Integer::to_int_unchecked(self.float) }
+    }
+}
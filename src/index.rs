@@ -0,0 +1,98 @@
+// Conversions to indices into a slice of a given length, for example a lookup table or a palette.
+
+use crate::*;
+
+/// How [`f32_to_index`] and [`f64_to_index`] map results outside of `0..len` into it.
+///
+/// The trait is implemented for [`Saturate`] and [`Wrap`] and cannot be implemented outside of this crate.
+pub trait IndexPolicy: sealed::Policy {
+    #[doc(hidden)]
+    fn f32_to_index(float: f32, len: usize) -> usize;
+
+    #[doc(hidden)]
+    fn f64_to_index(float: f64, len: usize) -> usize;
+}
+
+// The conversions between usize and u64 are lossless because usize has at most 64 bits on every supported target.
+
+impl IndexPolicy for Saturate {
+    #[inline(always)]
+    fn f32_to_index(float: f32, len: usize) -> usize {
+        // The comparison is false for NaN, which is unspecified for the clamping conversion.
+        let integer = if float >= 0. {
+            f32_to_u64_clamp_nearest(float)
+        } else {
+            0
+        };
+        integer.min(len as u64 - 1) as usize
+    }
+
+    #[inline(always)]
+    fn f64_to_index(float: f64, len: usize) -> usize {
+        // see f32_to_index
+        let integer = if float >= 0. {
+            f64_to_u64_clamp_nearest(float)
+        } else {
+            0
+        };
+        integer.min(len as u64 - 1) as usize
+    }
+}
+
+impl IndexPolicy for Wrap {
+    #[inline(always)]
+    fn f32_to_index(float: f32, len: usize) -> usize {
+        let integer = if <i64 as sealed::Sealed<f32>>::in_range(float) {
+            f32_to_i64(float)
+        } else {
+            0
+        };
+        wrap_index(integer, len)
+    }
+
+    #[inline(always)]
+    fn f64_to_index(float: f64, len: usize) -> usize {
+        let integer = if <i64 as sealed::Sealed<f64>>::in_range(float) {
+            f64_to_i64(float)
+        } else {
+            0
+        };
+        wrap_index(integer, len)
+    }
+}
+
+/// The integer modulo `len` in `0..len`.
+#[inline(always)]
+fn wrap_index(integer: i64, len: usize) -> usize {
+    let len = len as u64;
+    let index = if integer >= 0 {
+        integer as u64 % len
+    } else {
+        // For negative n, n mod len is len - 1 - ((-n - 1) mod len). Computing -n - 1 as !n cannot overflow.
+        len - 1 - (!integer) as u64 % len
+    };
+    index as usize
+}
+
+macro_rules! create_function {
+    ($name:ident, $Float:ty) => {
+        /// Convert the input to an index into a slice of length `len`.
+        ///
+        /// The input is truncated towards zero like the other conversions. `P` selects what happens to results outside of `0..len`:
+        ///
+        /// - [`Saturate`]: They clamp to `0` or `len - 1`. NaN converts to 0.
+        /// - [`Wrap`]: They wrap around modulo `len` into `0..len`, so -1 converts to `len - 1`. Inputs ≥ 2^63 or < -2^63, and NaN, convert to 0.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `len` is 0.
+        #[inline(always)]
+        pub fn $name<P: IndexPolicy>(float: $Float, len: usize) -> usize {
+            assert!(len != 0, "length is zero");
+            P::$name(float, len)
+        }
+    };
+}
+
+create_function! {f32_to_index, f32}
+create_function! {f64_to_index, f64}
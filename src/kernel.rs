@@ -0,0 +1,199 @@
+//! Conversions of a fixed number of values at once.
+//!
+//! This module is not covered by semver. It exists for advanced users that want to combine the crate's packed conversions with their own SIMD code.
+//!
+//! A kernel converts `LANES` floats in one step. On targets with packed conversion instructions some conversions have a kernel that uses those instructions. Every other conversion uses [`Scalar`], which converts lane by lane. The slice functions like [`convert_slice`](crate::convert_slice) are built on the kernels.
+
+use core::{marker::PhantomData, mem::MaybeUninit};
+
+use crate::FastFrom;
+
+pub use crate::active_target::kernel::*;
+
+/// Convert `LANES` floats at once.
+pub trait Kernel<const LANES: usize> {
+    type Float: crate::FloatType;
+    type Integer: FastFrom<Self::Float>;
+
+    /// Convert every lane like [`FastFrom::fast_from`].
+    fn convert(floats: [Self::Float; LANES]) -> [Self::Integer; LANES];
+
+    /// Convert every lane like the standard `as` operator.
+    fn convert_saturating(floats: [Self::Float; LANES]) -> [Self::Integer; LANES];
+
+    /// Round every lane to the nearest integer with ties to even and convert it like [`FastFrom::fast_from`].
+    #[inline(always)]
+    fn convert_round_ties_even(floats: [Self::Float; LANES]) -> [Self::Integer; LANES] {
+        Self::convert(floats.map(crate::sealed::Float::round_ties_even))
+    }
+}
+
+/// A kernel for any conversion and any number of lanes that converts lane by lane.
+pub struct Scalar<Float, Integer>(PhantomData<(Float, Integer)>);
+
+impl<Float, Integer, const LANES: usize> Kernel<LANES> for Scalar<Float, Integer>
+where
+    Float: crate::FloatType,
+    Integer: FastFrom<Float>,
+{
+    type Float = Float;
+    type Integer = Integer;
+
+    #[inline(always)]
+    fn convert(floats: [Float; LANES]) -> [Integer; LANES] {
+        floats.map(Integer::fast_from)
+    }
+
+    #[inline(always)]
+    fn convert_saturating(floats: [Float; LANES]) -> [Integer; LANES] {
+        floats.map(Integer::saturating_from)
+    }
+}
+
+/// Convert `LANES` stereo frames of f32 samples to interleaved i16 samples at once.
+pub trait StereoKernel<const LANES: usize> {
+    /// Convert every sample like [`f32_to_i16`](crate::f32_to_i16) and pair the left and right sample of each frame.
+    fn convert_interleaved(left: [f32; LANES], right: [f32; LANES]) -> [[i16; 2]; LANES];
+}
+
+impl<const LANES: usize> StereoKernel<LANES> for Scalar<f32, i16> {
+    #[inline(always)]
+    fn convert_interleaved(left: [f32; LANES], right: [f32; LANES]) -> [[i16; 2]; LANES] {
+        let mut frames = [[0i16; 2]; LANES];
+        for ((frame, left), right) in frames.iter_mut().zip(left).zip(right) {
+            *frame = [crate::f32_to_i16(left), crate::f32_to_i16(right)];
+        }
+        frames
+    }
+}
+
+/// Convert a block of `LANES` rows of `LANES` floats at once and transpose it.
+pub trait TransposeKernel<const LANES: usize> {
+    type Float: crate::FloatType;
+    type Integer: FastFrom<Self::Float>;
+
+    /// Convert every element like [`FastFrom::fast_from`]. Row `i` of the result holds the converted column `i` of the input.
+    fn convert_transposed(rows: [[Self::Float; LANES]; LANES]) -> [[Self::Integer; LANES]; LANES];
+}
+
+impl<Float, Integer, const LANES: usize> TransposeKernel<LANES> for Scalar<Float, Integer>
+where
+    Float: crate::FloatType,
+    Integer: FastFrom<Float>,
+{
+    type Float = Float;
+    type Integer = Integer;
+
+    #[inline(always)]
+    fn convert_transposed(rows: [[Float; LANES]; LANES]) -> [[Integer; LANES]; LANES] {
+        core::array::from_fn(|column| {
+            core::array::from_fn(|row| Integer::fast_from(rows[row][column]))
+        })
+    }
+}
+
+/// Convert a slice with a kernel function. The elements that do not fill a whole kernel are converted with the scalar function.
+///
+/// With the small-code feature every element is converted with the scalar function. This avoids a second loop and the code of the kernel.
+#[inline(always)]
+pub(crate) fn convert_slice<Float, Integer, const LANES: usize>(
+    src: &[Float],
+    dst: &mut [Integer],
+    kernel: impl Fn([Float; LANES]) -> [Integer; LANES],
+    scalar: impl Fn(Float) -> Integer,
+) where
+    Float: Copy,
+    Integer: Copy,
+{
+    // SAFETY: MaybeUninit<T> has the same layout as T. convert_slice_uninit only writes initialized values, so the elements stay initialized.
+    let dst = unsafe { &mut *(dst as *mut [Integer] as *mut [MaybeUninit<Integer>]) };
+    convert_slice_uninit(src, dst, kernel, scalar);
+}
+
+/// Like [`convert_slice`] but for uninitialized destination elements. The kernel stores its result directly into the destination. The first `src.len()` elements of `dst` are initialized afterwards if `dst` is not shorter.
+#[inline(always)]
+pub(crate) fn convert_slice_uninit<Float, Integer, const LANES: usize>(
+    src: &[Float],
+    dst: &mut [MaybeUninit<Integer>],
+    kernel: impl Fn([Float; LANES]) -> [Integer; LANES],
+    scalar: impl Fn(Float) -> Integer,
+) where
+    Float: Copy,
+    Integer: Copy,
+{
+    #[cfg(feature = "trace")]
+    crate::trace::slice_call(src.len(), LANES);
+
+    let len = src.len().min(dst.len());
+    let (src, dst) = (&src[..len], &mut dst[..len]);
+
+    if cfg!(feature = "small-code") {
+        for (src, dst) in src.iter().zip(dst) {
+            dst.write(scalar(*src));
+        }
+        return;
+    }
+
+    let mut chunks = src.chunks_exact(LANES);
+    let out = dst.as_mut_ptr().cast::<Integer>();
+    for (index, src) in (&mut chunks).enumerate() {
+        let floats: [Float; LANES] = src.try_into().unwrap();
+        // SAFETY: The chunk is in bounds because dst has the length of src. MaybeUninit<T> has the same layout as T and an array has the alignment of its elements.
+        unsafe {
+            out.add(index * LANES)
+                .cast::<[Integer; LANES]>()
+                .write(kernel(floats))
+        };
+    }
+    let remainder = chunks.remainder();
+    for (src, dst) in remainder.iter().zip(&mut dst[len - remainder.len()..]) {
+        dst.write(scalar(*src));
+    }
+}
+
+/// Convert the row major `rows` x `cols` matrix `src` into the row major `cols` x `rows` matrix `dst` with a transpose kernel function. The kernel converts the blocks of `LANES` x `LANES` elements. The rows and columns that do not fill a whole block are converted with the scalar function.
+///
+/// With the small-code feature every element is converted with the scalar function.
+#[inline(always)]
+pub(crate) fn convert_transpose<Float, Integer, const LANES: usize>(
+    src: &[Float],
+    rows: usize,
+    cols: usize,
+    dst: &mut [Integer],
+    kernel: impl Fn([[Float; LANES]; LANES]) -> [[Integer; LANES]; LANES],
+    scalar: impl Fn(Float) -> Integer,
+) where
+    Float: Copy,
+    Integer: Copy,
+{
+    let (block_rows, block_cols) = if cfg!(feature = "small-code") {
+        (0, 0)
+    } else {
+        (rows - rows % LANES, cols - cols % LANES)
+    };
+
+    for row in (0..block_rows).step_by(LANES) {
+        for col in (0..block_cols).step_by(LANES) {
+            let block: [[Float; LANES]; LANES] = core::array::from_fn(|index| {
+                let start = (row + index) * cols + col;
+                src[start..start + LANES].try_into().unwrap()
+            });
+            for (index, converted) in kernel(block).iter().enumerate() {
+                let start = (col + index) * rows + row;
+                dst[start..start + LANES].copy_from_slice(converted);
+            }
+        }
+    }
+
+    // The columns to the right of the blocks and then the rows below them.
+    for row in 0..block_rows {
+        for col in block_cols..cols {
+            dst[col * rows + row] = scalar(src[row * cols + col]);
+        }
+    }
+    for row in block_rows..rows {
+        for col in 0..cols {
+            dst[col * rows + row] = scalar(src[row * cols + col]);
+        }
+    }
+}
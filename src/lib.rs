@@ -19,6 +19,115 @@
 //!
 //! - `target_arch = "x86_64", target_feature = "sse"`: all conversions except 128 bit integers
 //! - `target_arch = "x86", target_feature = "sse"`: all conversions except 64 bit and 128 bit integers
+//! - `target_arch = "aarch64", target_feature = "neon"`: the rounding conversions and the packed kernels of the slice functions
+//!
+//! On aarch64 the default implementation already converts with a single FCVTZS or FCVTZU instruction, also to 8 and 16 bit integers through i32, so this target module only adds the rounding conversions and the packed kernels. [`is_specialized`] is true for the conversions whose rounding conversions use FCVTMS and similar instructions, which are all except 128 bit integers.
+//!
+//! # Slices
+//!
+//! Besides the scalar functions there are functions that convert whole slices like [`convert_slice`]. They are generic over the [`FastFrom`] trait, which is implemented for every conversion. The [`FloatType`] and [`IntType`] traits describe the input and output types for your own generic code.
+//!
+//! [`convert_le_bytes`] and [`convert_be_bytes`] convert floats that are stored as bytes, like in file formats and network protocols. [`convert_ne_bytes`] converts packed native endian floats like memory mapped columns, whether they are aligned or not.
+//!
+//! `convert_atomic_f32_slice` and `convert_atomic_f64_slice` convert floats that are stored as bits in `AtomicU32` and `AtomicU64`, like buffers that real-time audio threads share without locks. They load the elements with relaxed ordering in small chunks instead of copying the whole buffer first.
+//!
+//! [`convert_options`] and [`convert_slice_nan_to_null`] convert columns with missing values like dataframe libraries have. They write a sentinel for the missing values and produce a validity bitmap.
+//!
+//! [`convert_slice_prefetched`] prefetches the source ahead of the conversion for buffers that are streamed from RAM. [`convert_slice_prefetched_with_distance`] sets the distance.
+//!
+//! [`convert_slice_with_stats`] also reports the minimum, maximum and number of out of range values, which is useful for monitoring data quality.
+//!
+//! [`convert_transpose`] converts a row major matrix into its transpose in one pass, for example into an integer texture with swapped rows and columns.
+//!
+//! [`convert_planes`] converts planar multichannel data in one call. With the `rayon` feature, `par_convert_planes` converts the planes in parallel.
+//!
+//! With the `rayon` feature, `par_convert_slice` converts one large slice in parallel chunks. The result is exactly the same as the one of [`convert_slice`], also for out of range inputs. `par_convert_slice_with_chunk_size` sets the number of elements per task.
+//!
+//! Functions like [`f32_to_u32_negative_to_zero`] guarantee 0 for negative inputs and NaN.
+//!
+//! Functions like [`f32_to_i32_with_fract`] also return the fractional part that the conversion truncates, which interpolation code needs.
+//!
+//! [`f64_to_seconds_nanos`] and [`f64_to_duration`] split seconds into whole seconds and nanoseconds for timespec and [`Duration`](core::time::Duration) values.
+//!
+//! Functions like [`f64_to_i32_tolerant`] treat inputs slightly closer to 0 than an integer as that integer, which hides accumulated floating point error like 2.9999999.
+//!
+//! [`f32_to_grid_floor`] and [`f32_to_grid_round`] convert world coordinates to the coordinates of grid cells with a cell size that is known at compile time, like the tiles and voxels of games.
+//!
+//! Functions like [`f32_bits_to_i32`] take the IEEE 754 bit pattern of the float, for example from a network packet or a sensor register. With the `soft-float` feature they decode the bits with integer arithmetic, which is faster on targets without an FPU.
+//!
+//! [`convert_bits`] converts an iterator of bit patterns, like the packed float fields that a streaming decoder reads from a bitstream, without a buffer of floats.
+//!
+//! The [`ct`] module has the conversions that compile to code without branches on the specialized targets, x86 and x86_64 with SSE and aarch64 with NEON, for code that must not leak the input through timing.
+//!
+//! Functions like [`f32_to_i32_floor`], [`f32_to_i32_ceil`], [`f32_to_i32_round`] and [`f32_to_i32_round_ties_even`] round in a direction and convert in one step. On aarch64 they are a single FCVTMS, FCVTPS, FCVTAS or FCVTNS instruction, or the unsigned equivalent, instead of a rounding and a conversion. On x86 with the `sse4.1` target feature, floor and ceil are a ROUNDSS or ROUNDSD instruction followed by the truncating conversion. Without it, they correct the result of the truncating conversion.
+//!
+//! Functions like [`f32_to_i32_pretruncated`] convert floats that are already integers, for example because the pipeline called `trunc` earlier. They skip the rounding of the rounding conversions and check with debug assertions that the input has no fractional part.
+//!
+//! [`checked_convert_slice_bitmap`] converts a slice and marks every out of range element in a bitmap instead of stopping at the first. With the `std` feature, `checked_convert_slice_indices` collects their indices in a `Vec`.
+//!
+//! [`exact_pow2_f32`] and [`exact_pow2_f64`] return exact powers of two, including negative exponents, in const contexts. They are the scale factors of fixed point formats and the thresholds of the conversions in this crate. A power that is not representable is a compile time error in a `const` item.
+//!
+//! With the `warn-fallback` feature, the [`warn_fallback`] macro warns at compile time if the target has no specialized implementation, so you notice when you do not get a speedup.
+//!
+//! With the `std` feature, `convert_chunks_in_background` converts the chunks of a slow source like a file on a background thread while you process the previous chunk.
+//!
+//! With the `std` feature, `analyze` reports how the values of sample data are distributed with respect to the range of an integer type and recommends a conversion variant.
+//!
+//! With the `trace` feature, the slice functions report once which implementation was selected at compile time and when a slice is converted with the scalar path instead of a packed kernel, for example because it is shorter than one kernel call. The events go to standard error or to the hook that `set_trace_hook` sets, which can forward them to `log` or `tracing`. The crate has no runtime dispatch, so there are no events for it. The feature needs the standard library.
+//!
+//! With the `std` feature, `measure_conversion_throughput` measures how fast a conversion is on your hardware and data and prints the result in a format that is comparable between machines, for example for bug reports.
+//!
+//! With the `itoa` feature, `write_decimal` converts a slice and writes the integers as decimal text in one pass, for example to a CSV file. It takes the conversion as an argument so that you can truncate or round with the functions of this crate. The feature needs the standard library.
+//!
+//! With the `arbitrary-int` feature, `f32_to_arbitrary_int` and `f64_to_arbitrary_int` convert to the integer types of the [arbitrary-int](https://crates.io/crates/arbitrary-int) crate like `u12` and `i4` with saturation, for the fields of protocols and hardware registers. The feature needs Rust 1.83.
+//!
+//! With the `expect-finite` feature, functions like `f32_to_i32_expect_finite` panic if the input is NaN.
+//!
+//! The `small-code` feature prefers smaller code over faster code, which helps on embedded targets with little flash. The conversion functions are not forced to be inlined and the slice functions convert one element at a time. The branchless u64 conversions stay because they are also smaller than the branchful alternative.
+//!
+//! On x86_64 the f32 and f64 to u64 conversions subtract 2^63, which the compiler loads from memory. The `register-constants` feature builds the constant in a register from an immediate instead. This avoids a cache miss when the conversion runs rarely, in cold code, at the cost of two instructions in hot loops. The `cold_u64` benchmark group compares both.
+//!
+//! With the `avx512` feature on x86_64, functions like `f32_to_u32_avx512` are compiled with `#[target_feature(enable = "avx512f")]`. They are for applications that detect the CPU at runtime and cannot compile the whole crate with the target feature. The feature needs Rust 1.89.
+//!
+//! [`convert_array`] converts an array. Functions like [`f32_to_i32_x4`] convert arrays of 2, 3, 4 and 8 elements, like the vectors of game math, and are unrolled by hand so that they never contain a loop.
+//!
+//! Functions like `m128_to_i32x4` on x86 and `float32x4_to_int32x4` on aarch64 convert SIMD registers directly, without going through arrays.
+//!
+//! Functions like [`f32_to_i32_small`] convert floats of a small magnitude, at most 2^22 for f32 and 2^51 for f64, by adding a magic constant and subtracting its bit pattern. They round to the nearest integer with ties to even. This avoids the conversion instruction, which helps on cores where it is slow, and on 32 bit x86 for 64 bit integers.
+//!
+//! [`f64_to_i64_decimal_scaled`] multiplies by a power of ten before the conversion, for example to convert amounts of money to cents. The power of ten is a correctly rounded constant.
+//!
+//! [`behavior`] describes what the conversions of the active target do for inputs that are out of range.
+//!
+//! [`self_test`] checks at runtime that the conversions give the expected results on a few hundred boundary inputs. Deployments that verify their dependencies on the actual hardware can call it at startup.
+//!
+//! Under [Kani](https://github.com/model-checking/kani) the conversion functions like [`f32_to_i32`] have a function contract: if the input is in range, then the result is the same as the one of `as`. Code that is verified with Kani can replace the calls with the contract with `#[kani::stub_verified(fast_float_to_integer::f32_to_i32)]` instead of treating them as opaque.
+//!
+//! Functions like [`nan_boxed_to_i32`] decode the NaN-boxed values of dynamic language runtimes. They either convert the number or extract the payload of a boxed value.
+//!
+//! [`CONVERSIONS`] has every conversion with the same function signature. It selects a conversion at runtime from the input and output types, for example in an interpreter.
+//!
+//! [`convert_with`] is a generic entry point that selects the behavior for out of range inputs with a policy type like [`Saturate`] or [`Checked`].
+//!
+//! [`Converter`] carries the guarantee for out of range inputs in its type, like `Converter<f32, i32, Saturate>`. The guarantees are the policies [`Unspecified`], [`Deterministic`], [`Saturate`] and [`Wrap`]. A library can accept a converter with an [`AtLeast`] bound on the guarantee, so that the compiler rejects converters with a weaker guarantee.
+//!
+//! [`Convert`] combines the options in a builder: `Convert::new().rounding(Floor).overflow(Saturate).scale(256.0)`. The rounding direction and the guarantee are types, so every combination compiles to the code of the matching functions like [`f32_to_i32_floor`]. Its `slice` method uses the packed kernels where there are any for the options, also with a scale factor.
+//!
+//! With the `unstable-targets` feature, the implementations of every target module that is available for the compilation target are public in the `backends` module, independent of which one is active. This is useful for your own dispatch or for differential testing. The module is not covered by semver.
+//!
+//! With the `portable-deterministic` feature, every conversion decodes the bit pattern of the float with integer arithmetic like the `soft-float` feature. This is slower than the conversion instructions of the hardware but the results are the same on every target, including the results for out of range inputs and NaN. Most of those results depend on the bits of the input, so [`behavior`] reports them as unspecified even though they are deterministic. This is for code that must compute the same results on every machine, like lockstep multiplayer games. The feature takes precedence over the target specific implementations. It does not affect the functions that convert SIMD registers and the `*_avx512` functions, which only exist on their target.
+//!
+//! The results of out of range inputs differ between targets. On targets without a specialized implementation they usually saturate like the standard `as` operator, which can hide code that relies on saturation until it runs on a target where it does not. The `simulate-unspecified` feature is for testing: the default target module then returns the value with only the sign bit set for out of range inputs and NaN, and [`behavior`] reports unspecified results. Enable it together with the internal `force-default` feature to test on any machine. It has no effect together with `portable-deterministic`, whose results are the same on every target.
+//!
+//! The crate is `no_std`. The `std`, `rayon`, `trace` and `itoa` features pull in the standard library. Every other feature keeps the crate `no_std`.
+//!
+//! The minimum supported Rust version is 1.71. Features that need a newer version say so, like `avx512`, which needs 1.89, and `rayon` and `arbitrary-int`, whose current versions need 1.80 and 1.83. The conversions and the other features keep working with 1.71 when these features are disabled.
+//!
+//! # Subnormal floats
+//!
+//! Subnormal inputs of either sign convert to 0 in every conversion, including the slice functions, the `*_bits_to_*` functions and the `soft-float` feature. Some applications like audio and game engines enable the FTZ (flush to zero) and DAZ (denormals are zero) modes of the CPU. DAZ makes the CPU read subnormal inputs as 0, which also converts to 0, so the result does not depend on the mode. FTZ only affects float results and the conversions return integers.
+//!
+//! Rust assumes the default floating point environment and does not support changing it. We test this by hand on x86 with the ignored `subnormal_ftz_daz` test.
 //!
 //! # to_int_unchecked
 //!
@@ -89,26 +198,16 @@
 //!     ret
 //! ```
 
-#![cfg_attr(not(test), no_std)]
-
-/// Raise two to some power.
-///
-/// This function exists because libcore does not provide the [`f32::powi]`] family of functions.
-#[allow(dead_code)]
-const fn power_of_two_f32(exponent: u32) -> f32 {
-    (2u128).pow(exponent) as f32
-}
-
-/// Like power_of_two_f32 but for f64.
-#[allow(dead_code)]
-const fn power_of_two_f64(exponent: u32) -> f64 {
-    (2u128).pow(exponent) as f64
-}
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 
 macro_rules! create_target {
     ($name:ident) => {
         use $name as active_target;
 
+        /// The name of the active target module for the events of the `trace` feature.
+        #[cfg(feature = "trace")]
+        const ACTIVE_TARGET: &str = stringify!($name);
+
         // Create a test with the target name so we can check that the expected target is active. The following command prints the active target through the test name:
         //
         // cargo test --quiet --package fast-float-to-integer --lib -- --list
@@ -117,11 +216,143 @@ macro_rules! create_target {
     };
 }
 
-// Conditionally compiled target specific modules.The condition is set based on the availability of the intrinsics they use. This makes it safe to use the module. See the `default` module for the interface.
+/// Create the `implementation` module of a target module.
+///
+/// Every conversion names a function of the target module. The result of the function is converted to the output type with `as`. The names are resolved relative to the target module, not the inner module, and the macro only accepts every conversion exactly once in the order of the declaration. This prevents a wrapper from calling itself or a conversion from being forgotten.
+macro_rules! create_implementation {
+    (
+        f32_to_i8: $($via_f32_i8:ident)::+,
+        f32_to_u8: $($via_f32_u8:ident)::+,
+        f32_to_i16: $($via_f32_i16:ident)::+,
+        f32_to_u16: $($via_f32_u16:ident)::+,
+        f32_to_i32: $($via_f32_i32:ident)::+,
+        f32_to_u32: $($via_f32_u32:ident)::+,
+        f32_to_i64: $($via_f32_i64:ident)::+,
+        f32_to_u64: $($via_f32_u64:ident)::+,
+        f32_to_i128: $($via_f32_i128:ident)::+,
+        f32_to_u128: $($via_f32_u128:ident)::+,
+        f64_to_i8: $($via_f64_i8:ident)::+,
+        f64_to_u8: $($via_f64_u8:ident)::+,
+        f64_to_i16: $($via_f64_i16:ident)::+,
+        f64_to_u16: $($via_f64_u16:ident)::+,
+        f64_to_i32: $($via_f64_i32:ident)::+,
+        f64_to_u32: $($via_f64_u32:ident)::+,
+        f64_to_i64: $($via_f64_i64:ident)::+,
+        f64_to_u64: $($via_f64_u64:ident)::+,
+        f64_to_i128: $($via_f64_i128:ident)::+,
+        f64_to_u128: $($via_f64_u128:ident)::+,
+    ) => {
+        // There is an inner module to separate the implementation from the interface.
+        pub mod implementation {
+            create_implementation!(@function f32_to_i8, f32, i8, $($via_f32_i8)::+);
+            create_implementation!(@function f32_to_u8, f32, u8, $($via_f32_u8)::+);
+            create_implementation!(@function f32_to_i16, f32, i16, $($via_f32_i16)::+);
+            create_implementation!(@function f32_to_u16, f32, u16, $($via_f32_u16)::+);
+            create_implementation!(@function f32_to_i32, f32, i32, $($via_f32_i32)::+);
+            create_implementation!(@function f32_to_u32, f32, u32, $($via_f32_u32)::+);
+            create_implementation!(@function f32_to_i64, f32, i64, $($via_f32_i64)::+);
+            create_implementation!(@function f32_to_u64, f32, u64, $($via_f32_u64)::+);
+            create_implementation!(@function f32_to_i128, f32, i128, $($via_f32_i128)::+);
+            create_implementation!(@function f32_to_u128, f32, u128, $($via_f32_u128)::+);
+            create_implementation!(@function f64_to_i8, f64, i8, $($via_f64_i8)::+);
+            create_implementation!(@function f64_to_u8, f64, u8, $($via_f64_u8)::+);
+            create_implementation!(@function f64_to_i16, f64, i16, $($via_f64_i16)::+);
+            create_implementation!(@function f64_to_u16, f64, u16, $($via_f64_u16)::+);
+            create_implementation!(@function f64_to_i32, f64, i32, $($via_f64_i32)::+);
+            create_implementation!(@function f64_to_u32, f64, u32, $($via_f64_u32)::+);
+            create_implementation!(@function f64_to_i64, f64, i64, $($via_f64_i64)::+);
+            create_implementation!(@function f64_to_u64, f64, u64, $($via_f64_u64)::+);
+            create_implementation!(@function f64_to_i128, f64, i128, $($via_f64_i128)::+);
+            create_implementation!(@function f64_to_u128, f64, u128, $($via_f64_u128)::+);
+        }
+    };
+    (@function $name:ident, $Float:ty, $Integer:ty, $($via:ident)::+) => {
+        #[inline(always)]
+        pub fn $name(float: $Float) -> $Integer {
+            super::$($via)::+(float) as _
+        }
+    };
+}
+
+/// Create the `rounding` module of the x86 target modules with SSE4.1.
+///
+/// ROUNDSS and ROUNDSD round toward negative or positive infinity and the truncating conversion of the target module converts the integer valued result, which is two instructions for the conversions that are one instruction. Without SSE4.1 the rounding conversions use the sign correction of `software::rounding`, which also handles the other rounding directions.
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse4.1",
+    not(any(feature = "force-default", feature = "portable-deterministic", kani))
+))]
+macro_rules! create_sse41_rounding {
+    () => {
+        pub mod rounding {
+            #[cfg(target_arch = "x86")]
+            use core::arch::x86::*;
+            #[cfg(target_arch = "x86_64")]
+            use core::arch::x86_64::*;
+
+            create_sse41_rounding!(@function f32_to_i8, f32_to_i8_floor, f32_to_i8_ceil, f32, i8, _mm_set_ss, _mm_floor_ss, _mm_ceil_ss, _mm_cvtss_f32);
+            create_sse41_rounding!(@function f32_to_u8, f32_to_u8_floor, f32_to_u8_ceil, f32, u8, _mm_set_ss, _mm_floor_ss, _mm_ceil_ss, _mm_cvtss_f32);
+            create_sse41_rounding!(@function f32_to_i16, f32_to_i16_floor, f32_to_i16_ceil, f32, i16, _mm_set_ss, _mm_floor_ss, _mm_ceil_ss, _mm_cvtss_f32);
+            create_sse41_rounding!(@function f32_to_u16, f32_to_u16_floor, f32_to_u16_ceil, f32, u16, _mm_set_ss, _mm_floor_ss, _mm_ceil_ss, _mm_cvtss_f32);
+            create_sse41_rounding!(@function f32_to_i32, f32_to_i32_floor, f32_to_i32_ceil, f32, i32, _mm_set_ss, _mm_floor_ss, _mm_ceil_ss, _mm_cvtss_f32);
+            create_sse41_rounding!(@function f32_to_u32, f32_to_u32_floor, f32_to_u32_ceil, f32, u32, _mm_set_ss, _mm_floor_ss, _mm_ceil_ss, _mm_cvtss_f32);
+            create_sse41_rounding!(@function f32_to_i64, f32_to_i64_floor, f32_to_i64_ceil, f32, i64, _mm_set_ss, _mm_floor_ss, _mm_ceil_ss, _mm_cvtss_f32);
+            create_sse41_rounding!(@function f32_to_u64, f32_to_u64_floor, f32_to_u64_ceil, f32, u64, _mm_set_ss, _mm_floor_ss, _mm_ceil_ss, _mm_cvtss_f32);
+            create_sse41_rounding!(@function f32_to_i128, f32_to_i128_floor, f32_to_i128_ceil, f32, i128, _mm_set_ss, _mm_floor_ss, _mm_ceil_ss, _mm_cvtss_f32);
+            create_sse41_rounding!(@function f32_to_u128, f32_to_u128_floor, f32_to_u128_ceil, f32, u128, _mm_set_ss, _mm_floor_ss, _mm_ceil_ss, _mm_cvtss_f32);
+            create_sse41_rounding!(@function f64_to_i8, f64_to_i8_floor, f64_to_i8_ceil, f64, i8, _mm_set_sd, _mm_floor_sd, _mm_ceil_sd, _mm_cvtsd_f64);
+            create_sse41_rounding!(@function f64_to_u8, f64_to_u8_floor, f64_to_u8_ceil, f64, u8, _mm_set_sd, _mm_floor_sd, _mm_ceil_sd, _mm_cvtsd_f64);
+            create_sse41_rounding!(@function f64_to_i16, f64_to_i16_floor, f64_to_i16_ceil, f64, i16, _mm_set_sd, _mm_floor_sd, _mm_ceil_sd, _mm_cvtsd_f64);
+            create_sse41_rounding!(@function f64_to_u16, f64_to_u16_floor, f64_to_u16_ceil, f64, u16, _mm_set_sd, _mm_floor_sd, _mm_ceil_sd, _mm_cvtsd_f64);
+            create_sse41_rounding!(@function f64_to_i32, f64_to_i32_floor, f64_to_i32_ceil, f64, i32, _mm_set_sd, _mm_floor_sd, _mm_ceil_sd, _mm_cvtsd_f64);
+            create_sse41_rounding!(@function f64_to_u32, f64_to_u32_floor, f64_to_u32_ceil, f64, u32, _mm_set_sd, _mm_floor_sd, _mm_ceil_sd, _mm_cvtsd_f64);
+            create_sse41_rounding!(@function f64_to_i64, f64_to_i64_floor, f64_to_i64_ceil, f64, i64, _mm_set_sd, _mm_floor_sd, _mm_ceil_sd, _mm_cvtsd_f64);
+            create_sse41_rounding!(@function f64_to_u64, f64_to_u64_floor, f64_to_u64_ceil, f64, u64, _mm_set_sd, _mm_floor_sd, _mm_ceil_sd, _mm_cvtsd_f64);
+            create_sse41_rounding!(@function f64_to_i128, f64_to_i128_floor, f64_to_i128_ceil, f64, i128, _mm_set_sd, _mm_floor_sd, _mm_ceil_sd, _mm_cvtsd_f64);
+            create_sse41_rounding!(@function f64_to_u128, f64_to_u128_floor, f64_to_u128_ceil, f64, u128, _mm_set_sd, _mm_floor_sd, _mm_ceil_sd, _mm_cvtsd_f64);
+
+            pub use crate::software::rounding::{
+                f32_to_i128_round, f32_to_i128_round_ties_even, f32_to_i16_round, f32_to_i16_round_ties_even,
+                f32_to_i32_round, f32_to_i32_round_ties_even, f32_to_i64_round, f32_to_i64_round_ties_even,
+                f32_to_i8_round, f32_to_i8_round_ties_even, f32_to_u128_round, f32_to_u128_round_ties_even,
+                f32_to_u16_round, f32_to_u16_round_ties_even, f32_to_u32_round, f32_to_u32_round_ties_even,
+                f32_to_u64_round, f32_to_u64_round_ties_even, f32_to_u8_round, f32_to_u8_round_ties_even,
+                f64_to_i128_round, f64_to_i128_round_ties_even, f64_to_i16_round, f64_to_i16_round_ties_even,
+                f64_to_i32_round, f64_to_i32_round_ties_even, f64_to_i64_round, f64_to_i64_round_ties_even,
+                f64_to_i8_round, f64_to_i8_round_ties_even, f64_to_u128_round, f64_to_u128_round_ties_even,
+                f64_to_u16_round, f64_to_u16_round_ties_even, f64_to_u32_round, f64_to_u32_round_ties_even,
+                f64_to_u64_round, f64_to_u64_round_ties_even, f64_to_u8_round, f64_to_u8_round_ties_even,
+            };
+        }
+    };
+    (@function $convert:ident, $floor:ident, $ceil:ident, $Float:ty, $Integer:ty, $set:ident, $round_floor:ident, $round_ceil:ident, $get:ident) => {
+        #[inline(always)]
+        pub fn $floor(float: $Float) -> $Integer {
+            // see crate::x86_64_sse::f32_to_i64
+            let floats_register = unsafe { $set(float) };
+            let rounded = unsafe { $get($round_floor(floats_register, floats_register)) };
+            super::implementation::$convert(rounded)
+        }
+
+        #[inline(always)]
+        pub fn $ceil(float: $Float) -> $Integer {
+            let floats_register = unsafe { $set(float) };
+            let rounded = unsafe { $get($round_ceil(floats_register, floats_register)) };
+            super::implementation::$convert(rounded)
+        }
+    };
+}
+
+// Conditionally compiled target specific modules.The condition is set based on the availability of the intrinsics they use. This makes it safe to use the module. See the `default` module for the interface. The algorithms that do not depend on the target are in the `software` module and shared by the target modules.
 //
 // We would put the mod declaration inside of the create_target macro too, but then rustfmt does not understand it.
+//
+// Kani does not model the SSE and NEON intrinsics, so it verifies the contracts with the default target module.
 cfg_if::cfg_if! {
-    if #[cfg(feature = "force-default")] {
+    if #[cfg(feature = "portable-deterministic")] {
+        mod target_portable;
+        create_target!(target_portable);
+    } else if #[cfg(any(feature = "force-default", kani))] {
         mod target_default;
         create_target!(target_default);
     } else if #[cfg(all(target_arch = "x86_64", target_feature = "sse"))] {
@@ -130,30 +361,701 @@ cfg_if::cfg_if! {
     } else if #[cfg(all(target_arch = "x86", target_feature = "sse"))] {
         mod target_x86_sse;
         create_target!(target_x86_sse);
+    } else if #[cfg(all(target_arch = "aarch64", target_feature = "neon"))] {
+        mod target_aarch64_neon;
+        create_target!(target_aarch64_neon);
     } else {
         mod target_default;
         create_target!(target_default);
     }
 }
 
+// The `backends` module exposes every available target module, not only the active one. Declare the available modules that the cfg_if above did not declare.
+#[cfg(all(
+    feature = "unstable-targets",
+    any(feature = "force-default", feature = "portable-deterministic", kani),
+    target_arch = "aarch64",
+    target_feature = "neon",
+))]
+mod target_aarch64_neon;
+// The aarch64_neon target module uses the scalar conversions of the default target module, so it is also declared when aarch64_neon is active.
+#[cfg(any(
+    all(
+        feature = "unstable-targets",
+        any(
+            feature = "portable-deterministic",
+            all(
+                not(any(feature = "force-default", kani)),
+                any(
+                    all(target_arch = "x86_64", target_feature = "sse"),
+                    all(target_arch = "x86", target_feature = "sse"),
+                ),
+            ),
+        ),
+    ),
+    all(
+        not(any(feature = "portable-deterministic", feature = "force-default", kani)),
+        target_arch = "aarch64",
+        target_feature = "neon",
+    ),
+))]
+mod target_default;
+#[cfg(all(
+    feature = "unstable-targets",
+    any(feature = "force-default", feature = "portable-deterministic", kani),
+    target_arch = "x86_64",
+    target_feature = "sse",
+))]
+mod target_x86_64_sse;
+#[cfg(all(
+    feature = "unstable-targets",
+    any(feature = "force-default", feature = "portable-deterministic", kani),
+    target_arch = "x86",
+    target_feature = "sse",
+))]
+mod target_x86_sse;
+
+#[cfg(feature = "unstable-targets")]
+pub mod backends;
+
+pub mod ct;
+
+#[cfg(feature = "std")]
+mod analyze;
+#[cfg(feature = "arbitrary-int")]
+mod arbitrary_width;
+mod array;
+mod audio;
+#[cfg(all(feature = "avx512", target_arch = "x86_64"))]
+mod avx512;
+mod behavior;
+mod bits;
+mod checked_slice;
+#[cfg(feature = "std")]
+mod chunks;
+mod clamp_nearest;
+mod conversion_error;
+mod convert;
+mod converter;
+#[cfg(feature = "itoa")]
+mod decimal;
+mod exact_pow2;
+#[cfg(feature = "expect-finite")]
+mod expect_finite;
+mod fixed_point;
+mod grid;
+mod in_range;
+mod index;
+#[doc(hidden)]
+pub mod kernel;
+#[cfg(feature = "std")]
+mod measure;
+mod nan_boxed;
+mod negative_to_zero;
+mod normalized;
+mod nullable;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod planar;
+mod policy;
+mod prefetch;
+mod pretruncated;
+#[cfg(kani)]
+mod proofs;
+mod rounding;
+mod scaled;
+mod seconds;
+mod self_test;
+#[cfg(any(
+    all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2"
+    ),
+    all(target_arch = "aarch64", target_feature = "neon")
+))]
+mod simd;
+mod slice;
+mod small;
+mod software;
+mod stats;
+mod table;
+mod tolerant;
+#[cfg(feature = "trace")]
+mod trace;
+mod transpose;
+mod via_f32;
+mod warn_fallback;
+
+#[cfg(feature = "std")]
+pub use analyze::*;
+#[cfg(feature = "arbitrary-int")]
+pub use arbitrary_width::*;
+pub use array::*;
+pub use audio::*;
+#[cfg(all(feature = "avx512", target_arch = "x86_64"))]
+pub use avx512::*;
+pub use behavior::*;
+pub use bits::*;
+pub use checked_slice::*;
+#[cfg(feature = "std")]
+pub use chunks::*;
+pub use clamp_nearest::*;
+pub use conversion_error::*;
+pub use convert::*;
+pub use converter::*;
+#[cfg(feature = "itoa")]
+pub use decimal::*;
+pub use exact_pow2::*;
+#[cfg(feature = "expect-finite")]
+pub use expect_finite::*;
+pub use fixed_point::*;
+pub use grid::*;
+pub use in_range::*;
+pub use index::*;
+#[cfg(feature = "std")]
+pub use measure::*;
+pub use nan_boxed::*;
+pub use negative_to_zero::*;
+pub use normalized::*;
+pub use nullable::*;
+#[cfg(feature = "rayon")]
+pub use parallel::*;
+pub use planar::*;
+pub use policy::*;
+pub use prefetch::*;
+pub use pretruncated::*;
+pub use rounding::*;
+pub use scaled::*;
+pub use seconds::*;
+pub use self_test::*;
+#[cfg(any(
+    all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2"
+    ),
+    all(target_arch = "aarch64", target_feature = "neon")
+))]
+pub use simd::*;
+pub use slice::*;
+pub use small::*;
+pub use stats::*;
+pub use table::*;
+pub use tolerant::*;
+#[cfg(feature = "trace")]
+pub use trace::*;
+pub use transpose::*;
+pub use via_f32::*;
+pub use warn_fallback::*;
+
+mod sealed {
+    /// Integer operations that the slice functions need.
+    pub trait Integer: Copy + Ord {
+        const ZERO: Self;
+
+        fn wrapping_add(self, other: Self) -> Self;
+
+        /// Keep the low bits of the value like the `as` operator.
+        fn truncate_from_u128(value: u128) -> Self;
+    }
+
+    macro_rules! implement_integer {
+        ($($Integer:ty),*) => {
+            $(
+                impl Integer for $Integer {
+                    const ZERO: Self = 0;
+
+                    #[inline(always)]
+                    fn wrapping_add(self, other: Self) -> Self {
+                        <$Integer>::wrapping_add(self, other)
+                    }
+
+                    #[inline(always)]
+                    fn truncate_from_u128(value: u128) -> Self {
+                        value as _
+                    }
+                }
+            )*
+        };
+    }
+
+    implement_integer! {i8, u8, i16, u16, i32, u32, i64, u64, i128, u128}
+
+    pub trait Float: Copy + core::ops::Mul<Output = Self> {
+        const ZERO: Self;
+
+        fn to_f64(self) -> f64;
+
+        /// Decode from exactly as many little endian bytes as the type has.
+        fn from_le_bytes(bytes: &[u8]) -> Self;
+
+        /// Decode from exactly as many big endian bytes as the type has.
+        fn from_be_bytes(bytes: &[u8]) -> Self;
+
+        /// Decode from exactly as many native endian bytes as the type has.
+        fn from_ne_bytes(bytes: &[u8]) -> Self;
+
+        /// Convert with the `as` operator, which rounds to nearest for f32.
+        fn from_f64(float: f64) -> Self;
+
+        /// Round to the nearest integer with ties to even. NaN and infinities are unchanged.
+        fn round_ties_even(self) -> Self;
+
+        /// Round toward negative infinity. NaN and infinities are unchanged.
+        fn floor(self) -> Self;
+
+        /// Round toward positive infinity. NaN and infinities are unchanged.
+        fn ceil(self) -> Self;
+
+        /// Round to the nearest integer with ties away from zero. NaN and infinities are unchanged.
+        fn round(self) -> Self;
+
+        fn is_nan(self) -> bool;
+
+        fn exact_pow2(exponent: i32) -> Self;
+    }
+
+    // The standard library has round_ties_even since Rust 1.77 and only in std.
+    macro_rules! implement_float {
+        ($Float:ty, $Bits:ty, $exact_pow2:ident) => {
+            impl Float for $Float {
+                const ZERO: Self = 0.;
+
+                #[inline(always)]
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                #[inline(always)]
+                fn from_le_bytes(bytes: &[u8]) -> Self {
+                    <$Float>::from_le_bytes(bytes.try_into().unwrap())
+                }
+
+                #[inline(always)]
+                fn from_be_bytes(bytes: &[u8]) -> Self {
+                    <$Float>::from_be_bytes(bytes.try_into().unwrap())
+                }
+
+                #[inline(always)]
+                fn from_ne_bytes(bytes: &[u8]) -> Self {
+                    <$Float>::from_ne_bytes(bytes.try_into().unwrap())
+                }
+
+                #[inline(always)]
+                fn from_f64(float: f64) -> Self {
+                    float as _
+                }
+
+                #[inline(always)]
+                fn round_ties_even(self) -> Self {
+                    // Floats of this magnitude have no fractional bits. Adding and subtracting it rounds smaller magnitudes in the default rounding mode, which is to nearest with ties to even.
+                    const MAGIC: $Float = (1u64 << (<$Float>::MANTISSA_DIGITS - 1)) as $Float;
+                    const SIGN: $Bits = 1 << (<$Bits>::BITS - 1);
+
+                    let bits = self.to_bits();
+                    let magnitude = <$Float>::from_bits(bits & !SIGN);
+                    if magnitude < MAGIC {
+                        // Restoring the sign keeps -0.4 at -0.0 like the standard library.
+                        let rounded = (magnitude + MAGIC) - MAGIC;
+                        <$Float>::from_bits(rounded.to_bits() | (bits & SIGN))
+                    } else {
+                        // Already an integer, infinite or NaN. The comparison is false for NaN.
+                        self
+                    }
+                }
+
+                // The other directions correct the result of round_ties_even by one. Integer valued floats of this magnitude are exact, so the corrections are exact. The comparisons are false for NaN.
+
+                #[inline(always)]
+                fn floor(self) -> Self {
+                    let rounded = Float::round_ties_even(self);
+                    if rounded > self {
+                        rounded - 1.
+                    } else {
+                        rounded
+                    }
+                }
+
+                #[inline(always)]
+                fn ceil(self) -> Self {
+                    let rounded = Float::round_ties_even(self);
+                    if rounded < self {
+                        rounded + 1.
+                    } else {
+                        rounded
+                    }
+                }
+
+                #[inline(always)]
+                fn round(self) -> Self {
+                    // see crate::software::rounding
+                    let truncated = if self >= 0. {
+                        Float::floor(self)
+                    } else {
+                        Float::ceil(self)
+                    };
+                    let fraction = self - truncated;
+                    if fraction >= 0.5 {
+                        truncated + 1.
+                    } else if fraction <= -0.5 {
+                        truncated - 1.
+                    } else {
+                        truncated
+                    }
+                }
+
+                #[inline(always)]
+                fn is_nan(self) -> bool {
+                    <$Float>::is_nan(self)
+                }
+
+                #[inline(always)]
+                fn exact_pow2(exponent: i32) -> Self {
+                    crate::$exact_pow2(exponent)
+                }
+            }
+        };
+    }
+
+    implement_float! {f32, u32, exact_pow2_f32}
+    implement_float! {f64, u64, exact_pow2_f64}
+
+    pub trait Policy {}
+
+    pub trait FromBits<Bits> {}
+
+    /// The conversion of a policy that is a guarantee of a `Converter`. It has the bounds of the conversion functions, so that code that is generic over the guarantee does not need the bounds of the policy.
+    pub trait Guarantee: Policy {
+        fn convert<Float: crate::FloatType, Integer: crate::FastFrom<Float>>(
+            float: Float,
+        ) -> Integer;
+
+        /// Round with `R` and convert with the behavior of this guarantee.
+        fn convert_rounded<R, Float, Integer>(float: Float) -> Integer
+        where
+            R: crate::Rounding,
+            Float: crate::FloatType,
+            Integer: crate::FastFrom<Float>;
+
+        /// Like convert_rounded for slices of the same length.
+        fn convert_rounded_slice<R, Float, Integer>(src: &[Float], dst: &mut [Integer])
+        where
+            R: crate::Rounding,
+            Float: crate::FloatType,
+            Integer: crate::FastFrom<Float>;
+    }
+
+    /// The conversions of the `rounding` module. They have unspecified results for out of range inputs.
+    pub trait RoundFrom<Float> {
+        fn floor_from(float: Float) -> Self;
+
+        fn ceil_from(float: Float) -> Self;
+
+        fn round_from(float: Float) -> Self;
+
+        fn round_ties_even_from(float: Float) -> Self;
+    }
+
+    /// A rounding direction of the `Convert` builder. The methods round the input and convert it with the behavior of a policy. The slice methods get slices of the same length and convert element by element unless a rounding direction has a packed kernel.
+    pub trait Rounding {
+        /// Round to an integer valued float.
+        fn round<Float: crate::FloatType>(float: Float) -> Float;
+
+        fn unspecified<Float: crate::FloatType, Integer: crate::FastFrom<Float>>(
+            float: Float,
+        ) -> Integer;
+
+        #[inline(always)]
+        fn unspecified_slice<Float: crate::FloatType, Integer: crate::FastFrom<Float>>(
+            src: &[Float],
+            dst: &mut [Integer],
+        ) {
+            for (float, integer) in src.iter().zip(dst) {
+                *integer = Self::unspecified(*float);
+            }
+        }
+
+        #[inline(always)]
+        fn saturating<Float: crate::FloatType, Integer: crate::FastFrom<Float>>(
+            float: Float,
+        ) -> Integer {
+            Integer::saturating_from(Self::round(float))
+        }
+
+        #[inline(always)]
+        fn saturating_slice<Float: crate::FloatType, Integer: crate::FastFrom<Float>>(
+            src: &[Float],
+            dst: &mut [Integer],
+        ) {
+            for (float, integer) in src.iter().zip(dst) {
+                *integer = Self::saturating(*float);
+            }
+        }
+
+        #[inline(always)]
+        fn wrapping<Float: crate::FloatType, Integer: crate::FastFrom<Float>>(
+            float: Float,
+        ) -> Integer {
+            crate::convert_with::<crate::Wrap, Float, Integer>(Self::round(float))
+        }
+
+        #[inline(always)]
+        fn wrapping_slice<Float: crate::FloatType, Integer: crate::FastFrom<Float>>(
+            src: &[Float],
+            dst: &mut [Integer],
+        ) {
+            for (float, integer) in src.iter().zip(dst) {
+                *integer = Self::wrapping(*float);
+            }
+        }
+    }
+
+    /// The scale factor of the `Convert` builder.
+    pub trait Scaling: Copy {
+        fn apply<Float: crate::FloatType>(self, float: Float) -> Float;
+
+        /// Scale and convert slices of the same length with the slice method of the guarantee.
+        fn convert_slice<R, G, Float, Integer>(self, src: &[Float], dst: &mut [Integer])
+        where
+            R: crate::Rounding,
+            G: crate::Guarantee,
+            Float: crate::FloatType,
+            Integer: crate::FastFrom<Float>;
+    }
+
+    pub trait Sealed<Float>: Integer + RoundFrom<Float> {
+        /// The number of elements that the kernel of `convert_slice` converts at once. The remainder of a slice is converted with the scalar conversion.
+        const LANES: usize;
+
+        /// Convert like the standard `as` operator.
+        fn saturating_from(float: Float) -> Self;
+
+        /// Is the float in range of this integer type? That is the case if the float truncated towards zero is between the minimal and maximal value of the integer type.
+        fn in_range(float: Float) -> bool;
+
+        /// Convert with `to_int_unchecked`.
+        ///
+        /// # Safety
+        ///
+        /// The float must be in range.
+        unsafe fn to_int_unchecked(float: Float) -> Self;
+
+        /// Convert a slice with the kernel of the conversion. The slices have the same length.
+        fn convert_slice(src: &[Float], dst: &mut [Self]);
+
+        /// Like convert_slice but for uninitialized destination elements, which are all initialized afterwards.
+        fn convert_slice_uninit(src: &[Float], dst: &mut [core::mem::MaybeUninit<Self>]);
+
+        /// Like convert_slice but with saturating conversions.
+        fn saturating_convert_slice(src: &[Float], dst: &mut [Self]);
+
+        /// Like convert_slice but rounds to the nearest integer with ties to even instead of truncating.
+        fn round_ties_even_convert_slice(src: &[Float], dst: &mut [Self]);
+
+        /// Convert the row major `rows` x `cols` matrix `src` into its transpose `dst` with the transpose kernel of the conversion. The lengths are checked.
+        fn convert_transpose(src: &[Float], rows: usize, cols: usize, dst: &mut [Self]);
+    }
+}
+
+/// A floating point type that the conversions of this crate convert from.
+///
+/// The trait is implemented for f32 and f64 and cannot be implemented outside of this crate. It lets you write bounds and query properties of the type in code that is generic over the conversions.
+pub trait FloatType: sealed::Float {
+    /// The size of the type in bits.
+    const BITS: u32;
+    /// The number of significant digits in base 2 like [`f32::MANTISSA_DIGITS`]. Integers up to 2^MANTISSA_DIGITS are exactly representable.
+    const MANTISSA_DIGITS: u32;
+}
+
+impl FloatType for f32 {
+    const BITS: u32 = 32;
+    const MANTISSA_DIGITS: u32 = f32::MANTISSA_DIGITS;
+}
+
+impl FloatType for f64 {
+    const BITS: u32 = 64;
+    const MANTISSA_DIGITS: u32 = f64::MANTISSA_DIGITS;
+}
+
+/// An integer type that the conversions of this crate convert to.
+///
+/// The trait is implemented for every primitive integer type except isize and usize and cannot be implemented outside of this crate.
+pub trait IntType: sealed::Integer {
+    /// The size of the type in bits like [`i32::BITS`].
+    const BITS: u32;
+    /// Whether the type is signed.
+    const SIGNED: bool;
+}
+
+macro_rules! implement_int_type {
+    ($($Integer:ty),*) => {
+        $(
+            impl IntType for $Integer {
+                const BITS: u32 = <$Integer>::BITS;
+                const SIGNED: bool = <$Integer>::MIN != 0;
+            }
+        )*
+    };
+}
+
+implement_int_type! {i8, u8, i16, u16, i32, u32, i64, u64, i128, u128}
+
+/// Convert from a floating point type to this integer type like the conversion functions of this crate.
+///
+/// `i64::fast_from(float)` is the same as `f32_to_i64(float)`. The trait exists so that you can write code that is generic over the conversions. It is implemented for every conversion function and cannot be implemented outside of this crate.
+pub trait FastFrom<Float>: sealed::Sealed<Float> + IntType + Copy {
+    /// The smallest integer valued float that is in range. This is the minimal value of the integer type, which is always exactly representable.
+    const MIN_EXACT: Float;
+
+    /// The largest integer valued float that is in range.
+    ///
+    /// This is the maximal value of the integer type if it is exactly representable. Otherwise, it is the largest float below the maximal value. For example, for f32 to i32 it is 2^31 - 128.
+    const MAX_EXACT: Float;
+
+    /// Convert the input floating point value to this integer type.
+    ///
+    /// If the input value is out of range of the output type, then the result is unspecified. Otherwise, the result is the same as the standard `as` conversion.
+    fn fast_from(float: Float) -> Self;
+}
+
+// The optional kernels are used for converting slices and for converting and transposing matrices. They default to the scalar kernels. The scalar transpose kernel converts blocks of 8 x 8 elements, which keeps the accesses to both matrices local.
 macro_rules! create_function {
     ($name:ident, $Float:ty, $Integer:ty) => {
+        create_function! {$name, $Float, $Integer, kernel::Scalar<$Float, $Integer>, 1}
+    };
+    ($name:ident, $Float:ty, $Integer:ty, $Kernel:ty, $lanes:literal) => {
+        create_function! {$name, $Float, $Integer, $Kernel, $lanes, kernel::Scalar<$Float, $Integer>, 8}
+    };
+    ($name:ident, $Float:ty, $Integer:ty, $Kernel:ty, $lanes:literal, $Transpose:ty, $transpose_lanes:literal) => {
         /// Convert the input floating point value to the output integer type.
         ///
         /// If the input value is out of range of the output type, then the result is unspecified. Otherwise, the result is the same as the standard `as` conversion.
         #[cfg_attr(feature = "show-asm", inline(never))]
-        #[cfg_attr(not(feature = "show-asm"), inline(always))]
+        // With small-code the compiler decides. Small functions are still inlined, larger ones are called.
+        #[cfg_attr(not(any(feature = "show-asm", feature = "small-code")), inline(always))]
+        #[cfg_attr(kani, kani::ensures(|result: &$Integer| !<$Integer as sealed::Sealed<$Float>>::in_range(float) || *result == float as $Integer))]
         pub fn $name(float: $Float) -> $Integer {
             active_target::implementation::$name(float)
         }
+
+        impl sealed::Sealed<$Float> for $Integer {
+            const LANES: usize = $lanes;
+
+            #[inline(always)]
+            fn saturating_from(float: $Float) -> Self {
+                float as _
+            }
+
+            #[inline(always)]
+            fn in_range(float: $Float) -> bool {
+                // MAX + 1 is a power of two, which is exactly representable. If the conversion of MAX rounds, it rounds up to that power of two and adding 1 does not change it.
+                let upper_exclusive = <$Integer>::MAX as $Float + 1.;
+                // If MIN - 1 is not representable, then it rounds to MIN and there are no floats between them.
+                let min = <$Integer>::MIN as $Float;
+                (float > min - 1. || float == min) && float < upper_exclusive
+            }
+
+            #[inline(always)]
+            unsafe fn to_int_unchecked(float: $Float) -> Self {
+                float.to_int_unchecked()
+            }
+
+            #[inline(always)]
+            fn convert_slice(src: &[$Float], dst: &mut [Self]) {
+                kernel::convert_slice(
+                    src,
+                    dst,
+                    <$Kernel as kernel::Kernel<$lanes>>::convert,
+                    $name,
+                )
+            }
+
+            #[inline(always)]
+            fn convert_slice_uninit(src: &[$Float], dst: &mut [core::mem::MaybeUninit<Self>]) {
+                kernel::convert_slice_uninit(
+                    src,
+                    dst,
+                    <$Kernel as kernel::Kernel<$lanes>>::convert,
+                    $name,
+                )
+            }
+
+            #[inline(always)]
+            fn saturating_convert_slice(src: &[$Float], dst: &mut [Self]) {
+                kernel::convert_slice(
+                    src,
+                    dst,
+                    <$Kernel as kernel::Kernel<$lanes>>::convert_saturating,
+                    Self::saturating_from,
+                )
+            }
+
+            #[inline(always)]
+            fn round_ties_even_convert_slice(src: &[$Float], dst: &mut [Self]) {
+                kernel::convert_slice(
+                    src,
+                    dst,
+                    <$Kernel as kernel::Kernel<$lanes>>::convert_round_ties_even,
+                    |float: $Float| $name(sealed::Float::round_ties_even(float)),
+                )
+            }
+
+            #[inline(always)]
+            fn convert_transpose(src: &[$Float], rows: usize, cols: usize, dst: &mut [Self]) {
+                kernel::convert_transpose(
+                    src,
+                    rows,
+                    cols,
+                    dst,
+                    <$Transpose as kernel::TransposeKernel<$transpose_lanes>>::convert_transposed,
+                    $name,
+                )
+            }
+        }
+
+        impl FastFrom<$Float> for $Integer {
+            const MIN_EXACT: $Float = <$Integer>::MIN as $Float;
+            const MAX_EXACT: $Float = {
+                // The number of bits that encode the magnitude. MAX is 2^exponent - 1.
+                let exponent = (<$Integer>::BITS - <$Integer as IntType>::SIGNED as u32) as i32;
+                let digits = <$Float>::MANTISSA_DIGITS as i32;
+                // The computation is exact in f64 for both float types.
+                let max = if exponent <= digits {
+                    // Every integer up to 2^digits is representable.
+                    exact_pow2_f64(exponent) - 1.
+                } else {
+                    // The floats below 2^exponent are 2^(exponent - digits) apart.
+                    exact_pow2_f64(exponent) - exact_pow2_f64(exponent - digits)
+                };
+                max as $Float
+            };
+
+            #[inline(always)]
+            fn fast_from(float: $Float) -> Self {
+                $name(float)
+            }
+        }
     };
 }
 
 create_function! {f32_to_i8, f32, i8}
-create_function! {f32_to_u8, f32, u8}
+create_function! {f32_to_u8, f32, u8, kernel::F32ToU8, 16}
 create_function! {f32_to_i16, f32, i16}
-create_function! {f32_to_u16, f32, u16}
-create_function! {f32_to_i32, f32, i32}
+create_function! {f32_to_u16, f32, u16, kernel::F32ToU16, 16}
+// With SVE the aarch64_neon target module converts slices at the vector length of the CPU.
+#[cfg(all(
+    target_arch = "aarch64",
+    target_feature = "neon",
+    target_feature = "sve",
+    not(any(feature = "force-default", feature = "portable-deterministic", kani))
+))]
+create_function! {f32_to_i32, f32, i32, kernel::SveF32ToI32, 16, kernel::TransposeF32ToI32, 4}
+#[cfg(not(all(
+    target_arch = "aarch64",
+    target_feature = "neon",
+    target_feature = "sve",
+    not(any(feature = "force-default", feature = "portable-deterministic", kani))
+)))]
+create_function! {f32_to_i32, f32, i32, kernel::F32ToI32, 4, kernel::TransposeF32ToI32, 4}
 create_function! {f32_to_u32, f32, u32}
 create_function! {f32_to_i64, f32, i64}
 create_function! {f32_to_u64, f32, u64}
@@ -164,7 +1066,7 @@ create_function! {f64_to_i8, f64, i8}
 create_function! {f64_to_u8, f64, u8}
 create_function! {f64_to_i16, f64, i16}
 create_function! {f64_to_u16, f64, u16}
-create_function! {f64_to_i32, f64, i32}
+create_function! {f64_to_i32, f64, i32, kernel::F64ToI32, 2}
 create_function! {f64_to_u32, f64, u32}
 create_function! {f64_to_i64, f64, i64}
 create_function! {f64_to_u64, f64, u64}
@@ -0,0 +1,94 @@
+// A measurement loop for conversions. It lets users benchmark the crate on their own hardware and data, without the benchmark crate of the repository.
+
+use std::{
+    fmt,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::IntType;
+
+/// The result of [`measure_conversion_throughput`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ConversionThroughput {
+    /// The number of converted values per run.
+    pub elements: usize,
+    /// The number of runs.
+    pub runs: u32,
+    /// The duration of the fastest run.
+    pub fastest: Duration,
+}
+
+impl ConversionThroughput {
+    /// The duration of the fastest run per converted value in nanoseconds. 0 if there are no values.
+    pub fn nanos_per_element(&self) -> f64 {
+        if self.elements == 0 {
+            return 0.;
+        }
+        self.fastest.as_nanos() as f64 / self.elements as f64
+    }
+
+    /// The number of converted values per second in the fastest run. Infinite if the run was too fast to measure.
+    pub fn elements_per_second(&self) -> f64 {
+        self.elements as f64 / self.fastest.as_secs_f64()
+    }
+}
+
+/// The format is the same for every measurement so that numbers from different machines can be compared, for example in bug reports.
+impl fmt::Display for ConversionThroughput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} elements, fastest of {} runs on {}: {:?}, {:.3} ns/element, {:.1} M elements/s",
+            self.elements,
+            self.runs,
+            std::env::consts::ARCH,
+            self.fastest,
+            self.nanos_per_element(),
+            self.elements_per_second() / 1e6,
+        )
+    }
+}
+
+/// Measure how fast `convert` converts the values of `data`.
+///
+/// This is the measurement loop of the smoke benchmark of the repository. The results of the conversions depend on each other so that neither the compiler nor the CPU can skip a conversion. The loop runs several times and the fastest run is reported to reduce noise.
+///
+/// The measurement is only meaningful with optimizations. Compare against the standard conversion on the same data to see the speedup on your hardware.
+///
+/// ```
+/// use fast_float_to_integer::measure_conversion_throughput;
+///
+/// let data: Vec<f32> = (0..1000).map(|i| i as f32 * 0.5).collect();
+/// let fast = measure_conversion_throughput(fast_float_to_integer::f32_to_i32, &data);
+/// let standard = measure_conversion_throughput(|float: f32| float as i32, &data);
+/// println!("fast: {fast}");
+/// println!("standard: {standard}");
+/// ```
+pub fn measure_conversion_throughput<Float, Output>(
+    convert: impl Fn(Float) -> Output,
+    data: &[Float],
+) -> ConversionThroughput
+where
+    Float: Copy,
+    Output: IntType,
+{
+    const RUNS: u32 = 20;
+
+    let mut fastest = Duration::MAX;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        let mut result = Output::ZERO;
+        for float in black_box(data) {
+            result = result.wrapping_add(convert(*float));
+        }
+        black_box(result);
+        fastest = fastest.min(start.elapsed());
+    }
+    ConversionThroughput {
+        elements: data.len(),
+        runs: RUNS,
+        fastest,
+    }
+}
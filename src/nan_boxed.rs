@@ -0,0 +1,54 @@
+// Conversions of NaN-boxed values. Dynamic language runtimes store every value in 64 bits. Numbers are f64 and the other values are hidden in the payload bits of quiet NaNs, which no float operation produces.
+
+use crate::*;
+
+/// The bits of a quiet NaN: all exponent bits and the highest mantissa bit.
+const QUIET_NAN: u64 = 0x7ff8_0000_0000_0000;
+/// The mantissa bits below the quiet bit.
+const PAYLOAD: u64 = 0x0007_ffff_ffff_ffff;
+
+/// A NaN-boxed value decoded by functions like [`nan_boxed_to_i32`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NanBoxed<Integer> {
+    /// The value is a number. This is its conversion.
+    Number(Integer),
+    /// The value is boxed. These are the 51 payload bits below the quiet bit.
+    Payload(u64),
+}
+
+macro_rules! create_function {
+    ($name:ident, $convert:ident, $Integer:ty) => {
+        /// Decode the NaN-boxed value with the bits `bits`.
+        ///
+        /// A value is boxed if it is a quiet NaN with a payload that is not 0, regardless of the sign bit. The discrimination is two bitwise ANDs and two comparisons without float operations. The sign bit is not part of the payload. Schemes that use it as a tag can read it from `bits`.
+        ///
+        #[doc = concat!("Every other value is a number, including infinities, signaling NaNs and the quiet NaN without payload that float operations produce. It is converted like [`", stringify!($convert), "`]. If it is out of range of the output type, then the result is unspecified.")]
+        ///
+        /// ```
+        #[doc = concat!("use fast_float_to_integer::{", stringify!($name), ", NanBoxed};")]
+        ///
+        #[doc = concat!("assert_eq!(", stringify!($name), "(2.5f64.to_bits()), NanBoxed::Number(2));")]
+        #[doc = concat!("assert_eq!(", stringify!($name), "(0x7ff8_0000_0000_002a), NanBoxed::Payload(42));")]
+        /// ```
+        #[inline(always)]
+        pub fn $name(bits: u64) -> NanBoxed<$Integer> {
+            let payload = bits & PAYLOAD;
+            if bits & QUIET_NAN == QUIET_NAN && payload != 0 {
+                NanBoxed::Payload(payload)
+            } else {
+                NanBoxed::Number($convert(f64::from_bits(bits)))
+            }
+        }
+    };
+}
+
+create_function! {nan_boxed_to_i8, f64_to_i8, i8}
+create_function! {nan_boxed_to_u8, f64_to_u8, u8}
+create_function! {nan_boxed_to_i16, f64_to_i16, i16}
+create_function! {nan_boxed_to_u16, f64_to_u16, u16}
+create_function! {nan_boxed_to_i32, f64_to_i32, i32}
+create_function! {nan_boxed_to_u32, f64_to_u32, u32}
+create_function! {nan_boxed_to_i64, f64_to_i64, i64}
+create_function! {nan_boxed_to_u64, f64_to_u64, u64}
+create_function! {nan_boxed_to_i128, f64_to_i128, i128}
+create_function! {nan_boxed_to_u128, f64_to_u128, u128}
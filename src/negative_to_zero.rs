@@ -0,0 +1,28 @@
+// Conversions to unsigned integers that guarantee 0 for negative inputs. Code that converts counts or sizes can often rule out huge values but not tiny negative values from rounding errors.
+
+use crate::*;
+
+macro_rules! create_function {
+    ($name:ident, $convert:ident, $Float:ty, $Integer:ty) => {
+        /// Convert the input floating point value to the output unsigned integer type. Negative inputs and NaN convert to 0.
+        ///
+        /// If the input value is larger than the maximum of the output type, then the result is unspecified. Otherwise, the result is the same as the standard `as` conversion.
+        #[inline(always)]
+        pub fn $name(float: $Float) -> $Integer {
+            // max returns the other operand if one operand is NaN.
+            $convert(float.max(0.))
+        }
+    };
+}
+
+create_function! {f32_to_u8_negative_to_zero, f32_to_u8, f32, u8}
+create_function! {f32_to_u16_negative_to_zero, f32_to_u16, f32, u16}
+create_function! {f32_to_u32_negative_to_zero, f32_to_u32, f32, u32}
+create_function! {f32_to_u64_negative_to_zero, f32_to_u64, f32, u64}
+create_function! {f32_to_u128_negative_to_zero, f32_to_u128, f32, u128}
+
+create_function! {f64_to_u8_negative_to_zero, f64_to_u8, f64, u8}
+create_function! {f64_to_u16_negative_to_zero, f64_to_u16, f64, u16}
+create_function! {f64_to_u32_negative_to_zero, f64_to_u32, f64, u32}
+create_function! {f64_to_u64_negative_to_zero, f64_to_u64, f64, u64}
+create_function! {f64_to_u128_negative_to_zero, f64_to_u128, f64, u128}
@@ -0,0 +1,288 @@
+// Conversions to the normalized integer formats of graphics APIs. Vulkan and Direct3D call them UNORM and SNORM. An n bit UNORM integer represents the range [0, 1] and an n bit SNORM integer represents the range [-1, 1].
+
+use crate::*;
+
+/// Adding and subtracting this value rounds f32 values with a magnitude below 2^22 to the nearest integer with ties to even. The sum has no fractional bits, so the addition does the rounding.
+const ROUND_F32: f32 = exact_pow2_f32(23);
+
+/// Clamp the input to [min, 1]. NaN becomes 0.
+#[inline(always)]
+fn clamp(float: f32, min: f32) -> f32 {
+    // The comparisons are false for NaN, which then takes the last branch.
+    if float >= min {
+        if float <= 1. {
+            float
+        } else {
+            1.
+        }
+    } else if float < min {
+        min
+    } else {
+        0.
+    }
+}
+
+macro_rules! create_function {
+    ($name:ident, $slice_name:ident, $convert:ident, $Integer:ty, $min:literal, $format:literal) => {
+        #[doc = concat!("Convert the input f32 to the ", $format, " format of the output integer type.")]
+        ///
+        #[doc = concat!("The input is clamped to [", stringify!($min), ", 1], multiplied by ", stringify!($Integer), "::MAX and rounded to the nearest integer with ties to even. NaN converts to 0. These are the conversion rules of Vulkan and Direct3D.")]
+        ///
+        /// Unlike the other conversions in this crate, the result is fully specified for every input.
+        #[inline(always)]
+        pub fn $name(float: f32) -> $Integer {
+            let scaled = clamp(float, $min) * <$Integer>::MAX as f32;
+            let rounded = (scaled + ROUND_F32) - ROUND_F32;
+            // The rounded value is always in range.
+            $convert(rounded)
+        }
+
+        #[doc = concat!("Like [`convert_slice`](crate::convert_slice) but every element is converted like [`", stringify!($name), "`].")]
+        ///
+        /// The conversion has no branches, which lets the compiler vectorize the loop.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the slices have different lengths.
+        #[inline]
+        pub fn $slice_name(src: &[f32], dst: &mut [$Integer]) {
+            assert_eq!(src.len(), dst.len(), "slices have different lengths");
+            for (src, dst) in src.iter().zip(dst) {
+                *dst = $name(*src);
+            }
+        }
+    };
+}
+
+create_function! {unorm_f32_to_u8, unorm_f32_to_u8_slice, f32_to_u8, u8, 0., "UNORM"}
+create_function! {unorm_f32_to_u16, unorm_f32_to_u16_slice, f32_to_u16, u16, 0., "UNORM"}
+create_function! {snorm_f32_to_i8, snorm_f32_to_i8_slice, f32_to_i8, i8, -1., "SNORM"}
+create_function! {snorm_f32_to_i16, snorm_f32_to_i16_slice, f32_to_i16, i16, -1., "SNORM"}
+
+// The sRGB transfer function encodes linear values as `12.92 * x` below 0.0031308 and as `1.055 * x^(1 / 2.4) - 0.055` above. There is no powf without the standard library, so we split the input into exponent and mantissa: `x^(1 / 2.4) = mantissa^(1 / 2.4) * 2^(exponent / 2.4)`. A table holds the second factor and a polynomial approximates the first.
+
+/// 2^(exponent / 2.4) for the exponents -9 to 0. 0.0031308 has the exponent -9.
+const SRGB_EXPONENT_TABLE: [f32; 10] = [
+    0.07432544,
+    0.099212565,
+    0.1324329,
+    0.17677669,
+    0.23596857,
+    0.31498027,
+    0.4204482,
+    0.561231,
+    0.74915355,
+    1.0,
+];
+
+/// The coefficients of a polynomial in `mantissa - 1` that approximates mantissa^(1 / 2.4) for mantissas in [1, 2) with an error below 1.1e-4. The polynomial interpolates at the Chebyshev nodes.
+const SRGB_MANTISSA_POLYNOMIAL: [f32; 4] = [1.0001068, 0.4131778, -0.10211241, 0.023732815];
+
+/// Apply the sRGB transfer function to a linear value in [0, 1]. The result is in [0, 1].
+#[inline(always)]
+fn linear_to_srgb(linear: f32) -> f32 {
+    let bits = linear.to_bits();
+    let exponent = (bits >> 23) as i32 - 127;
+    let mantissa = f32::from_bits((bits & 0x007f_ffff) | 0x3f80_0000) - 1.;
+    let [c0, c1, c2, c3] = SRGB_MANTISSA_POLYNOMIAL;
+    let power = ((c3 * mantissa + c2) * mantissa + c1) * mantissa + c0;
+    // Small inputs have smaller exponents but they use the linear segment.
+    let power = power * SRGB_EXPONENT_TABLE[(exponent + 9).clamp(0, 9) as usize];
+    let encoded = if linear <= 0.0031308 {
+        12.92 * linear
+    } else {
+        1.055 * power - 0.055
+    };
+    // The approximation can exceed 1 by a tiny amount.
+    if encoded <= 1. {
+        encoded
+    } else {
+        1.
+    }
+}
+
+/// Apply the sRGB transfer function to the input linear value and convert the result to the 8 bit UNORM format.
+///
+/// The input is clamped to [0, 1] and NaN converts to 0 like [`unorm_f32_to_u8`]. The transfer function is approximated. The result differs from the exactly rounded result only if the exact value is within 0.05 of a rounding boundary.
+///
+/// ```
+/// // The exact result is 136.96, which is far from a rounding boundary.
+/// assert_eq!(fast_float_to_integer::srgb_f32_to_u8(0.25), 137);
+/// ```
+#[inline(always)]
+pub fn srgb_f32_to_u8(float: f32) -> u8 {
+    let scaled = linear_to_srgb(clamp(float, 0.)) * u8::MAX as f32;
+    let rounded = (scaled + ROUND_F32) - ROUND_F32;
+    // The rounded value is always in range.
+    f32_to_u8(rounded)
+}
+
+/// Like [`convert_slice`](crate::convert_slice) but every element is converted like [`srgb_f32_to_u8`].
+///
+/// Applying the transfer function in the same pass as the conversion avoids a separate pass over the whole image.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+#[inline]
+pub fn srgb_f32_to_u8_slice(src: &[f32], dst: &mut [u8]) {
+    assert_eq!(src.len(), dst.len(), "slices have different lengths");
+    for (src, dst) in src.iter().zip(dst) {
+        *dst = srgb_f32_to_u8(*src);
+    }
+}
+
+/// The transfer function that [`unorm_f32_to_u8_slice_with`] applies before the conversion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransferFunction {
+    /// Convert the values unchanged like [`unorm_f32_to_u8`].
+    Linear,
+    /// Encode the values with the sRGB transfer function like [`srgb_f32_to_u8`].
+    Srgb,
+}
+
+/// Like [`unorm_f32_to_u8_slice`] but with a transfer function that is chosen at runtime.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+///
+/// ```
+/// use fast_float_to_integer::{unorm_f32_to_u8_slice_with, TransferFunction};
+///
+/// let src = [0.0f32, 0.25, 1.0];
+/// let mut dst = [0u8; 3];
+/// unorm_f32_to_u8_slice_with(&src, &mut dst, TransferFunction::Srgb);
+/// assert_eq!(dst, [0, 137, 255]);
+/// ```
+#[inline]
+pub fn unorm_f32_to_u8_slice_with(src: &[f32], dst: &mut [u8], transfer: TransferFunction) {
+    // Branching once outside of the loop keeps the loops vectorizable.
+    match transfer {
+        TransferFunction::Linear => unorm_f32_to_u8_slice(src, dst),
+        TransferFunction::Srgb => srgb_f32_to_u8_slice(src, dst),
+    }
+}
+
+// Packed formats that store several UNORM channels with fewer than 16 bits each in one u32. The first channel is in the least significant bits like in Vulkan and Direct3D.
+
+/// Convert the input to a UNORM channel whose largest value is `max`.
+#[inline(always)]
+fn unorm_f32_to_channel(float: f32, max: u32) -> u32 {
+    // see unorm_f32_to_u16
+    let scaled = clamp(float, 0.) * max as f32;
+    let rounded = (scaled + ROUND_F32) - ROUND_F32;
+    f32_to_u32(rounded)
+}
+
+/// Convert the inputs to 11, 11 and 10 bit UNORM channels and pack them into a u32.
+///
+/// Red is in bits 0 to 10, green in bits 11 to 21 and blue in bits 22 to 31. Every channel is converted like [`unorm_f32_to_u8`] but with its own bit width.
+///
+/// This is not the small float format that Direct3D calls `R11G11B10_FLOAT`.
+///
+/// ```
+/// let packed = fast_float_to_integer::pack_unorm_r11g11b10([1.0, 0.0, 1.0]);
+/// assert_eq!(packed, 0x3ff << 22 | 0x7ff);
+/// ```
+#[inline(always)]
+pub fn pack_unorm_r11g11b10([red, green, blue]: [f32; 3]) -> u32 {
+    unorm_f32_to_channel(red, (1 << 11) - 1)
+        | unorm_f32_to_channel(green, (1 << 11) - 1) << 11
+        | unorm_f32_to_channel(blue, (1 << 10) - 1) << 22
+}
+
+/// Convert the inputs to 10, 10, 10 and 2 bit UNORM channels and pack them into a u32.
+///
+/// Red is in bits 0 to 9, green in bits 10 to 19, blue in bits 20 to 29 and alpha in bits 30 and 31. Every channel is converted like [`unorm_f32_to_u8`] but with its own bit width.
+///
+/// ```
+/// let packed = fast_float_to_integer::pack_unorm_r10g10b10a2([1.0, 0.0, 0.0, 1.0]);
+/// assert_eq!(packed, 0b11 << 30 | 0x3ff);
+/// ```
+#[inline(always)]
+pub fn pack_unorm_r10g10b10a2([red, green, blue, alpha]: [f32; 4]) -> u32 {
+    unorm_f32_to_channel(red, (1 << 10) - 1)
+        | unorm_f32_to_channel(green, (1 << 10) - 1) << 10
+        | unorm_f32_to_channel(blue, (1 << 10) - 1) << 20
+        | unorm_f32_to_channel(alpha, (1 << 2) - 1) << 30
+}
+
+/// Like [`convert_slice`](crate::convert_slice) but every element is packed like [`pack_unorm_r11g11b10`].
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+#[inline]
+pub fn pack_unorm_r11g11b10_slice(src: &[[f32; 3]], dst: &mut [u32]) {
+    assert_eq!(src.len(), dst.len(), "slices have different lengths");
+    for (src, dst) in src.iter().zip(dst) {
+        *dst = pack_unorm_r11g11b10(*src);
+    }
+}
+
+/// Like [`convert_slice`](crate::convert_slice) but every element is packed like [`pack_unorm_r10g10b10a2`].
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+#[inline]
+pub fn pack_unorm_r10g10b10a2_slice(src: &[[f32; 4]], dst: &mut [u32]) {
+    assert_eq!(src.len(), dst.len(), "slices have different lengths");
+    for (src, dst) in src.iter().zip(dst) {
+        *dst = pack_unorm_r10g10b10a2(*src);
+    }
+}
+
+// Depth buffer formats. D16 is the 16 bit UNORM format. D24 is a 24 bit UNORM format that is stored in the lower bits of a u32.
+
+/// The largest D24 value.
+const D24_MAX: u32 = (1 << 24) - 1;
+
+/// Like ROUND_F32 but for f64 values with a magnitude below 2^51.
+const ROUND_F64: f64 = exact_pow2_f64(52);
+
+/// Convert the input depth value to the D24 format.
+///
+/// The input is clamped to [0, 1], multiplied by 2^24 - 1 and rounded to the nearest integer with ties to even. NaN converts to 0. The upper 8 bits of the result are 0.
+///
+/// The product is computed in f64. Above 2^22 f32 has too few fractional bits to round the product correctly.
+#[inline(always)]
+pub fn depth_f32_to_d24(float: f32) -> u32 {
+    // The product of two 24 bit mantissas fits into the 53 bit mantissa of f64 so it is exact.
+    let scaled = clamp(float, 0.) as f64 * D24_MAX as f64;
+    let rounded = (scaled + ROUND_F64) - ROUND_F64;
+    // The rounded value is always in range.
+    f64_to_u32(rounded)
+}
+
+/// Convert the input depth value to the D16 format.
+///
+/// This is the same as [`unorm_f32_to_u16`].
+#[inline(always)]
+pub fn depth_f32_to_d16(float: f32) -> u16 {
+    unorm_f32_to_u16(float)
+}
+
+/// Like [`convert_slice`](crate::convert_slice) but every element is converted like [`depth_f32_to_d24`].
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+#[inline]
+pub fn depth_f32_to_d24_slice(src: &[f32], dst: &mut [u32]) {
+    assert_eq!(src.len(), dst.len(), "slices have different lengths");
+    for (src, dst) in src.iter().zip(dst) {
+        *dst = depth_f32_to_d24(*src);
+    }
+}
+
+/// Like [`convert_slice`](crate::convert_slice) but every element is converted like [`depth_f32_to_d16`].
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+#[inline]
+pub fn depth_f32_to_d16_slice(src: &[f32], dst: &mut [u16]) {
+    unorm_f32_to_u16_slice(src, dst);
+}
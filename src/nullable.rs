@@ -0,0 +1,100 @@
+// Conversions of columns with missing values. Dataframe libraries and columnar formats like Apache Arrow store such a column as the values plus a validity bitmap.
+
+use crate::{FastFrom, FloatType};
+
+/// Convert the values that `decode` returns for the elements of `src`. `None` is null.
+#[inline(always)]
+fn convert_nullable<Element, Float, Integer>(
+    src: &[Element],
+    sentinel: Integer,
+    dst: &mut [Integer],
+    validity: &mut [u8],
+    decode: impl Fn(&Element) -> Option<Float>,
+) where
+    Float: FloatType,
+    Integer: FastFrom<Float>,
+{
+    assert_eq!(src.len(), dst.len(), "slices have different lengths");
+    assert!(validity.len() >= (src.len() + 7) / 8, "bitmap is too short");
+    // The chunks have 64 elements so that the bits of a chunk fit into a u64 and the chunks start at the beginning of a byte.
+    let mut buffer = [Float::ZERO; 64];
+    for ((src, dst), validity) in src
+        .chunks(buffer.len())
+        .zip(dst.chunks_mut(buffer.len()))
+        .zip(validity.chunks_mut(8))
+    {
+        let buffer = &mut buffer[..src.len()];
+        let mut bits = 0u64;
+        for (index, (float, element)) in buffer.iter_mut().zip(src).enumerate() {
+            let value = decode(element);
+            bits |= (value.is_some() as u64) << index;
+            // Converting 0 is harmless. The result is replaced with the sentinel below.
+            *float = value.unwrap_or(Float::ZERO);
+        }
+        Integer::convert_slice(buffer, dst);
+        for (index, integer) in dst.iter_mut().enumerate() {
+            let valid = bits & (1 << index) != 0;
+            *integer = if valid { *integer } else { sentinel };
+        }
+        let bytes = (src.len() + 7) / 8;
+        validity[..bytes].copy_from_slice(&bits.to_le_bytes()[..bytes]);
+    }
+}
+
+/// Convert a column with missing values.
+///
+/// `None` and NaN are null. Their element in `dst` is `sentinel` and their bit in `validity` is 0. The other elements are converted like [`convert_slice`](crate::convert_slice) and their bit is 1.
+///
+/// Element `i` has bit `i % 8` of `validity[i / 8]`. This is the least significant bit first layout of Apache Arrow. The bits after the last element in the last byte are 0. The bytes after that are left untouched.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths or if `validity` is too short to have a bit for every element.
+///
+/// ```
+/// let src = [Some(1.5f32), None, Some(f32::NAN), Some(-3.0)];
+/// let mut dst = [0i32; 4];
+/// let mut validity = [0u8; 1];
+/// fast_float_to_integer::convert_options(&src, -1, &mut dst, &mut validity);
+/// assert_eq!(dst, [1, -1, -1, -3]);
+/// assert_eq!(validity, [0b1001]);
+/// ```
+#[inline]
+pub fn convert_options<Float, Integer>(
+    src: &[Option<Float>],
+    sentinel: Integer,
+    dst: &mut [Integer],
+    validity: &mut [u8],
+) where
+    Float: FloatType,
+    Integer: FastFrom<Float>,
+{
+    convert_nullable(src, sentinel, dst, validity, |element| {
+        element.filter(|float| !float.is_nan())
+    });
+}
+
+/// Like [`convert_options`] but NaN is the only null value.
+///
+/// ```
+/// let src = [1.5f64, f64::NAN, 3.0];
+/// let mut dst = [0u8; 3];
+/// let mut validity = [0u8; 1];
+/// fast_float_to_integer::convert_slice_nan_to_null(&src, 0, &mut dst, &mut validity);
+/// assert_eq!(dst, [1, 0, 3]);
+/// assert_eq!(validity, [0b101]);
+/// ```
+#[inline]
+pub fn convert_slice_nan_to_null<Float, Integer>(
+    src: &[Float],
+    sentinel: Integer,
+    dst: &mut [Integer],
+    validity: &mut [u8],
+) where
+    Float: FloatType,
+    Integer: FastFrom<Float>,
+{
+    convert_nullable(src, sentinel, dst, validity, |float| {
+        Some(*float).filter(|float| !float.is_nan())
+    });
+}
@@ -0,0 +1,62 @@
+// Conversion of large slices in parallel on the rayon thread pool.
+//
+// The slice is split into chunks that are a multiple of the lanes of the kernel. Every element then takes the same path through the kernel or the scalar conversion of the remainder as in the serial `convert_slice`, so even the unspecified results of out of range inputs are the same. The conversions truncate and do not depend on the rounding mode of the worker thread.
+
+use rayon::prelude::*;
+
+use crate::{sealed::Sealed, FastFrom};
+
+/// The default number of elements per task of [`par_convert_slice`].
+///
+/// A chunk of this size is 256 KiB of f32 or 512 KiB of f64, which is large enough that the overhead of a task does not matter.
+pub const PAR_CHUNK_SIZE: usize = 1 << 16;
+
+/// Like [`convert_slice`](crate::convert_slice) but the slice is converted in parallel on the rayon thread pool.
+///
+/// The slice is split into chunks of [`PAR_CHUNK_SIZE`] elements. `dst` gets exactly the same values as with [`convert_slice`](crate::convert_slice), including the unspecified results of out of range inputs and NaN.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+///
+/// ```
+/// let src = vec![1.5f32; 1_000_000];
+/// let mut dst = vec![0i32; 1_000_000];
+/// fast_float_to_integer::par_convert_slice(&src, &mut dst);
+/// assert!(dst.iter().all(|integer| *integer == 1));
+/// ```
+#[inline]
+pub fn par_convert_slice<Float, Integer>(src: &[Float], dst: &mut [Integer])
+where
+    Float: Copy + Sync,
+    Integer: FastFrom<Float> + Send,
+{
+    par_convert_slice_with_chunk_size(src, dst, PAR_CHUNK_SIZE);
+}
+
+/// Like [`par_convert_slice`] but with `chunk_size` elements per task.
+///
+/// Tune the chunk size for the caches and the NUMA nodes of large servers. The chunk size is rounded up to a multiple of the number of elements that the packed kernel of the conversion converts at once, so that the result is the same as the one of [`convert_slice`](crate::convert_slice) for every chunk size.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths or if `chunk_size` is 0.
+#[inline]
+pub fn par_convert_slice_with_chunk_size<Float, Integer>(
+    src: &[Float],
+    dst: &mut [Integer],
+    chunk_size: usize,
+) where
+    Float: Copy + Sync,
+    Integer: FastFrom<Float> + Send,
+{
+    assert_eq!(src.len(), dst.len(), "slices have different lengths");
+    assert_ne!(chunk_size, 0, "chunk size is 0");
+    let lanes = <Integer as Sealed<Float>>::LANES;
+    // Limiting the chunk size to the length first prevents an overflow when rounding up.
+    let chunk_size = chunk_size.min(src.len()).max(1);
+    let chunk_size = (chunk_size + lanes - 1) / lanes * lanes;
+    src.par_chunks(chunk_size)
+        .zip(dst.par_chunks_mut(chunk_size))
+        .for_each(|(src, dst)| Integer::convert_slice(src, dst));
+}
@@ -0,0 +1,54 @@
+// Conversions of planar multichannel data. Every channel is a separate slice, called a plane. Audio and video pipelines commonly store channels like this.
+
+use crate::FastFrom;
+
+/// Convert every plane of `src` to the plane at the same index of `dst` like [`convert_slice`](crate::convert_slice).
+///
+/// Every plane is converted with the packed kernels of the active target.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have a different number of planes or if any two planes at the same index have different lengths.
+///
+/// ```
+/// let left = [1.5f32, -2.5];
+/// let right = [3.0f32, 4.5];
+/// let mut left_out = [0i16; 2];
+/// let mut right_out = [0i16; 2];
+/// fast_float_to_integer::convert_planes(&[&left, &right], &mut [&mut left_out, &mut right_out]);
+/// assert_eq!(left_out, [1, -2]);
+/// assert_eq!(right_out, [3, 4]);
+/// ```
+#[inline]
+pub fn convert_planes<Float, Integer>(src: &[&[Float]], dst: &mut [&mut [Integer]])
+where
+    Float: Copy,
+    Integer: FastFrom<Float>,
+{
+    assert_eq!(src.len(), dst.len(), "different number of planes");
+    for (src, dst) in src.iter().zip(dst) {
+        crate::convert_slice(src, dst);
+    }
+}
+
+/// Like [`convert_planes`] but the planes are converted in parallel on the rayon thread pool.
+///
+/// Every plane is one task. This pays off when there are several large planes.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have a different number of planes or if any two planes at the same index have different lengths.
+#[cfg(feature = "rayon")]
+#[inline]
+pub fn par_convert_planes<Float, Integer>(src: &[&[Float]], dst: &mut [&mut [Integer]])
+where
+    Float: Copy + Sync,
+    Integer: FastFrom<Float> + Send,
+{
+    use rayon::prelude::*;
+
+    assert_eq!(src.len(), dst.len(), "different number of planes");
+    src.par_iter()
+        .zip(dst.par_iter_mut())
+        .for_each(|(src, dst)| crate::convert_slice(src, dst));
+}
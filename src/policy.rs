@@ -0,0 +1,161 @@
+// One generic entry point for the conversions with different out of range behavior. The policies are zero sized types that select the behavior at compile time.
+
+use crate::{sealed, FastFrom, FloatType};
+
+/// What a conversion does for inputs that are out of range. Used by [`convert_with`].
+///
+/// The trait is implemented for [`Unspecified`], [`Deterministic`], [`Saturate`], [`Wrap`] and [`Checked`] and cannot be implemented outside of this crate.
+pub trait Policy<Float, Integer>: sealed::Policy {
+    /// The result of the conversion.
+    type Output;
+
+    /// Convert the input floating point value according to the policy.
+    fn convert(float: Float) -> Self::Output;
+}
+
+/// Out of range inputs convert to an unspecified value like [`FastFrom::fast_from`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Unspecified;
+
+/// Out of range inputs and NaN convert to a value that is the same on every target.
+///
+/// The value is not specified beyond that. Currently this is the standard `as` operator, which is the fastest conversion with this property on most targets, but a future version can change the value.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Deterministic;
+
+/// Out of range inputs saturate and NaN converts to 0 like the standard `as` operator.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Saturate;
+
+/// The input is truncated towards zero and the result wraps around modulo 2^BITS like the `wrapping_*` operations of the integer types. Infinities and NaN convert to 0.
+///
+/// This is slower than the other policies because it has to decompose the float.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Wrap;
+
+/// Out of range inputs and NaN convert to `None`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Checked;
+
+impl sealed::Policy for Unspecified {}
+impl sealed::Policy for Deterministic {}
+impl sealed::Policy for Saturate {}
+impl sealed::Policy for Wrap {}
+impl sealed::Policy for Checked {}
+
+impl<Float, Integer> Policy<Float, Integer> for Unspecified
+where
+    Integer: FastFrom<Float>,
+{
+    type Output = Integer;
+
+    #[inline(always)]
+    fn convert(float: Float) -> Integer {
+        Integer::fast_from(float)
+    }
+}
+
+impl<Float, Integer> Policy<Float, Integer> for Deterministic
+where
+    Integer: FastFrom<Float>,
+{
+    type Output = Integer;
+
+    #[inline(always)]
+    fn convert(float: Float) -> Integer {
+        Integer::saturating_from(float)
+    }
+}
+
+impl<Float, Integer> Policy<Float, Integer> for Saturate
+where
+    Integer: FastFrom<Float>,
+{
+    type Output = Integer;
+
+    #[inline(always)]
+    fn convert(float: Float) -> Integer {
+        Integer::saturating_from(float)
+    }
+}
+
+impl<Float, Integer> Policy<Float, Integer> for Wrap
+where
+    Float: FloatType,
+    Integer: FastFrom<Float>,
+{
+    type Output = Integer;
+
+    #[inline(always)]
+    fn convert(float: Float) -> Integer {
+        // Every f32 is exactly representable as f64.
+        Integer::truncate_from_u128(wrapping_f64_to_u128(float.to_f64()))
+    }
+}
+
+impl<Float, Integer> Policy<Float, Integer> for Checked
+where
+    Float: FloatType,
+    Integer: FastFrom<Float>,
+{
+    type Output = Option<Integer>;
+
+    #[inline(always)]
+    fn convert(float: Float) -> Option<Integer> {
+        if Integer::in_range(float) {
+            // SAFETY: The input is in range.
+            Some(unsafe { Integer::to_int_unchecked(float) })
+        } else {
+            None
+        }
+    }
+}
+
+/// Convert the input f64 truncated towards zero to an integer modulo 2^128. Infinities and NaN convert to 0.
+#[inline(always)]
+fn wrapping_f64_to_u128(float: f64) -> u128 {
+    const MANTISSA_BITS: u32 = f64::MANTISSA_DIGITS - 1;
+
+    let bits = float.to_bits();
+    let biased_exponent = ((bits >> MANTISSA_BITS) & 0x7ff) as i32;
+    if biased_exponent == 0x7ff {
+        return 0;
+    }
+    // Subnormal inputs are below 1 and convert to 0 through the shift below, so we do not need to handle them separately.
+    let mantissa = (bits & ((1 << MANTISSA_BITS) - 1)) | (1 << MANTISSA_BITS);
+    // The value of the input is `mantissa * 2^shift`.
+    let shift = biased_exponent - 1023 - MANTISSA_BITS as i32;
+    let magnitude = if shift >= 128 || shift <= -64 {
+        // Either every bit of the integer is shifted out of the low 128 bits or the input is below 1.
+        0
+    } else if shift >= 0 {
+        (mantissa as u128) << shift
+    } else {
+        (mantissa >> -shift) as u128
+    };
+    if float.is_sign_negative() {
+        magnitude.wrapping_neg()
+    } else {
+        magnitude
+    }
+}
+
+/// Convert the input floating point value to the output integer type with the behavior that `P` selects.
+///
+/// This is one generic entry point for the conversions of this crate. The concrete functions like [`f32_to_i32`](crate::f32_to_i32) and [`FastFrom::fast_from`] are the same as the [`Unspecified`] policy.
+///
+/// ```
+/// use fast_float_to_integer::{convert_with, Checked, Saturate, Wrap};
+///
+/// assert_eq!(convert_with::<Saturate, f32, u8>(300.), 255);
+/// assert_eq!(convert_with::<Wrap, f32, u8>(300.), 44);
+/// assert_eq!(convert_with::<Checked, f32, u8>(300.), None);
+/// assert_eq!(convert_with::<Checked, f32, u8>(200.5), Some(200));
+/// ```
+#[inline(always)]
+pub fn convert_with<P, Float, Integer>(float: Float) -> P::Output
+where
+    P: Policy<Float, Integer>,
+{
+    P::convert(float)
+}
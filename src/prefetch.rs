@@ -0,0 +1,111 @@
+// Slice conversion with software prefetching. The hardware prefetchers of most CPUs detect the sequential access of the slice functions on their own. Software prefetching can still help when the source is streamed from RAM and the hardware prefetcher does not run far enough ahead, for example because it stops at page boundaries or because other threads compete for it.
+
+use core::mem::size_of;
+
+use crate::FastFrom;
+
+/// The prefetch distance in bytes of [`convert_slice_prefetched`].
+///
+/// This is a distance that works for streaming from RAM on common desktop CPUs. The best distance depends on the memory latency and the throughput of the conversion on the machine. The `buffer_size` benchmark group shows the effect.
+pub const PREFETCH_DISTANCE: usize = 4 << 10;
+
+/// The source is converted in blocks of this many bytes. Every cache line of a block is prefetched before the block is converted.
+const BLOCK_BYTES: usize = 1 << 10;
+
+/// The size of a cache line on the targets that have prefetch instructions. Prefetching more often than once per line does not hurt but costs instructions.
+const CACHE_LINE: usize = 64;
+
+/// Hint to the CPU that the memory at `pointer` will be read soon. This does not dereference the pointer and never faults.
+#[inline(always)]
+fn prefetch(pointer: *const u8) {
+    cfg_if::cfg_if! {
+        if #[cfg(all(
+            any(target_arch = "x86", target_arch = "x86_64"),
+            target_feature = "sse",
+            not(feature = "force-default")
+        ))] {
+            #[cfg(target_arch = "x86")]
+            use core::arch::x86::{_mm_prefetch, _MM_HINT_T0};
+            #[cfg(target_arch = "x86_64")]
+            use core::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+
+            // SAFETY: PREFETCHT0 is a hint and does not access the memory.
+            unsafe { _mm_prefetch::<_MM_HINT_T0>(pointer.cast()) };
+        } else if #[cfg(all(target_arch = "aarch64", not(feature = "force-default")))] {
+            // The prefetch intrinsics of aarch64 are not stable. PLDL1STRM prefetches for a load into L1 with the hint that the data is only used once.
+            // SAFETY: PRFM is a hint and does not access the memory.
+            unsafe {
+                core::arch::asm!("prfm pldl1strm, [{}]", in(reg) pointer, options(nostack, preserves_flags, readonly));
+            }
+        } else {
+            // There is no stable prefetch instruction on this target.
+            let _ = pointer;
+        }
+    }
+}
+
+/// Like [`convert_slice`](crate::convert_slice) but prefetches the source [`PREFETCH_DISTANCE`] bytes ahead.
+///
+/// See [`convert_slice_prefetched_with_distance`].
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+///
+/// ```
+/// let src = vec![1.5f32; 10_000];
+/// let mut dst = vec![0i32; 10_000];
+/// fast_float_to_integer::convert_slice_prefetched(&src, &mut dst);
+/// assert!(dst.iter().all(|integer| *integer == 1));
+/// ```
+#[inline]
+pub fn convert_slice_prefetched<Float, Integer>(src: &[Float], dst: &mut [Integer])
+where
+    Float: Copy,
+    Integer: FastFrom<Float>,
+{
+    convert_slice_prefetched_with_distance(src, dst, PREFETCH_DISTANCE);
+}
+
+/// Like [`convert_slice`](crate::convert_slice) but prefetches the source `distance` bytes ahead of the conversion.
+///
+/// The source is converted with the packed kernels in blocks of 1 KiB. Before a block is converted, the block that starts `distance` bytes later is prefetched. This helps when `src` is much larger than the caches and is read from RAM. For buffers that fit into the cache it only costs a few instructions per block. The results are the same as the ones of `convert_slice`.
+///
+/// x86 with SSE uses the PREFETCHT0 instruction and aarch64 uses PRFM. On the other targets there is no prefetching and this is the same as `convert_slice`.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+///
+/// ```
+/// let src = vec![-2.5f64; 10_000];
+/// let mut dst = vec![0i64; 10_000];
+/// fast_float_to_integer::convert_slice_prefetched_with_distance(&src, &mut dst, 8 << 10);
+/// assert!(dst.iter().all(|integer| *integer == -2));
+/// ```
+#[inline]
+pub fn convert_slice_prefetched_with_distance<Float, Integer>(
+    src: &[Float],
+    dst: &mut [Integer],
+    distance: usize,
+) where
+    Float: Copy,
+    Integer: FastFrom<Float>,
+{
+    assert_eq!(src.len(), dst.len(), "slices have different lengths");
+    // The floats are 4 or 8 bytes. The block is a multiple of the lanes of every kernel, so that only the last block has a scalar remainder.
+    let float_size = size_of::<Float>();
+    let block = BLOCK_BYTES / float_size;
+    let distance = distance / float_size;
+    let line = CACHE_LINE / float_size;
+    for (index, (src_block, dst_block)) in src.chunks(block).zip(dst.chunks_mut(block)).enumerate()
+    {
+        let start = (index * block).saturating_add(distance);
+        let end = start.saturating_add(block).min(src.len());
+        // Only addresses inside of the slice are prefetched.
+        for offset in (start..end).step_by(line) {
+            prefetch(src.as_ptr().wrapping_add(offset).cast());
+        }
+        Integer::convert_slice(src_block, dst_block);
+    }
+}
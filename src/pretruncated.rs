@@ -0,0 +1,48 @@
+// Conversions of floats that are already integers, for example because the pipeline called `trunc` or `floor` earlier. Every rounding direction gives the same integer for them, so the conversion can skip the rounding of the `*_floor`, `*_ceil` and `*_round` functions and the correction that some targets need for them.
+
+use crate::*;
+
+macro_rules! create_function {
+    ($name:ident, $convert:ident, $Float:ty, $Integer:ty) => {
+        /// Convert the input floating point value, which must not have a fractional part, to the output integer type.
+        ///
+        /// The input is expected to be the result of `trunc`, `floor`, `ceil` or `round`. Then the result is the same as the one of the rounding conversions like the `*_floor` function, without the cost of rounding again.
+        ///
+        /// If the input value is out of range of the output type, then the result is unspecified. Otherwise, the result is the same as the standard `as` conversion.
+        ///
+        /// # Panics
+        ///
+        /// With debug assertions, panics if the input has a fractional part or is NaN. Without debug assertions, such an input converts like the truncating conversion.
+        #[inline(always)]
+        pub fn $name(float: $Float) -> $Integer {
+            // Infinities pass the check. They are out of range, which is unspecified anyway.
+            debug_assert!(
+                sealed::Float::round_ties_even(float) == float,
+                "the float has a fractional part"
+            );
+            $convert(float)
+        }
+    };
+}
+
+create_function! {f32_to_i8_pretruncated, f32_to_i8, f32, i8}
+create_function! {f32_to_u8_pretruncated, f32_to_u8, f32, u8}
+create_function! {f32_to_i16_pretruncated, f32_to_i16, f32, i16}
+create_function! {f32_to_u16_pretruncated, f32_to_u16, f32, u16}
+create_function! {f32_to_i32_pretruncated, f32_to_i32, f32, i32}
+create_function! {f32_to_u32_pretruncated, f32_to_u32, f32, u32}
+create_function! {f32_to_i64_pretruncated, f32_to_i64, f32, i64}
+create_function! {f32_to_u64_pretruncated, f32_to_u64, f32, u64}
+create_function! {f32_to_i128_pretruncated, f32_to_i128, f32, i128}
+create_function! {f32_to_u128_pretruncated, f32_to_u128, f32, u128}
+
+create_function! {f64_to_i8_pretruncated, f64_to_i8, f64, i8}
+create_function! {f64_to_u8_pretruncated, f64_to_u8, f64, u8}
+create_function! {f64_to_i16_pretruncated, f64_to_i16, f64, i16}
+create_function! {f64_to_u16_pretruncated, f64_to_u16, f64, u16}
+create_function! {f64_to_i32_pretruncated, f64_to_i32, f64, i32}
+create_function! {f64_to_u32_pretruncated, f64_to_u32, f64, u32}
+create_function! {f64_to_i64_pretruncated, f64_to_i64, f64, i64}
+create_function! {f64_to_u64_pretruncated, f64_to_u64, f64, u64}
+create_function! {f64_to_i128_pretruncated, f64_to_i128, f64, i128}
+create_function! {f64_to_u128_pretruncated, f64_to_u128, f64, u128}
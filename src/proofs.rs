@@ -0,0 +1,34 @@
+// Kani harnesses that prove the contracts of the conversion functions. Run them with:
+//
+// cargo kani --package fast-float-to-integer -Z function-contracts
+
+macro_rules! create_proof {
+    ($name:ident) => {
+        #[kani::proof_for_contract(crate::$name)]
+        fn $name() {
+            crate::$name(kani::any());
+        }
+    };
+}
+
+create_proof! {f32_to_i8}
+create_proof! {f32_to_u8}
+create_proof! {f32_to_i16}
+create_proof! {f32_to_u16}
+create_proof! {f32_to_i32}
+create_proof! {f32_to_u32}
+create_proof! {f32_to_i64}
+create_proof! {f32_to_u64}
+create_proof! {f32_to_i128}
+create_proof! {f32_to_u128}
+
+create_proof! {f64_to_i8}
+create_proof! {f64_to_u8}
+create_proof! {f64_to_i16}
+create_proof! {f64_to_u16}
+create_proof! {f64_to_i32}
+create_proof! {f64_to_u32}
+create_proof! {f64_to_i64}
+create_proof! {f64_to_u64}
+create_proof! {f64_to_i128}
+create_proof! {f64_to_u128}
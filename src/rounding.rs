@@ -0,0 +1,109 @@
+// Conversions that round to the nearest integer in a direction instead of truncating. Some architectures have conversion instructions that round, which are faster than rounding and converting separately. The target modules that have them provide a `rounding` module. The other targets use the algorithms in `software::rounding`.
+
+cfg_if::cfg_if! {
+    if #[cfg(all(
+        target_arch = "aarch64",
+        target_feature = "neon",
+        not(any(feature = "force-default", feature = "portable-deterministic", kani))
+    ))] {
+        use crate::target_aarch64_neon::rounding as implementation;
+    } else if #[cfg(all(
+        target_arch = "x86_64",
+        target_feature = "sse4.1",
+        not(any(feature = "force-default", feature = "portable-deterministic", kani))
+    ))] {
+        use crate::target_x86_64_sse::rounding as implementation;
+    } else if #[cfg(all(
+        target_arch = "x86",
+        target_feature = "sse4.1",
+        not(any(feature = "force-default", feature = "portable-deterministic", kani))
+    ))] {
+        use crate::target_x86_sse::rounding as implementation;
+    } else {
+        use crate::software::rounding as implementation;
+    }
+}
+
+macro_rules! create_function {
+    ($floor:ident, $ceil:ident, $round:ident, $round_ties_even:ident, $Float:ty, $Integer:ty) => {
+        /// Round the input floating point value toward negative infinity and convert it to the output integer type.
+        ///
+        /// If the rounded value is out of range of the output type, then the result is unspecified. Otherwise, the result is the same as the standard `as` conversion of the rounded value.
+        #[cfg_attr(feature = "show-asm", inline(never))]
+        #[cfg_attr(not(feature = "show-asm"), inline(always))]
+        pub fn $floor(float: $Float) -> $Integer {
+            implementation::$floor(float)
+        }
+
+        /// Round the input floating point value toward positive infinity and convert it to the output integer type.
+        ///
+        /// If the rounded value is out of range of the output type, then the result is unspecified. Otherwise, the result is the same as the standard `as` conversion of the rounded value.
+        #[cfg_attr(feature = "show-asm", inline(never))]
+        #[cfg_attr(not(feature = "show-asm"), inline(always))]
+        pub fn $ceil(float: $Float) -> $Integer {
+            implementation::$ceil(float)
+        }
+
+        /// Round the input floating point value to the nearest integer with ties away from zero like `round` of the standard library and convert it to the output integer type.
+        ///
+        /// If the rounded value is out of range of the output type, then the result is unspecified. Otherwise, the result is the same as the standard `as` conversion of the rounded value.
+        #[cfg_attr(feature = "show-asm", inline(never))]
+        #[cfg_attr(not(feature = "show-asm"), inline(always))]
+        pub fn $round(float: $Float) -> $Integer {
+            implementation::$round(float)
+        }
+
+        /// Round the input floating point value to the nearest integer with ties to even and convert it to the output integer type.
+        ///
+        /// If the rounded value is out of range of the output type, then the result is unspecified. Otherwise, the result is the same as the standard `as` conversion of the rounded value.
+        #[cfg_attr(feature = "show-asm", inline(never))]
+        #[cfg_attr(not(feature = "show-asm"), inline(always))]
+        pub fn $round_ties_even(float: $Float) -> $Integer {
+            implementation::$round_ties_even(float)
+        }
+
+        impl crate::sealed::RoundFrom<$Float> for $Integer {
+            #[inline(always)]
+            fn floor_from(float: $Float) -> Self {
+                $floor(float)
+            }
+
+            #[inline(always)]
+            fn ceil_from(float: $Float) -> Self {
+                $ceil(float)
+            }
+
+            #[inline(always)]
+            fn round_from(float: $Float) -> Self {
+                $round(float)
+            }
+
+            #[inline(always)]
+            fn round_ties_even_from(float: $Float) -> Self {
+                $round_ties_even(float)
+            }
+        }
+    };
+}
+
+create_function! {f32_to_i8_floor, f32_to_i8_ceil, f32_to_i8_round, f32_to_i8_round_ties_even, f32, i8}
+create_function! {f32_to_u8_floor, f32_to_u8_ceil, f32_to_u8_round, f32_to_u8_round_ties_even, f32, u8}
+create_function! {f32_to_i16_floor, f32_to_i16_ceil, f32_to_i16_round, f32_to_i16_round_ties_even, f32, i16}
+create_function! {f32_to_u16_floor, f32_to_u16_ceil, f32_to_u16_round, f32_to_u16_round_ties_even, f32, u16}
+create_function! {f32_to_i32_floor, f32_to_i32_ceil, f32_to_i32_round, f32_to_i32_round_ties_even, f32, i32}
+create_function! {f32_to_u32_floor, f32_to_u32_ceil, f32_to_u32_round, f32_to_u32_round_ties_even, f32, u32}
+create_function! {f32_to_i64_floor, f32_to_i64_ceil, f32_to_i64_round, f32_to_i64_round_ties_even, f32, i64}
+create_function! {f32_to_u64_floor, f32_to_u64_ceil, f32_to_u64_round, f32_to_u64_round_ties_even, f32, u64}
+create_function! {f32_to_i128_floor, f32_to_i128_ceil, f32_to_i128_round, f32_to_i128_round_ties_even, f32, i128}
+create_function! {f32_to_u128_floor, f32_to_u128_ceil, f32_to_u128_round, f32_to_u128_round_ties_even, f32, u128}
+
+create_function! {f64_to_i8_floor, f64_to_i8_ceil, f64_to_i8_round, f64_to_i8_round_ties_even, f64, i8}
+create_function! {f64_to_u8_floor, f64_to_u8_ceil, f64_to_u8_round, f64_to_u8_round_ties_even, f64, u8}
+create_function! {f64_to_i16_floor, f64_to_i16_ceil, f64_to_i16_round, f64_to_i16_round_ties_even, f64, i16}
+create_function! {f64_to_u16_floor, f64_to_u16_ceil, f64_to_u16_round, f64_to_u16_round_ties_even, f64, u16}
+create_function! {f64_to_i32_floor, f64_to_i32_ceil, f64_to_i32_round, f64_to_i32_round_ties_even, f64, i32}
+create_function! {f64_to_u32_floor, f64_to_u32_ceil, f64_to_u32_round, f64_to_u32_round_ties_even, f64, u32}
+create_function! {f64_to_i64_floor, f64_to_i64_ceil, f64_to_i64_round, f64_to_i64_round_ties_even, f64, i64}
+create_function! {f64_to_u64_floor, f64_to_u64_ceil, f64_to_u64_round, f64_to_u64_round_ties_even, f64, u64}
+create_function! {f64_to_i128_floor, f64_to_i128_ceil, f64_to_i128_round, f64_to_i128_round_ties_even, f64, i128}
+create_function! {f64_to_u128_floor, f64_to_u128_ceil, f64_to_u128_round, f64_to_u128_round_ties_even, f64, u128}
@@ -0,0 +1,96 @@
+// Conversions that multiply the input by a scale factor that is known at compile time. Float const generics are not stable, so the scale is a fraction of integers or a power of ten.
+
+use crate::*;
+
+/// The scale factor as a constant of each float type. Evaluating the constants fails at compile time if the denominator is zero or if the numerator or denominator is not exactly representable in the float type.
+///
+/// Both operands of the division are exact, so the division rounds once and the factor is the correctly rounded fraction. Converting the quotient of f64 operands to f32 would round twice, and integers above 2^53 would already be rounded as f64 operands.
+pub(crate) struct Scale<const NUMERATOR: i64, const DENOMINATOR: i64>;
+
+impl<const NUMERATOR: i64, const DENOMINATOR: i64> Scale<NUMERATOR, DENOMINATOR> {
+    pub(crate) const F32: f32 = {
+        assert!(DENOMINATOR != 0, "scale denominator is zero");
+        // i128 holds the value of i64::MAX as f32, which is 2^63.
+        assert!(
+            NUMERATOR as f32 as i128 == NUMERATOR as i128
+                && DENOMINATOR as f32 as i128 == DENOMINATOR as i128,
+            "scale numerator or denominator is not exactly representable in f32"
+        );
+        NUMERATOR as f32 / DENOMINATOR as f32
+    };
+    pub(crate) const F64: f64 = {
+        assert!(DENOMINATOR != 0, "scale denominator is zero");
+        assert!(
+            NUMERATOR as f64 as i128 == NUMERATOR as i128
+                && DENOMINATOR as f64 as i128 == DENOMINATOR as i128,
+            "scale numerator or denominator is not exactly representable in f64"
+        );
+        NUMERATOR as f64 / DENOMINATOR as f64
+    };
+}
+
+macro_rules! create_function {
+    ($name:ident, $convert:ident, $Float:ty, $Integer:ty, $SCALE:ident) => {
+        /// Multiply the input by `NUMERATOR / DENOMINATOR` and convert the product to the output integer type.
+        ///
+        /// The scale factor is the correctly rounded fraction and is computed at compile time. The multiplication is a single float multiplication with the scale factor, which the compiler can fold into surrounding constants. The numerator and denominator must be exactly representable in the float type of the input, which every integer up to 2^24 for f32 and 2^53 for f64 is. Using a zero or inexact denominator or an inexact numerator is a compile time error.
+        ///
+        /// If the product is out of range of the output type, then the result is unspecified. Otherwise, the result is the same as the standard `as` conversion of the product.
+        #[inline(always)]
+        pub fn $name<const NUMERATOR: i64, const DENOMINATOR: i64>(float: $Float) -> $Integer {
+            $convert(float * Scale::<NUMERATOR, DENOMINATOR>::$SCALE)
+        }
+    };
+}
+
+create_function! {f32_to_i8_scaled_const, f32_to_i8, f32, i8, F32}
+create_function! {f32_to_u8_scaled_const, f32_to_u8, f32, u8, F32}
+create_function! {f32_to_i16_scaled_const, f32_to_i16, f32, i16, F32}
+create_function! {f32_to_u16_scaled_const, f32_to_u16, f32, u16, F32}
+create_function! {f32_to_i32_scaled_const, f32_to_i32, f32, i32, F32}
+create_function! {f32_to_u32_scaled_const, f32_to_u32, f32, u32, F32}
+create_function! {f32_to_i64_scaled_const, f32_to_i64, f32, i64, F32}
+create_function! {f32_to_u64_scaled_const, f32_to_u64, f32, u64, F32}
+create_function! {f32_to_i128_scaled_const, f32_to_i128, f32, i128, F32}
+create_function! {f32_to_u128_scaled_const, f32_to_u128, f32, u128, F32}
+
+create_function! {f64_to_i8_scaled_const, f64_to_i8, f64, i8, F64}
+create_function! {f64_to_u8_scaled_const, f64_to_u8, f64, u8, F64}
+create_function! {f64_to_i16_scaled_const, f64_to_i16, f64, i16, F64}
+create_function! {f64_to_u16_scaled_const, f64_to_u16, f64, u16, F64}
+create_function! {f64_to_i32_scaled_const, f64_to_i32, f64, i32, F64}
+create_function! {f64_to_u32_scaled_const, f64_to_u32, f64, u32, F64}
+create_function! {f64_to_i64_scaled_const, f64_to_i64, f64, i64, F64}
+create_function! {f64_to_u64_scaled_const, f64_to_u64, f64, u64, F64}
+create_function! {f64_to_i128_scaled_const, f64_to_i128, f64, i128, F64}
+create_function! {f64_to_u128_scaled_const, f64_to_u128, f64, u128, F64}
+
+/// 10^EXP correctly rounded to f64. Evaluating the constant fails at compile time if EXP is larger than 38.
+pub(crate) struct Pow10<const EXP: u32>;
+
+impl<const EXP: u32> Pow10<EXP> {
+    pub(crate) const F64: f64 = {
+        assert!(EXP <= 38, "10^EXP does not fit into u128");
+        // The conversion from u128 rounds to nearest, unlike `powi` or repeated multiplication, which are off by one ulp for some exponents above 22. Up to 10^22 the power is exact.
+        10u128.pow(EXP) as f64
+    };
+}
+
+macro_rules! create_decimal_function {
+    ($name:ident, $convert:ident, $Integer:ty) => {
+        /// Multiply the input by 10^EXP and convert the product to the output integer type, for example to convert an amount of money to cents with `EXP = 2`.
+        ///
+        /// The power of ten is a correctly rounded constant that is computed at compile time. Up to `EXP = 22` it is exact and the result is the truncated correctly rounded product. Using an `EXP` larger than 38 is a compile time error.
+        ///
+        /// Like the standard `as` conversion of the product, this truncates. Most decimal fractions are not exact in f64, so inputs close to a multiple of 10^-EXP can truncate to the integer below: 0.29 is slightly less than 29 / 100 and converts to 28 with `EXP = 2`. Round the product with a function like [`f64_to_i64_round_ties_even`] if you need the nearest integer.
+        ///
+        /// If the product is out of range of the output type, then the result is unspecified. Otherwise, the result is the same as the standard `as` conversion of the product.
+        #[inline(always)]
+        pub fn $name<const EXP: u32>(float: f64) -> $Integer {
+            $convert(float * Pow10::<EXP>::F64)
+        }
+    };
+}
+
+create_decimal_function! {f64_to_i32_decimal_scaled, f64_to_i32, i32}
+create_decimal_function! {f64_to_i64_decimal_scaled, f64_to_i64, i64}
@@ -0,0 +1,56 @@
+// Conversions of a number of seconds to whole seconds and nanoseconds like timespec and Duration have.
+
+use core::time::Duration;
+
+use crate::{sealed::Float, *};
+
+/// Split `seconds` into whole seconds and nanoseconds like a timespec.
+///
+/// The whole seconds are rounded towards negative infinity and the nanoseconds are in `0..1_000_000_000`, so -1.25 becomes (-2, 750_000_000). The nanoseconds are rounded to nearest with ties to even. If they round up to a whole second, then the seconds are increased instead. There are no branches.
+///
+/// If the whole seconds are out of range of i64, then the result is unspecified, but the nanoseconds are still less than one second. The result is exact to the nanosecond for magnitudes up to about 2^22 seconds, 48 days. Larger magnitudes have less precision than a nanosecond in f64.
+///
+/// ```
+/// use fast_float_to_integer::f64_to_seconds_nanos;
+///
+/// assert_eq!(f64_to_seconds_nanos(1.5), (1, 500_000_000));
+/// assert_eq!(f64_to_seconds_nanos(-1.25), (-2, 750_000_000));
+/// assert_eq!(f64_to_seconds_nanos(0.9999999999), (1, 0));
+/// ```
+#[inline(always)]
+pub fn f64_to_seconds_nanos(seconds: f64) -> (i64, u32) {
+    const NANOS_PER_SECOND: u32 = 1_000_000_000;
+
+    let truncated = f64_to_i64(seconds);
+    // Truncation rounds negative values with a fraction up. The wrapping subtraction cannot panic for out of range values.
+    let whole = truncated.wrapping_sub((truncated as f64 > seconds) as i64);
+    // The subtraction is exact because both values are close to each other. The fraction is in 0..1.
+    let fraction = seconds - whole as f64;
+    // The inherent method needs the standard library.
+    let nanos = f64_to_u32(Float::round_ties_even(fraction * NANOS_PER_SECOND as f64));
+    // In range, the nanoseconds are at most one second. Selecting 0 instead of subtracting keeps them below one second for every input.
+    let carry = nanos >= NANOS_PER_SECOND;
+    (
+        whole.wrapping_add(carry as i64),
+        if carry { 0 } else { nanos },
+    )
+}
+
+/// Like [`f64_to_seconds_nanos`] but returns a [`Duration`].
+///
+/// Unlike [`Duration::from_secs_f64`], this never panics. If `seconds` is negative or out of range of u64, then the result is unspecified.
+///
+/// ```
+/// use core::time::Duration;
+///
+/// assert_eq!(
+///     fast_float_to_integer::f64_to_duration(2.000_000_001),
+///     Duration::new(2, 1)
+/// );
+/// ```
+#[inline(always)]
+pub fn f64_to_duration(seconds: f64) -> Duration {
+    let (whole, nanos) = f64_to_seconds_nanos(seconds);
+    // The nanoseconds are less than a second for every input, so the constructor does not carry them into the seconds, which could overflow.
+    Duration::new(whole as u64, nanos)
+}
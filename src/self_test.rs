@@ -0,0 +1,134 @@
+// A check of the conversions at runtime, on the machine that runs them. Deployments that must verify their dependencies, like medical and industrial devices, can run it at startup.
+//
+// The expected results of in range inputs come from the soft-float decoding of the bit pattern, which only uses integer arithmetic. Out of range inputs and NaN are only checked if `behavior` promises a result.
+
+use core::{fmt, hint::black_box};
+
+use crate::{behavior, sealed::Sealed, software::soft_float, Conversion, Nan, OutOfRange};
+
+/// A wrong result of [`self_test`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SelfTestError {
+    /// The conversion that returned the wrong result.
+    pub conversion: Conversion,
+    /// The bit pattern of the input. An f32 is in the lower 32 bits.
+    pub input: u64,
+    /// The result, converted to u128 with the `as` operator.
+    pub actual: u128,
+    /// The expected result, converted to u128 with the `as` operator.
+    pub expected: u128,
+}
+
+impl fmt::Display for SelfTestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} of the float with the bit pattern {:#x} returned {:#x} instead of {:#x}",
+            self.conversion, self.input, self.actual, self.expected
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SelfTestError {}
+
+/// The bit patterns of the inputs with both signs: zeros, subnormals, 1.5, 2.5, the powers of two from 0.5 up to 2^129 or the largest power of two of the float type and the floats next to them, infinity and NaN.
+fn inputs(mantissa_bits: u32, exponent_bias: i64, sign: u64) -> impl Iterator<Item = u64> {
+    let power = move |exponent: i64| ((exponent + exponent_bias) as u64) << mantissa_bits;
+    let infinity = power(exponent_bias + 1);
+    let quiet = 1 << (mantissa_bits - 1);
+    let special = [
+        0,
+        1,
+        quiet - 1,
+        power(0) | quiet,
+        power(1) | quiet >> 1,
+        infinity,
+        infinity | quiet,
+        infinity | 1,
+    ];
+    let powers = (-1..=exponent_bias.min(129)).flat_map(move |exponent| {
+        let bits = power(exponent);
+        [bits - 1, bits, bits + 1]
+    });
+    special
+        .into_iter()
+        .chain(powers)
+        .flat_map(move |bits| [bits, bits | sign])
+}
+
+macro_rules! check {
+    ($conversion:ident, $convert:ident, $reference:ident, $Float:ty, $Bits:ty, $Integer:ty) => {{
+        let behavior = behavior(Conversion::$conversion);
+        let inputs = inputs(
+            <$Float>::MANTISSA_DIGITS - 1,
+            <$Float>::MAX_EXP as i64 - 1,
+            1 << (<$Bits>::BITS - 1),
+        );
+        for input in inputs {
+            let bits = input as $Bits;
+            let float = <$Float>::from_bits(bits);
+            let expected: Option<$Integer> = if float.is_nan() {
+                match behavior.nan {
+                    Nan::Zero => Some(0),
+                    Nan::Sentinel(sentinel) => Some(sentinel as $Integer),
+                    Nan::Unspecified => None,
+                }
+            } else if <$Integer as Sealed<$Float>>::in_range(float) {
+                Some(soft_float::$reference(bits))
+            } else {
+                match behavior.out_of_range {
+                    OutOfRange::Saturating if float < 0. => Some(<$Integer>::MIN),
+                    OutOfRange::Saturating => Some(<$Integer>::MAX),
+                    OutOfRange::Sentinel(sentinel) => Some(sentinel as $Integer),
+                    OutOfRange::Unspecified => None,
+                }
+            };
+            // The compiler must not compute the results at compile time.
+            let actual = crate::$convert(black_box(float));
+            if let Some(expected) = expected {
+                if actual != expected {
+                    return Err(SelfTestError {
+                        conversion: Conversion::$conversion,
+                        input,
+                        actual: actual as u128,
+                        expected: expected as u128,
+                    });
+                }
+            }
+        }
+    }};
+}
+
+/// Check the conversions of the active target on this machine.
+///
+/// Every conversion converts a few hundred inputs at the boundaries, like powers of two, the floats next to them, zeros, subnormals, infinity and NaN. The in range results must be the same as the ones of the standard `as` operator, which the check computes from the bit pattern with integer arithmetic. The results of out of range inputs and NaN are checked if [`behavior`] promises them. With optimizations the check takes about 0.1 milliseconds on a desktop x86_64 CPU.
+///
+/// Returns the first wrong result. This is for deployments that verify at startup that the conversions work on the actual hardware, for example because of an FPU erratum.
+///
+/// ```
+/// fast_float_to_integer::self_test().unwrap();
+/// ```
+pub fn self_test() -> Result<(), SelfTestError> {
+    check!(F32ToI8, f32_to_i8, f32_bits_to_i8, f32, u32, i8);
+    check!(F32ToU8, f32_to_u8, f32_bits_to_u8, f32, u32, u8);
+    check!(F32ToI16, f32_to_i16, f32_bits_to_i16, f32, u32, i16);
+    check!(F32ToU16, f32_to_u16, f32_bits_to_u16, f32, u32, u16);
+    check!(F32ToI32, f32_to_i32, f32_bits_to_i32, f32, u32, i32);
+    check!(F32ToU32, f32_to_u32, f32_bits_to_u32, f32, u32, u32);
+    check!(F32ToI64, f32_to_i64, f32_bits_to_i64, f32, u32, i64);
+    check!(F32ToU64, f32_to_u64, f32_bits_to_u64, f32, u32, u64);
+    check!(F32ToI128, f32_to_i128, f32_bits_to_i128, f32, u32, i128);
+    check!(F32ToU128, f32_to_u128, f32_bits_to_u128, f32, u32, u128);
+    check!(F64ToI8, f64_to_i8, f64_bits_to_i8, f64, u64, i8);
+    check!(F64ToU8, f64_to_u8, f64_bits_to_u8, f64, u64, u8);
+    check!(F64ToI16, f64_to_i16, f64_bits_to_i16, f64, u64, i16);
+    check!(F64ToU16, f64_to_u16, f64_bits_to_u16, f64, u64, u16);
+    check!(F64ToI32, f64_to_i32, f64_bits_to_i32, f64, u64, i32);
+    check!(F64ToU32, f64_to_u32, f64_bits_to_u32, f64, u64, u32);
+    check!(F64ToI64, f64_to_i64, f64_bits_to_i64, f64, u64, i64);
+    check!(F64ToU64, f64_to_u64, f64_bits_to_u64, f64, u64, u64);
+    check!(F64ToI128, f64_to_i128, f64_bits_to_i128, f64, u64, i128);
+    check!(F64ToU128, f64_to_u128, f64_bits_to_u128, f64, u64, u128);
+    Ok(())
+}
@@ -0,0 +1,138 @@
+// Conversions of SIMD registers for callers that already hold their floats in registers. Converting through arrays relies on the optimizer to remove the loads and stores around the conversion.
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2"
+))]
+mod x86 {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+
+    use crate::{exact_pow2_f32, exact_pow2_f64};
+
+    /// Convert four f32 to i32 with the CVTTPS2DQ instruction.
+    ///
+    /// Available on x86 and x86_64 with SSE2.
+    ///
+    /// If a lane is out of range of i32, then its result is unspecified. Otherwise, the result is the same as the standard `as` conversion.
+    #[inline(always)]
+    pub fn m128_to_i32x4(floats: __m128) -> __m128i {
+        unsafe { _mm_cvttps_epi32(floats) }
+    }
+
+    /// Convert four f32 to i32 with the CVTPS2DQ instruction. The lanes are rounded to the nearest integer with ties to even instead of truncated.
+    ///
+    /// Available on x86 and x86_64 with SSE2.
+    ///
+    /// If a rounded lane is out of range of i32, then its result is unspecified.
+    #[inline(always)]
+    pub fn m128_to_i32x4_round_ties_even(floats: __m128) -> __m128i {
+        // The instruction uses the rounding mode of MXCSR. Rust code runs with the default mode, which is to nearest with ties to even.
+        unsafe { _mm_cvtps_epi32(floats) }
+    }
+
+    /// Convert four f32 to i32 like the standard `as` conversion.
+    ///
+    /// Available on x86 and x86_64 with SSE2.
+    #[inline(always)]
+    pub fn m128_to_i32x4_saturating(floats: __m128) -> __m128i {
+        // Out of range and NaN lanes are i32::MIN. This is already the saturated value for too small inputs.
+        let integers = m128_to_i32x4(floats);
+        // Flipping all bits of i32::MIN results in i32::MAX.
+        let threshold = unsafe { _mm_set1_ps(exact_pow2_f32(31)) };
+        let too_large = unsafe { _mm_castps_si128(_mm_cmpge_ps(floats, threshold)) };
+        let integers = unsafe { _mm_xor_si128(integers, too_large) };
+        // NaN lanes become 0.
+        let not_nan = unsafe { _mm_castps_si128(_mm_cmpord_ps(floats, floats)) };
+        unsafe { _mm_and_si128(integers, not_nan) }
+    }
+
+    /// Convert two f64 to i32 with the CVTTPD2DQ instruction. The results are in the lower two lanes. The upper two lanes are 0.
+    ///
+    /// Available on x86 and x86_64 with SSE2.
+    ///
+    /// If a lane is out of range of i32, then its result is unspecified. Otherwise, the result is the same as the standard `as` conversion.
+    #[inline(always)]
+    pub fn m128d_to_i32x2(floats: __m128d) -> __m128i {
+        unsafe { _mm_cvttpd_epi32(floats) }
+    }
+
+    /// Convert two f64 to i32 with the CVTPD2DQ instruction. The lanes are rounded to the nearest integer with ties to even instead of truncated. The results are in the lower two lanes. The upper two lanes are 0.
+    ///
+    /// Available on x86 and x86_64 with SSE2.
+    ///
+    /// If a rounded lane is out of range of i32, then its result is unspecified.
+    #[inline(always)]
+    pub fn m128d_to_i32x2_round_ties_even(floats: __m128d) -> __m128i {
+        // see m128_to_i32x4_round_ties_even
+        unsafe { _mm_cvtpd_epi32(floats) }
+    }
+
+    /// Convert two f64 to i32 like the standard `as` conversion. The results are in the lower two lanes. The upper two lanes are 0.
+    ///
+    /// Available on x86 and x86_64 with SSE2.
+    #[inline(always)]
+    pub fn m128d_to_i32x2_saturating(floats: __m128d) -> __m128i {
+        // see m128_to_i32x4_saturating
+        let integers = m128d_to_i32x2(floats);
+        let threshold = unsafe { _mm_set1_pd(exact_pow2_f64(31)) };
+        let too_large = unsafe { _mm_castpd_si128(_mm_cmpge_pd(floats, threshold)) };
+        let not_nan = unsafe { _mm_castpd_si128(_mm_cmpord_pd(floats, floats)) };
+        // The masks have 64 bit lanes. Move the lower half of each lane into the lower two 32 bit lanes to match the integers.
+        let too_large = unsafe { _mm_shuffle_epi32(too_large, 0b1000) };
+        let not_nan = unsafe { _mm_shuffle_epi32(not_nan, 0b1000) };
+        let integers = unsafe { _mm_xor_si128(integers, too_large) };
+        let integers = unsafe { _mm_and_si128(integers, not_nan) };
+        // The shuffles fill the upper two lanes with copies of the lower ones. Zero them again.
+        unsafe { _mm_move_epi64(integers) }
+    }
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2"
+))]
+pub use x86::*;
+
+// The NEON conversions saturate and convert NaN to 0 like the standard `as` conversion, so there are no separate saturating functions.
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+mod aarch64 {
+    use core::arch::aarch64::*;
+
+    /// Convert four f32 to i32 with the FCVTZS instruction like the standard `as` conversion.
+    ///
+    /// Available on aarch64 with NEON.
+    #[inline(always)]
+    pub fn float32x4_to_int32x4(floats: float32x4_t) -> int32x4_t {
+        unsafe { vcvtq_s32_f32(floats) }
+    }
+
+    /// Convert four f32 to u32 with the FCVTZU instruction like the standard `as` conversion.
+    ///
+    /// Available on aarch64 with NEON.
+    #[inline(always)]
+    pub fn float32x4_to_uint32x4(floats: float32x4_t) -> uint32x4_t {
+        unsafe { vcvtq_u32_f32(floats) }
+    }
+
+    /// Convert two f64 to i64 with the FCVTZS instruction like the standard `as` conversion.
+    ///
+    /// Available on aarch64 with NEON.
+    #[inline(always)]
+    pub fn float64x2_to_int64x2(floats: float64x2_t) -> int64x2_t {
+        unsafe { vcvtq_s64_f64(floats) }
+    }
+
+    /// Convert two f64 to u64 with the FCVTZU instruction like the standard `as` conversion.
+    ///
+    /// Available on aarch64 with NEON.
+    #[inline(always)]
+    pub fn float64x2_to_uint64x2(floats: float64x2_t) -> uint64x2_t {
+        unsafe { vcvtq_u64_f64(floats) }
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+pub use aarch64::*;
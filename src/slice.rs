@@ -0,0 +1,620 @@
+// Conversions of whole slices. Most functions loop over the scalar conversions. This gives the compiler the chance to vectorize the loop. `convert_slice` and `saturating_convert_slice` use the packed kernels of the active target.
+
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::AtomicU64;
+#[cfg(target_has_atomic = "32")]
+use core::sync::atomic::{AtomicU32, Ordering};
+use core::{marker::PhantomData, mem::MaybeUninit};
+
+use crate::{FastFrom, FloatType};
+
+/// The number of elements of the buffers on the stack that the functions convert in chunks. The buffers stay in the L1 cache. A multiple of 8, so the chunks start at the beginning of a byte of a bitmap.
+pub(crate) const CHUNK: usize = 64;
+
+/// Convert every element of `src` and write the result to the element at the same index in `dst`.
+///
+/// The conversions with a packed kernel convert several elements at once. On x86 with SSE2 f32 to i32 converts four elements with CVTTPS2DQ. On aarch64 with NEON f32 to i32 converts four elements and f64 to i32 two elements with FCVTZS, and f32 to u8 and u16 convert sixteen elements. With the `sve` target feature f32 to i32 converts sixteen elements with the SVE FCVTZS instruction at the vector length of the CPU.
+///
+/// Every element gets exactly the same result as the scalar conversion, like [`f32_to_i32`](crate::f32_to_i32), on every target and for every input. This includes the unspecified results of out of range inputs and NaN, so they do not depend on the position of the element in the slice.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+///
+/// ```
+/// let src = [1.5f32, -2.5, 3.0];
+/// let mut dst = [0i32; 3];
+/// fast_float_to_integer::convert_slice(&src, &mut dst);
+/// assert_eq!(dst, [1, -2, 3]);
+/// ```
+#[inline]
+pub fn convert_slice<Float, Integer>(src: &[Float], dst: &mut [Integer])
+where
+    Float: Copy,
+    Integer: FastFrom<Float>,
+{
+    assert_eq!(src.len(), dst.len(), "slices have different lengths");
+    Integer::convert_slice(src, dst);
+}
+
+/// Like [`convert_slice`] but with the conversion behavior of the standard `as` operator.
+///
+/// Out of range inputs saturate to the minimal/maximal value of the output type and NaN converts to 0. Unlike the `as` operator in a loop, this uses packed conversion instructions with explicit clamping where the active target has them.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+///
+/// ```
+/// let src = [1.5f32, -3e9, 3e9, f32::NAN];
+/// let mut dst = [0i32; 4];
+/// fast_float_to_integer::saturating_convert_slice(&src, &mut dst);
+/// assert_eq!(dst, [1, i32::MIN, i32::MAX, 0]);
+/// ```
+#[inline]
+pub fn saturating_convert_slice<Float, Integer>(src: &[Float], dst: &mut [Integer])
+where
+    Float: Copy,
+    Integer: FastFrom<Float>,
+{
+    assert_eq!(src.len(), dst.len(), "slices have different lengths");
+    Integer::saturating_convert_slice(src, dst);
+}
+
+/// Like [`convert_slice`] but rounds to the nearest integer with ties to even instead of truncating.
+///
+/// This is banker's rounding like `f32::round_ties_even`. The rounding is part of the conversion, so there is no separate pass over the data. On x86 with SSE2 the f32 and f64 to i32 conversions use the CVTPS2DQ and CVTPD2DQ instructions.
+///
+/// If a rounded value is out of range of the output type, then its result is unspecified.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+///
+/// ```
+/// let src = [0.5f32, 1.5, 2.5, -0.5, -1.5, 2.7];
+/// let mut dst = [0i32; 6];
+/// fast_float_to_integer::round_ties_even_convert_slice(&src, &mut dst);
+/// assert_eq!(dst, [0, 2, 2, 0, -2, 3]);
+/// ```
+#[inline]
+pub fn round_ties_even_convert_slice<Float, Integer>(src: &[Float], dst: &mut [Integer])
+where
+    Float: Copy,
+    Integer: FastFrom<Float>,
+{
+    assert_eq!(src.len(), dst.len(), "slices have different lengths");
+    Integer::round_ties_even_convert_slice(src, dst);
+}
+
+/// Decode the floats of `src` in small chunks into a buffer on the stack and convert each chunk into `dst`.
+///
+/// The buffer stays in the L1 cache, so unlike decoding the whole slice first this reads and writes main memory only once.
+#[inline(always)]
+fn convert_bytes<Float, Integer>(src: &[u8], dst: &mut [Integer], decode: impl Fn(&[u8]) -> Float)
+where
+    Float: FloatType,
+    Integer: FastFrom<Float>,
+{
+    let size = (Float::BITS / 8) as usize;
+    assert_eq!(
+        src.len(),
+        dst.len() * size,
+        "byte slice does not have the size of the destination slice"
+    );
+    let mut buffer = [Float::ZERO; CHUNK];
+    for (src, dst) in src
+        .chunks(buffer.len() * size)
+        .zip(dst.chunks_mut(buffer.len()))
+    {
+        let buffer = &mut buffer[..dst.len()];
+        for (float, bytes) in buffer.iter_mut().zip(src.chunks_exact(size)) {
+            *float = decode(bytes);
+        }
+        Integer::convert_slice(buffer, dst);
+    }
+}
+
+/// Like [`convert_slice`] but `src` holds the floats as little endian bytes.
+///
+/// The bytes do not need to be aligned. This converts data from file formats and network protocols without decoding it into a float buffer first.
+///
+/// # Panics
+///
+/// Panics if `src` does not have as many bytes as `dst` has floats.
+///
+/// ```
+/// let src: Vec<u8> = [1.5f32, -2.5, 3.0].iter().flat_map(|float| float.to_le_bytes()).collect();
+/// let mut dst = [0i32; 3];
+/// fast_float_to_integer::convert_le_bytes::<f32, _>(&src, &mut dst);
+/// assert_eq!(dst, [1, -2, 3]);
+/// ```
+#[inline]
+pub fn convert_le_bytes<Float, Integer>(src: &[u8], dst: &mut [Integer])
+where
+    Float: FloatType,
+    Integer: FastFrom<Float>,
+{
+    convert_bytes(src, dst, Float::from_le_bytes);
+}
+
+/// Like [`convert_le_bytes`] but `src` holds the floats as big endian bytes.
+///
+/// # Panics
+///
+/// Panics if `src` does not have as many bytes as `dst` has floats.
+///
+/// ```
+/// let src: Vec<u8> = [1.5f64, -2.5, 3.0].iter().flat_map(|float| float.to_be_bytes()).collect();
+/// let mut dst = [0i32; 3];
+/// fast_float_to_integer::convert_be_bytes::<f64, _>(&src, &mut dst);
+/// assert_eq!(dst, [1, -2, 3]);
+/// ```
+#[inline]
+pub fn convert_be_bytes<Float, Integer>(src: &[u8], dst: &mut [Integer])
+where
+    Float: FloatType,
+    Integer: FastFrom<Float>,
+{
+    convert_bytes(src, dst, Float::from_be_bytes);
+}
+
+/// Like [`convert_le_bytes`] but `src` holds the floats as native endian bytes.
+///
+/// This is the layout of a float slice that has been cast to bytes, for example with `bytemuck::cast_slice`, and of memory mapped float columns. If `src` is aligned for the float type, then the bytes are converted in place like [`convert_slice`]. Otherwise they are copied into an aligned buffer on the stack in small chunks. You do not need to check the alignment yourself.
+///
+/// # Panics
+///
+/// Panics if `src` does not have as many bytes as `dst` has floats.
+///
+/// ```
+/// let src: Vec<u8> = [1.5f64, -2.5, 3.0].iter().flat_map(|float| float.to_ne_bytes()).collect();
+/// let mut dst = [0i64; 3];
+/// fast_float_to_integer::convert_ne_bytes::<f64, _>(&src, &mut dst);
+/// assert_eq!(dst, [1, -2, 3]);
+/// ```
+#[inline]
+pub fn convert_ne_bytes<Float, Integer>(src: &[u8], dst: &mut [Integer])
+where
+    Float: FloatType,
+    Integer: FastFrom<Float>,
+{
+    // SAFETY: Every bit pattern is a valid float.
+    let (prefix, floats, suffix) = unsafe { src.align_to::<Float>() };
+    if prefix.is_empty() && suffix.is_empty() {
+        assert_eq!(
+            floats.len(),
+            dst.len(),
+            "byte slice does not have the size of the destination slice"
+        );
+        Integer::convert_slice(floats, dst);
+    } else {
+        convert_bytes(src, dst, Float::from_ne_bytes);
+    }
+}
+
+/// Load the floats of `src` in small chunks into a buffer on the stack and convert each chunk into `dst`.
+#[cfg(any(target_has_atomic = "32", target_has_atomic = "64"))]
+#[inline(always)]
+fn convert_atomics<Atomic, Float, Integer>(
+    src: &[Atomic],
+    dst: &mut [Integer],
+    load: impl Fn(&Atomic) -> Float,
+) where
+    Float: FloatType,
+    Integer: FastFrom<Float>,
+{
+    assert_eq!(src.len(), dst.len(), "slices have different lengths");
+    let mut buffer = [Float::ZERO; CHUNK];
+    for (src, dst) in src.chunks(buffer.len()).zip(dst.chunks_mut(buffer.len())) {
+        let buffer = &mut buffer[..dst.len()];
+        for (float, atomic) in buffer.iter_mut().zip(src) {
+            *float = load(atomic);
+        }
+        Integer::convert_slice(buffer, dst);
+    }
+}
+
+/// Like [`convert_slice`] but `src` holds the bits of f32 values in atomics, like a buffer that an audio thread shares with other threads without a lock.
+///
+/// Every element is loaded with [`Ordering::Relaxed`](core::sync::atomic::Ordering::Relaxed) into a buffer on the stack in small chunks, which is converted with the packed kernels. You do not need to copy the whole buffer out first. The loads are not a snapshot of the whole buffer. If another thread stores while the slice is converted, then `dst` can have old values for some elements and new values for others, but every element is the conversion of a whole float. Use a separate flag or sequence counter with a stronger ordering if you need a consistent snapshot.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+///
+/// ```
+/// use core::sync::atomic::AtomicU32;
+///
+/// let src = [1.5f32, -2.5, 3.0].map(|float| AtomicU32::new(float.to_bits()));
+/// let mut dst = [0i16; 3];
+/// fast_float_to_integer::convert_atomic_f32_slice(&src, &mut dst);
+/// assert_eq!(dst, [1, -2, 3]);
+/// ```
+#[cfg(target_has_atomic = "32")]
+#[inline]
+pub fn convert_atomic_f32_slice<Integer>(src: &[AtomicU32], dst: &mut [Integer])
+where
+    Integer: FastFrom<f32>,
+{
+    convert_atomics(src, dst, |atomic| {
+        f32::from_bits(atomic.load(Ordering::Relaxed))
+    });
+}
+
+/// Like [`convert_atomic_f32_slice`] but `src` holds the bits of f64 values.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+///
+/// ```
+/// use core::sync::atomic::AtomicU64;
+///
+/// let src = [1.5f64, -2.5, 3.0].map(|float| AtomicU64::new(float.to_bits()));
+/// let mut dst = [0i32; 3];
+/// fast_float_to_integer::convert_atomic_f64_slice(&src, &mut dst);
+/// assert_eq!(dst, [1, -2, 3]);
+/// ```
+#[cfg(target_has_atomic = "64")]
+#[inline]
+pub fn convert_atomic_f64_slice<Integer>(src: &[AtomicU64], dst: &mut [Integer])
+where
+    Integer: FastFrom<f64>,
+{
+    convert_atomics(src, dst, |atomic| {
+        f64::from_bits(atomic.load(Ordering::Relaxed))
+    });
+}
+
+/// Like [`convert_slice`] but for uninitialized destination elements.
+///
+/// Returns `dst` as an initialized slice. This lets you convert into freshly allocated memory without zeroing it first.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+///
+/// ```
+/// use core::mem::MaybeUninit;
+///
+/// let src = [1.5f32, -2.5, 3.0];
+/// let mut dst = [MaybeUninit::<i32>::uninit(); 3];
+/// let dst = fast_float_to_integer::convert_slice_uninit(&src, &mut dst);
+/// assert_eq!(dst, [1, -2, 3]);
+/// ```
+#[inline]
+pub fn convert_slice_uninit<'dst, Float, Integer>(
+    src: &[Float],
+    dst: &'dst mut [MaybeUninit<Integer>],
+) -> &'dst mut [Integer]
+where
+    Float: Copy,
+    Integer: FastFrom<Float>,
+{
+    assert_eq!(src.len(), dst.len(), "slices have different lengths");
+    Integer::convert_slice_uninit(src, dst);
+    // SAFETY: Every element has been initialized by the conversion because the slices have the same length. MaybeUninit<T> has the same layout as T.
+    unsafe { core::slice::from_raw_parts_mut(dst.as_mut_ptr().cast::<Integer>(), dst.len()) }
+}
+
+/// Evaluating the constant fails at compile time if the types have a different size or alignment.
+struct SameLayout<Float, Integer>(PhantomData<(Float, Integer)>);
+
+impl<Float, Integer> SameLayout<Float, Integer> {
+    const ASSERT: () = assert!(
+        core::mem::size_of::<Float>() == core::mem::size_of::<Integer>()
+            && core::mem::align_of::<Float>() == core::mem::align_of::<Integer>(),
+        "float and integer types have different layouts"
+    );
+}
+
+/// Convert every element of `values` and store the result in the same memory.
+///
+/// Returns the memory of `values` as a slice of the integer type. This avoids a second buffer of the same size, which matters for memory constrained targets and for uploading the results directly.
+///
+/// The integer type must have the same size and alignment as the float type. That is the case for f32 to i32/u32 and f64 to i64/u64. Other combinations are a compile time error.
+///
+/// ```
+/// let mut values = [1.5f32, -2.5, 3.0];
+/// let integers: &mut [i32] = fast_float_to_integer::convert_in_place(&mut values);
+/// assert_eq!(integers, [1, -2, 3]);
+/// ```
+#[inline]
+pub fn convert_in_place<Float, Integer>(values: &mut [Float]) -> &mut [Integer]
+where
+    Float: Copy,
+    Integer: FastFrom<Float>,
+{
+    #[allow(clippy::let_unit_value)]
+    let () = SameLayout::<Float, Integer>::ASSERT;
+    let mut buffer = [Integer::ZERO; CHUNK];
+    for chunk in values.chunks_mut(buffer.len()) {
+        let buffer = &mut buffer[..chunk.len()];
+        Integer::convert_slice(chunk, buffer);
+        // SAFETY: The types have the same layout and the buffer does not overlap the chunk. The chunk keeps the integer bits, which is fine because every bit pattern is a valid float.
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                buffer.as_ptr(),
+                chunk.as_mut_ptr().cast::<Integer>(),
+                chunk.len(),
+            )
+        };
+    }
+    // SAFETY: Every element has been overwritten with an integer and the types have the same layout.
+    unsafe { core::slice::from_raw_parts_mut(values.as_mut_ptr().cast::<Integer>(), values.len()) }
+}
+
+/// Convert consecutive pairs of elements of `src` into the elements of `dst`.
+///
+/// `src[2 * i]` is written to `dst[i].0` and `src[2 * i + 1]` is written to `dst[i].1`. This is useful for packing converted values directly into interleaved structures like stereo samples or 2D coordinates without converting into a temporary buffer first. The elements are converted with the packed kernels in small chunks into a buffer on the stack, which stays in the L1 cache.
+///
+/// # Panics
+///
+/// Panics if `src` is not twice as long as `dst`.
+///
+/// ```
+/// let src = [1.5f32, -2.5, 3.0, 4.0];
+/// let mut dst = [(0i16, 0i16); 2];
+/// fast_float_to_integer::convert_slice_pairs(&src, &mut dst);
+/// assert_eq!(dst, [(1, -2), (3, 4)]);
+/// ```
+#[inline]
+pub fn convert_slice_pairs<Float, Integer>(src: &[Float], dst: &mut [(Integer, Integer)])
+where
+    Float: Copy,
+    Integer: FastFrom<Float>,
+{
+    assert_eq!(
+        src.len(),
+        dst.len() * 2,
+        "source slice is not twice as long as destination slice"
+    );
+    // The chunks of the source have an even length, so they hold whole pairs.
+    let mut buffer = [Integer::ZERO; CHUNK];
+    for (src, dst) in src.chunks(CHUNK).zip(dst.chunks_mut(CHUNK / 2)) {
+        let buffer = &mut buffer[..src.len()];
+        Integer::convert_slice(src, buffer);
+        for (pair, dst) in buffer.chunks_exact(2).zip(dst) {
+            *dst = (pair[0], pair[1]);
+        }
+    }
+}
+
+/// Convert the elements of two slices into pairs.
+///
+/// `first[i]` is written to `dst[i].0` and `second[i]` is written to `dst[i].1`. This is the structure-of-arrays to array-of-structures version of [`convert_slice_pairs`]. Like it, the elements are converted with the packed kernels in small chunks.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+///
+/// ```
+/// let left = [1.5f32, 3.0];
+/// let right = [-2.5f32, 4.0];
+/// let mut dst = [(0i16, 0i16); 2];
+/// fast_float_to_integer::convert_slices_to_pairs(&left, &right, &mut dst);
+/// assert_eq!(dst, [(1, -2), (3, 4)]);
+/// ```
+#[inline]
+pub fn convert_slices_to_pairs<Float, Integer>(
+    first: &[Float],
+    second: &[Float],
+    dst: &mut [(Integer, Integer)],
+) where
+    Float: Copy,
+    Integer: FastFrom<Float>,
+{
+    assert_eq!(first.len(), dst.len(), "slices have different lengths");
+    assert_eq!(second.len(), dst.len(), "slices have different lengths");
+    let mut first_buffer = [Integer::ZERO; CHUNK];
+    let mut second_buffer = [Integer::ZERO; CHUNK];
+    for ((first, second), dst) in first
+        .chunks(CHUNK)
+        .zip(second.chunks(CHUNK))
+        .zip(dst.chunks_mut(CHUNK))
+    {
+        let first_buffer = &mut first_buffer[..first.len()];
+        let second_buffer = &mut second_buffer[..second.len()];
+        Integer::convert_slice(first, first_buffer);
+        Integer::convert_slice(second, second_buffer);
+        for ((first, second), dst) in first_buffer.iter().zip(second_buffer.iter()).zip(dst) {
+            *dst = (*first, *second);
+        }
+    }
+}
+
+/// Convert the elements of `src` in small chunks into a buffer on the stack and pass each chunk together with its offset in `src` to `f`.
+///
+/// This uses the packed kernels without materializing a buffer for the whole slice.
+#[inline(always)]
+fn for_each_converted_chunk<Float, Integer>(src: &[Float], mut f: impl FnMut(usize, &[Integer]))
+where
+    Float: Copy,
+    Integer: FastFrom<Float>,
+{
+    let mut buffer = [Integer::ZERO; CHUNK];
+    for (index, src) in src.chunks(buffer.len()).enumerate() {
+        let buffer = &mut buffer[..src.len()];
+        Integer::convert_slice(src, buffer);
+        f(index * CHUNK, buffer);
+    }
+}
+
+/// Convert the elements of `src` and combine the results with `operation`.
+#[inline(always)]
+fn convert_and_reduce<Float, Integer>(
+    src: &[Float],
+    init: Integer,
+    operation: impl Fn(Integer, Integer) -> Integer,
+) -> Integer
+where
+    Float: Copy,
+    Integer: FastFrom<Float>,
+{
+    let mut result = init;
+    for_each_converted_chunk(src, |_, converted: &[Integer]| {
+        result = converted.iter().copied().fold(result, &operation);
+    });
+    result
+}
+
+/// Like [`convert_slice`] but only writes the elements whose entry in `mask` is `true`.
+///
+/// The other elements of `dst` are left untouched.
+///
+/// Every element is converted with the packed kernels into a buffer on the stack, including the unselected ones. This is harmless because the conversions never have undefined behavior. Then a loop selects for every element between the result and the old value in `dst`. The select is scalar code without a branch, which the compiler can vectorize into blend instructions. There are no masked stores, so every element of `dst` is written.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+///
+/// ```
+/// let src = [1.5f32, f32::NAN, 3.0];
+/// let mask = [true, false, true];
+/// let mut dst = [-1i32; 3];
+/// fast_float_to_integer::convert_slice_select(&src, &mask, &mut dst);
+/// assert_eq!(dst, [1, -1, 3]);
+/// ```
+#[inline]
+pub fn convert_slice_select<Float, Integer>(src: &[Float], mask: &[bool], dst: &mut [Integer])
+where
+    Float: Copy,
+    Integer: FastFrom<Float>,
+{
+    assert_eq!(src.len(), dst.len(), "slices have different lengths");
+    assert_eq!(src.len(), mask.len(), "slices have different lengths");
+    for_each_converted_chunk(src, |offset, converted: &[Integer]| {
+        let dst = &mut dst[offset..][..converted.len()];
+        let mask = &mask[offset..][..converted.len()];
+        for ((dst, converted), selected) in dst.iter_mut().zip(converted).zip(mask) {
+            *dst = if *selected { *converted } else { *dst };
+        }
+    });
+}
+
+/// Like [`convert_slice_select`] but the mask is a bitmap.
+///
+/// Element `i` is selected if bit `i % 8` of `bitmap[i / 8]` is set. This is the least significant bit first layout that columnar formats like Apache Arrow use for validity bitmaps.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths or if `bitmap` is too short to have a bit for every element.
+///
+/// ```
+/// let src = [1.5f32, f32::NAN, 3.0];
+/// let bitmap = [0b101];
+/// let mut dst = [-1i32; 3];
+/// fast_float_to_integer::convert_slice_select_bitmap(&src, &bitmap, &mut dst);
+/// assert_eq!(dst, [1, -1, 3]);
+/// ```
+#[inline]
+pub fn convert_slice_select_bitmap<Float, Integer>(
+    src: &[Float],
+    bitmap: &[u8],
+    dst: &mut [Integer],
+) where
+    Float: Copy,
+    Integer: FastFrom<Float>,
+{
+    assert_eq!(src.len(), dst.len(), "slices have different lengths");
+    assert!(bitmap.len() >= (src.len() + 7) / 8, "bitmap is too short");
+    for_each_converted_chunk(src, |offset, converted: &[Integer]| {
+        let dst = &mut dst[offset..][..converted.len()];
+        // CHUNK is a multiple of 8, so the chunk starts at the beginning of a byte.
+        let bitmap = &bitmap[offset / 8..];
+        for (index, (dst, converted)) in dst.iter_mut().zip(converted).enumerate() {
+            let selected = bitmap[index / 8] & (1 << (index % 8)) != 0;
+            *dst = if selected { *converted } else { *dst };
+        }
+    });
+}
+
+/// Convert the elements of `src` and return the sum of the results.
+///
+/// The elements are converted like [`convert_slice`] and the sum wraps around on overflow. This avoids converting into an intermediate buffer when you only need the sum.
+///
+/// Only the conversions that have a packed kernel in [`convert_slice`], like f32 to i32, convert several elements at once. With i64 and the other output types every element goes through the scalar conversion. To sum i32 conversions without wrapping around use [`convert_and_sum_wide`], which uses the i32 kernel.
+///
+/// ```
+/// let src = [1.5f32, -2.5, 3.0];
+/// let sum: i64 = fast_float_to_integer::convert_and_sum(&src);
+/// assert_eq!(sum, 2);
+/// ```
+#[inline]
+pub fn convert_and_sum<Float, Integer>(src: &[Float]) -> Integer
+where
+    Float: Copy,
+    Integer: FastFrom<Float>,
+{
+    convert_and_reduce(src, Integer::ZERO, Integer::wrapping_add)
+}
+
+/// Convert the elements of `src` to i32 and return the sum of the results as i64.
+///
+/// The elements are converted like [`convert_slice`] with the i32 kernel and every chunk is widened into the sum. Every converted element has a magnitude of at most 2^31, so unlike [`convert_and_sum`] with i32 the sum can only wrap around if `src.len()` × 2^31 > 2^63, that is if `src` has more than 2^32 elements.
+///
+/// ```
+/// let src = [2e9f32, 2e9, -1.5];
+/// assert_eq!(fast_float_to_integer::convert_and_sum_wide(&src), 3_999_999_999);
+/// ```
+#[inline]
+pub fn convert_and_sum_wide<Float>(src: &[Float]) -> i64
+where
+    Float: Copy,
+    i32: FastFrom<Float>,
+{
+    let mut sum = 0i64;
+    for_each_converted_chunk(src, |_, converted: &[i32]| {
+        sum = converted.iter().fold(sum, |sum, converted| {
+            sum.wrapping_add(i64::from(*converted))
+        });
+    });
+    sum
+}
+
+/// Convert the elements of `src` and return the smallest result.
+///
+/// Returns `None` if `src` is empty.
+///
+/// ```
+/// let src = [1.5f32, -2.5, 3.0];
+/// let min: Option<i32> = fast_float_to_integer::convert_and_min(&src);
+/// assert_eq!(min, Some(-2));
+/// ```
+#[inline]
+pub fn convert_and_min<Float, Integer>(src: &[Float]) -> Option<Integer>
+where
+    Float: Copy,
+    Integer: FastFrom<Float>,
+{
+    let (first, rest) = src.split_first()?;
+    Some(convert_and_reduce(
+        rest,
+        Integer::fast_from(*first),
+        Ord::min,
+    ))
+}
+
+/// Convert the elements of `src` and return the largest result.
+///
+/// Returns `None` if `src` is empty.
+///
+/// ```
+/// let src = [1.5f32, -2.5, 3.0];
+/// let max: Option<i32> = fast_float_to_integer::convert_and_max(&src);
+/// assert_eq!(max, Some(3));
+/// ```
+#[inline]
+pub fn convert_and_max<Float, Integer>(src: &[Float]) -> Option<Integer>
+where
+    Float: Copy,
+    Integer: FastFrom<Float>,
+{
+    let (first, rest) = src.split_first()?;
+    Some(convert_and_reduce(
+        rest,
+        Integer::fast_from(*first),
+        Ord::max,
+    ))
+}
@@ -0,0 +1,50 @@
+// Conversions of floats whose magnitude is small enough that adding a magic constant moves the integer into the low bits of the mantissa. The sum is 1.5 * 2^23 (f32) or 1.5 * 2^52 (f64) plus the integer, so its bit pattern minus the bit pattern of the constant is the integer, including for negative inputs. This is one addition and one integer subtraction. It has no branches and no conversion instruction, which helps on cores with a slow or missing conversion instruction. The bit patterns at both ends of the range are exact powers of two, so the subtraction is also correct for inputs of exactly 2^22 or 2^51 in magnitude.
+//
+// The FPU rounds the addition to nearest with ties to even, so the conversion does too. On x86 without SSE the x87 FPU computes the f64 addition with a 64 bit mantissa and rounds it again when storing, which can round a fraction very close to 0.5 in the wrong direction.
+
+use crate::*;
+
+macro_rules! create_function {
+    ($name:ident, $Float:ty, $Integer:ty, $Signed:ty, $exact_pow2:ident, $limit:literal) => {
+        /// Convert the input floating point value to the output integer type with the magic number trick. The magnitude of the input must be small.
+        ///
+        #[doc = concat!("The input must be at most 2^", stringify!($limit), " in magnitude and the rounded value must be in range of the output type. Otherwise, the result is unspecified. Unlike the truncating conversions, this rounds to the nearest integer with ties to even like the `*_round_ties_even` functions. For inputs without a fractional part the result is the same as the one of the standard `as` conversion.")]
+        ///
+        /// This can be faster than the regular conversion on cores with a slow conversion instruction. On x86_64 and aarch64 the regular conversion is faster.
+        ///
+        /// # Panics
+        ///
+        /// With debug assertions, panics if the magnitude of the input is too large or the input is NaN.
+        #[cfg_attr(feature = "show-asm", inline(never))]
+        #[cfg_attr(not(feature = "show-asm"), inline(always))]
+        pub fn $name(float: $Float) -> $Integer {
+            const MAGIC: $Float = 1.5 * $exact_pow2(<$Float>::MANTISSA_DIGITS as i32 - 1);
+            const LIMIT: $Float = $exact_pow2($limit);
+            debug_assert!((-LIMIT..=LIMIT).contains(&float), "the float is not small");
+            let bits = (float + MAGIC).to_bits() as $Signed;
+            bits.wrapping_sub(MAGIC.to_bits() as $Signed) as $Integer
+        }
+    };
+}
+
+create_function! {f32_to_i8_small, f32, i8, i32, exact_pow2_f32, 22}
+create_function! {f32_to_u8_small, f32, u8, i32, exact_pow2_f32, 22}
+create_function! {f32_to_i16_small, f32, i16, i32, exact_pow2_f32, 22}
+create_function! {f32_to_u16_small, f32, u16, i32, exact_pow2_f32, 22}
+create_function! {f32_to_i32_small, f32, i32, i32, exact_pow2_f32, 22}
+create_function! {f32_to_u32_small, f32, u32, i32, exact_pow2_f32, 22}
+create_function! {f32_to_i64_small, f32, i64, i32, exact_pow2_f32, 22}
+create_function! {f32_to_u64_small, f32, u64, i32, exact_pow2_f32, 22}
+create_function! {f32_to_i128_small, f32, i128, i32, exact_pow2_f32, 22}
+create_function! {f32_to_u128_small, f32, u128, i32, exact_pow2_f32, 22}
+
+create_function! {f64_to_i8_small, f64, i8, i64, exact_pow2_f64, 51}
+create_function! {f64_to_u8_small, f64, u8, i64, exact_pow2_f64, 51}
+create_function! {f64_to_i16_small, f64, i16, i64, exact_pow2_f64, 51}
+create_function! {f64_to_u16_small, f64, u16, i64, exact_pow2_f64, 51}
+create_function! {f64_to_i32_small, f64, i32, i64, exact_pow2_f64, 51}
+create_function! {f64_to_u32_small, f64, u32, i64, exact_pow2_f64, 51}
+create_function! {f64_to_i64_small, f64, i64, i64, exact_pow2_f64, 51}
+create_function! {f64_to_u64_small, f64, u64, i64, exact_pow2_f64, 51}
+create_function! {f64_to_i128_small, f64, i128, i64, exact_pow2_f64, 51}
+create_function! {f64_to_u128_small, f64, u128, i64, exact_pow2_f64, 51}
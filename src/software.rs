@@ -0,0 +1,227 @@
+// Algorithms that do not depend on the target. The target modules provide the conversion instructions of their architecture and build the remaining conversions from the algorithms here, so that a new target module only has to write the parts that are specific to it.
+
+/// The standard `as` conversions. Target modules use them for the conversions they cannot make faster, for example the 128 bit conversions, which call into compiler_builtins on every target.
+pub mod standard {
+    // Every target module uses a different subset.
+    #![allow(dead_code)]
+
+    macro_rules! create_function {
+        ($name:ident, $Float:ty, $Integer:ty) => {
+            #[inline(always)]
+            pub fn $name(float: $Float) -> $Integer {
+                float as _
+            }
+        };
+    }
+
+    create_function! {f32_to_i8, f32, i8}
+    create_function! {f32_to_u8, f32, u8}
+    create_function! {f32_to_i16, f32, i16}
+    create_function! {f32_to_u16, f32, u16}
+    create_function! {f32_to_i32, f32, i32}
+    create_function! {f32_to_u32, f32, u32}
+    create_function! {f32_to_i64, f32, i64}
+    create_function! {f32_to_u64, f32, u64}
+    create_function! {f32_to_i128, f32, i128}
+    create_function! {f32_to_u128, f32, u128}
+
+    create_function! {f64_to_i8, f64, i8}
+    create_function! {f64_to_u8, f64, u8}
+    create_function! {f64_to_i16, f64, i16}
+    create_function! {f64_to_u16, f64, u16}
+    create_function! {f64_to_i32, f64, i32}
+    create_function! {f64_to_u32, f64, u32}
+    create_function! {f64_to_i64, f64, i64}
+    create_function! {f64_to_u64, f64, u64}
+    create_function! {f64_to_i128, f64, i128}
+    create_function! {f64_to_u128, f64, u128}
+}
+
+// Conversion instructions often only produce signed integers. This is a problem for floats that exceed the maximum of the signed type. We cannot handle this with one instruction, but we can still do better than the as operator.
+
+// This approach branches into a special case if the input is too large. The branchless approach below is faster and is the one we use. We keep this code around for documentation purposes.
+#[allow(dead_code)]
+#[inline(always)]
+fn f32_to_u64_branchful(float: f32, f32_to_i64: impl Fn(f32) -> i64) -> u64 {
+    const THRESHOLD_FLOAT: f32 = crate::exact_pow2_f32(63);
+    const THRESHOLD_INTEGER: u64 = 2u64.pow(63);
+
+    let in_range = float <= THRESHOLD_FLOAT;
+    if in_range {
+        f32_to_i64(float) as u64
+    } else {
+        // Subtract the threshold from the float. The result is >= 0 because the input is larger than the subtrahend. The result is <= i64::MAX because `u64::MAX - i64::MAX == i64::MAX`.
+        let in_range_float = float - THRESHOLD_FLOAT;
+        let integer = f32_to_i64(in_range_float) as u64;
+        // Overflow is benign because it can only occur for invalid inputs.
+        integer.overflowing_add(THRESHOLD_INTEGER).0
+    }
+}
+
+// This approach avoids the branch. It is faster than the branchful approach.
+macro_rules! create_unsigned_via_signed {
+    ($name:ident, $with_threshold:ident, $Float:ty, $Signed:ty, $Unsigned:ty) => {
+        #[doc = concat!("Convert ", stringify!($Float), " to ", stringify!($Unsigned), " with a conversion to ", stringify!($Signed), " that returns ", stringify!($Signed), "::MIN for out of range inputs.")]
+        // Only the target modules without an unsigned conversion instruction use this.
+        #[allow(dead_code)]
+        #[inline(always)]
+        pub fn $name(float: $Float, to_signed: impl Fn($Float) -> $Signed) -> $Unsigned {
+            const THRESHOLD: $Float = (1u128 << (<$Signed>::BITS - 1)) as $Float;
+
+            $with_threshold(float, THRESHOLD, to_signed)
+        }
+
+        #[doc = concat!("Like [`", stringify!($name), "`] with the threshold 2^", stringify!($Signed), "::BITS - 1 as an argument. Target modules can pass a threshold that they build in a register instead of a constant that the compiler loads from memory.")]
+        #[allow(dead_code)]
+        #[inline(always)]
+        pub fn $with_threshold(float: $Float, threshold: $Float, to_signed: impl Fn($Float) -> $Signed) -> $Unsigned {
+            const SHIFT: u32 = <$Signed>::BITS - 1;
+            debug_assert_eq!(threshold, (1u128 << SHIFT) as $Float);
+
+            let integer1 = to_signed(float);
+            let integer2 = to_signed(float - threshold);
+            // If the input is larger than the signed maximum, then integer1 is the signed minimum. This value has 1 as the leftmost bit and 0 as the remaining bits. Right shift on signed values is arithmetic, not logical [1]. We end up with all 0 (in range) or all 1 (out of range).
+            let too_large = integer1 >> SHIFT;
+            // # If the input is not too large:
+            //
+            // Integer1 has the correct value. The mask is all 0, which makes the Or result in integer1.
+            //
+            // # If the input is too large:
+            //
+            // Integer1 is the signed minimum and the mask is all 1. The Or results in `MIN | integer2`. integer2 has the correct result minus the threshold. This is the correct result without the leftmost bit. The Or adds the missing leftmost bit back.
+            (integer1 | (integer2 & too_large)) as $Unsigned
+
+            // [1] https://doc.rust-lang.org/reference/expressions/operator-expr.html#arithmetic-and-logical-binary-operators
+        }
+    };
+}
+
+create_unsigned_via_signed! {f32_to_u32_via_i32, f32_to_u32_via_i32_with_threshold, f32, i32, u32}
+create_unsigned_via_signed! {f32_to_u64_via_i64, f32_to_u64_via_i64_with_threshold, f32, i64, u64}
+create_unsigned_via_signed! {f64_to_u32_via_i32, f64_to_u32_via_i32_with_threshold, f64, i32, u32}
+create_unsigned_via_signed! {f64_to_u64_via_i64, f64_to_u64_via_i64_with_threshold, f64, i64, u64}
+
+/// Conversions from the IEEE 754 bit pattern with integer arithmetic. They do not need an FPU.
+pub mod soft_float {
+    macro_rules! create_function {
+        ($name:ident, $Float:ty, $Bits:ty, $Integer:ty, $Wide:ty) => {
+            /// If the float is out of range of the output type, then the result is unspecified.
+            #[inline(always)]
+            pub fn $name(bits: $Bits) -> $Integer {
+                const MANTISSA_BITS: u32 = <$Float>::MANTISSA_DIGITS - 1;
+                const EXPONENT_MASK: $Bits = (1 << (<$Bits>::BITS - 1 - MANTISSA_BITS)) - 1;
+                const EXPONENT_BIAS: i32 = <$Float>::MAX_EXP - 1;
+                const MANTISSA_MASK: $Bits = (1 << MANTISSA_BITS) - 1;
+
+                let exponent = ((bits >> MANTISSA_BITS) & EXPONENT_MASK) as i32 - EXPONENT_BIAS;
+                if exponent < 0 {
+                    // The magnitude is less than 1, including 0 and subnormal floats.
+                    return 0;
+                }
+                let exponent = exponent as u32;
+                // Add the implicit leading bit. The value is mantissa * 2^(exponent - MANTISSA_BITS).
+                let mantissa = ((bits & MANTISSA_MASK) | (MANTISSA_MASK + 1)) as $Wide;
+                // The wrapping shift cannot panic. It only wraps for out of range inputs, whose result is unspecified.
+                let magnitude = if exponent >= MANTISSA_BITS {
+                    mantissa.wrapping_shl(exponent - MANTISSA_BITS)
+                } else {
+                    mantissa >> (MANTISSA_BITS - exponent)
+                };
+                let negative = bits >> (<$Bits>::BITS - 1) != 0;
+                // Negating in the wide unsigned type and truncating is the two's complement of the magnitude.
+                let integer = if negative {
+                    magnitude.wrapping_neg()
+                } else {
+                    magnitude
+                };
+                integer as $Integer
+            }
+        };
+    }
+
+    // The wide type holds the magnitude of every in range value. It is at least as wide as the mantissa and as the output type.
+    create_function! {f32_bits_to_i8, f32, u32, i8, u32}
+    create_function! {f32_bits_to_u8, f32, u32, u8, u32}
+    create_function! {f32_bits_to_i16, f32, u32, i16, u32}
+    create_function! {f32_bits_to_u16, f32, u32, u16, u32}
+    create_function! {f32_bits_to_i32, f32, u32, i32, u32}
+    create_function! {f32_bits_to_u32, f32, u32, u32, u32}
+    create_function! {f32_bits_to_i64, f32, u32, i64, u64}
+    create_function! {f32_bits_to_u64, f32, u32, u64, u64}
+    create_function! {f32_bits_to_i128, f32, u32, i128, u128}
+    create_function! {f32_bits_to_u128, f32, u32, u128, u128}
+
+    create_function! {f64_bits_to_i8, f64, u64, i8, u64}
+    create_function! {f64_bits_to_u8, f64, u64, u8, u64}
+    create_function! {f64_bits_to_i16, f64, u64, i16, u64}
+    create_function! {f64_bits_to_u16, f64, u64, u16, u64}
+    create_function! {f64_bits_to_i32, f64, u64, i32, u64}
+    create_function! {f64_bits_to_u32, f64, u64, u32, u64}
+    create_function! {f64_bits_to_i64, f64, u64, i64, u64}
+    create_function! {f64_bits_to_u64, f64, u64, u64, u64}
+    create_function! {f64_bits_to_i128, f64, u64, i128, u128}
+    create_function! {f64_bits_to_u128, f64, u64, u128, u128}
+}
+
+/// Conversions that round instead of truncating. They correct the truncating conversion of the active target, which is exact for in range inputs, so they work on every target. Target modules with conversion instructions that round replace them in their own `rounding` module.
+pub mod rounding {
+    // Unused on targets whose rounding module replaces every conversion.
+    #![allow(dead_code)]
+
+    use crate::sealed::Float;
+
+    // The public conversion functions are not inlined with the show-asm feature, so these call the implementation of the active target directly.
+    macro_rules! create_function {
+        ($convert:ident, $floor:ident, $ceil:ident, $round:ident, $round_ties_even:ident, $Float:ty, $Integer:ty) => {
+            #[inline(always)]
+            pub fn $floor(float: $Float) -> $Integer {
+                // The truncated value is exactly representable, so the comparison is exact. Wrapping only happens for out of range results.
+                let truncated = crate::active_target::implementation::$convert(float);
+                truncated.wrapping_sub((truncated as $Float > float) as $Integer)
+            }
+
+            #[inline(always)]
+            pub fn $ceil(float: $Float) -> $Integer {
+                let truncated = crate::active_target::implementation::$convert(float);
+                truncated.wrapping_add(((truncated as $Float) < float) as $Integer)
+            }
+
+            #[inline(always)]
+            pub fn $round(float: $Float) -> $Integer {
+                // The difference to the truncated value is exact because both have the same sign and the truncated value has no more significant bits than the input.
+                let truncated = crate::active_target::implementation::$convert(float);
+                let fraction = float - truncated as $Float;
+                truncated
+                    .wrapping_add((fraction >= 0.5) as $Integer)
+                    .wrapping_sub((fraction <= -0.5) as $Integer)
+            }
+
+            #[inline(always)]
+            pub fn $round_ties_even(float: $Float) -> $Integer {
+                crate::active_target::implementation::$convert(Float::round_ties_even(float))
+            }
+        };
+    }
+
+    create_function! {f32_to_i8, f32_to_i8_floor, f32_to_i8_ceil, f32_to_i8_round, f32_to_i8_round_ties_even, f32, i8}
+    create_function! {f32_to_u8, f32_to_u8_floor, f32_to_u8_ceil, f32_to_u8_round, f32_to_u8_round_ties_even, f32, u8}
+    create_function! {f32_to_i16, f32_to_i16_floor, f32_to_i16_ceil, f32_to_i16_round, f32_to_i16_round_ties_even, f32, i16}
+    create_function! {f32_to_u16, f32_to_u16_floor, f32_to_u16_ceil, f32_to_u16_round, f32_to_u16_round_ties_even, f32, u16}
+    create_function! {f32_to_i32, f32_to_i32_floor, f32_to_i32_ceil, f32_to_i32_round, f32_to_i32_round_ties_even, f32, i32}
+    create_function! {f32_to_u32, f32_to_u32_floor, f32_to_u32_ceil, f32_to_u32_round, f32_to_u32_round_ties_even, f32, u32}
+    create_function! {f32_to_i64, f32_to_i64_floor, f32_to_i64_ceil, f32_to_i64_round, f32_to_i64_round_ties_even, f32, i64}
+    create_function! {f32_to_u64, f32_to_u64_floor, f32_to_u64_ceil, f32_to_u64_round, f32_to_u64_round_ties_even, f32, u64}
+    create_function! {f32_to_i128, f32_to_i128_floor, f32_to_i128_ceil, f32_to_i128_round, f32_to_i128_round_ties_even, f32, i128}
+    create_function! {f32_to_u128, f32_to_u128_floor, f32_to_u128_ceil, f32_to_u128_round, f32_to_u128_round_ties_even, f32, u128}
+
+    create_function! {f64_to_i8, f64_to_i8_floor, f64_to_i8_ceil, f64_to_i8_round, f64_to_i8_round_ties_even, f64, i8}
+    create_function! {f64_to_u8, f64_to_u8_floor, f64_to_u8_ceil, f64_to_u8_round, f64_to_u8_round_ties_even, f64, u8}
+    create_function! {f64_to_i16, f64_to_i16_floor, f64_to_i16_ceil, f64_to_i16_round, f64_to_i16_round_ties_even, f64, i16}
+    create_function! {f64_to_u16, f64_to_u16_floor, f64_to_u16_ceil, f64_to_u16_round, f64_to_u16_round_ties_even, f64, u16}
+    create_function! {f64_to_i32, f64_to_i32_floor, f64_to_i32_ceil, f64_to_i32_round, f64_to_i32_round_ties_even, f64, i32}
+    create_function! {f64_to_u32, f64_to_u32_floor, f64_to_u32_ceil, f64_to_u32_round, f64_to_u32_round_ties_even, f64, u32}
+    create_function! {f64_to_i64, f64_to_i64_floor, f64_to_i64_ceil, f64_to_i64_round, f64_to_i64_round_ties_even, f64, i64}
+    create_function! {f64_to_u64, f64_to_u64_floor, f64_to_u64_ceil, f64_to_u64_round, f64_to_u64_round_ties_even, f64, u64}
+    create_function! {f64_to_i128, f64_to_i128_floor, f64_to_i128_ceil, f64_to_i128_round, f64_to_i128_round_ties_even, f64, i128}
+    create_function! {f64_to_u128, f64_to_u128_floor, f64_to_u128_ceil, f64_to_u128_round, f64_to_u128_round_ties_even, f64, u128}
+}
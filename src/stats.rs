@@ -0,0 +1,62 @@
+// Conversions that collect statistics about the data. Data quality monitoring needs these anyway and a second pass over data that is no longer in the cache is the expensive part.
+
+use crate::FastFrom;
+
+/// Statistics about converted data. Returned by [`convert_slice_with_stats`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ConversionStats<Integer> {
+    /// The smallest result of the values that are in range. `None` if no value is in range.
+    pub min: Option<Integer>,
+    /// The largest result of the values that are in range. `None` if no value is in range.
+    pub max: Option<Integer>,
+    /// The number of values that are out of range, including NaN. Their results are unspecified.
+    pub out_of_range: usize,
+}
+
+/// Like [`convert_slice`](crate::convert_slice) but also collect [`ConversionStats`].
+///
+/// The elements are converted in small chunks with the packed kernels. The statistics of a chunk are collected while it is still in the cache, so the data is read from memory only once.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+///
+/// ```
+/// let src = [1.5f32, -2.5, 3e9, f32::NAN];
+/// let mut dst = [0i32; 4];
+/// let stats = fast_float_to_integer::convert_slice_with_stats(&src, &mut dst);
+/// assert_eq!(&dst[..2], [1, -2]);
+/// assert_eq!(stats.min, Some(-2));
+/// assert_eq!(stats.max, Some(1));
+/// assert_eq!(stats.out_of_range, 2);
+/// ```
+#[inline]
+pub fn convert_slice_with_stats<Float, Integer>(
+    src: &[Float],
+    dst: &mut [Integer],
+) -> ConversionStats<Integer>
+where
+    Float: Copy,
+    Integer: FastFrom<Float>,
+{
+    assert_eq!(src.len(), dst.len(), "slices have different lengths");
+    let mut stats = ConversionStats {
+        min: None,
+        max: None,
+        out_of_range: 0,
+    };
+    for (src, dst) in src.chunks(64).zip(dst.chunks_mut(64)) {
+        Integer::convert_slice(src, dst);
+        let in_range = || {
+            src.iter()
+                .zip(dst.iter())
+                .filter(|(float, _)| Integer::in_range(**float))
+                .map(|(_, integer)| *integer)
+        };
+        stats.out_of_range += src.len() - in_range().count();
+        stats.min = stats.min.into_iter().chain(in_range().min()).min();
+        stats.max = stats.max.into_iter().chain(in_range().max()).max();
+    }
+    stats
+}
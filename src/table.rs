@@ -0,0 +1,127 @@
+// Selection of a conversion at runtime. Interpreters, JITs and plugin systems know the types only as values.
+
+use core::ops::Index;
+
+use crate::Conversion;
+
+/// The input type of a conversion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FloatKind {
+    F32,
+    F64,
+}
+
+/// The output type of a conversion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IntegerKind {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    I64,
+    U64,
+    I128,
+    U128,
+}
+
+impl Conversion {
+    /// The conversion from `from` to `to`.
+    ///
+    /// ```
+    /// use fast_float_to_integer::{Conversion, FloatKind, IntegerKind};
+    ///
+    /// let conversion = Conversion::new(FloatKind::F64, IntegerKind::U16);
+    /// assert_eq!(conversion, Conversion::F64ToU16);
+    /// assert_eq!(conversion.from(), FloatKind::F64);
+    /// assert_eq!(conversion.to(), IntegerKind::U16);
+    /// ```
+    pub const fn new(from: FloatKind, to: IntegerKind) -> Conversion {
+        // ALL is ordered by input type and then by output type like the enums.
+        Conversion::ALL[from as usize * 10 + to as usize]
+    }
+
+    /// The input type.
+    pub const fn from(self) -> FloatKind {
+        [FloatKind::F32, FloatKind::F64][self as usize / 10]
+    }
+
+    /// The output type.
+    pub const fn to(self) -> IntegerKind {
+        [
+            IntegerKind::I8,
+            IntegerKind::U8,
+            IntegerKind::I16,
+            IntegerKind::U16,
+            IntegerKind::I32,
+            IntegerKind::U32,
+            IntegerKind::I64,
+            IntegerKind::U64,
+            IntegerKind::I128,
+            IntegerKind::U128,
+        ][self as usize % 10]
+    }
+}
+
+/// A conversion with the same signature for every input and output type.
+///
+/// The input is the bit pattern of the float. An f32 is in the lower 32 bits and the upper bits are ignored. The output is the result converted to u128 with the `as` operator. Converting it back to the output type with `as` gives the result. The conversion behaves like the `*_bits_to_*` functions like [`f32_bits_to_i8`](crate::f32_bits_to_i8).
+pub type ErasedConversion = fn(u64) -> u128;
+
+/// The conversion functions indexed by [`Conversion`]. See [`CONVERSIONS`].
+#[derive(Debug)]
+pub struct ConversionTable {
+    functions: [ErasedConversion; 20],
+}
+
+impl ConversionTable {
+    /// The function of the conversion.
+    pub const fn get(&self, conversion: Conversion) -> ErasedConversion {
+        self.functions[conversion as usize]
+    }
+}
+
+impl Index<Conversion> for ConversionTable {
+    type Output = ErasedConversion;
+
+    fn index(&self, conversion: Conversion) -> &ErasedConversion {
+        &self.functions[conversion as usize]
+    }
+}
+
+/// Every conversion as an [`ErasedConversion`].
+///
+/// This lets you select a conversion at runtime from the input and output types, for example in an interpreter, without matching on every combination.
+///
+/// ```
+/// use fast_float_to_integer::{Conversion, FloatKind, IntegerKind, CONVERSIONS};
+///
+/// let convert = CONVERSIONS[Conversion::new(FloatKind::F32, IntegerKind::I8)];
+/// let result = convert((-2.5f32).to_bits().into());
+/// assert_eq!(result as i8, -2);
+/// ```
+pub static CONVERSIONS: ConversionTable = ConversionTable {
+    functions: [
+        |bits| crate::f32_bits_to_i8(bits as u32) as u128,
+        |bits| crate::f32_bits_to_u8(bits as u32) as u128,
+        |bits| crate::f32_bits_to_i16(bits as u32) as u128,
+        |bits| crate::f32_bits_to_u16(bits as u32) as u128,
+        |bits| crate::f32_bits_to_i32(bits as u32) as u128,
+        |bits| crate::f32_bits_to_u32(bits as u32) as u128,
+        |bits| crate::f32_bits_to_i64(bits as u32) as u128,
+        |bits| crate::f32_bits_to_u64(bits as u32) as u128,
+        |bits| crate::f32_bits_to_i128(bits as u32) as u128,
+        |bits| crate::f32_bits_to_u128(bits as u32),
+        |bits| crate::f64_bits_to_i8(bits) as u128,
+        |bits| crate::f64_bits_to_u8(bits) as u128,
+        |bits| crate::f64_bits_to_i16(bits) as u128,
+        |bits| crate::f64_bits_to_u16(bits) as u128,
+        |bits| crate::f64_bits_to_i32(bits) as u128,
+        |bits| crate::f64_bits_to_u32(bits) as u128,
+        |bits| crate::f64_bits_to_i64(bits) as u128,
+        |bits| crate::f64_bits_to_u64(bits) as u128,
+        |bits| crate::f64_bits_to_i128(bits) as u128,
+        crate::f64_bits_to_u128,
+    ],
+};
@@ -0,0 +1,428 @@
+// aarch64 has the FCVTZS and FCVTZU instructions, which convert f32 and f64 to 32 and 64 bit integers. They saturate out of range inputs and return 0 for NaN, which is the semantics of the standard `as` operator. The compiler emits a single instruction for `as`, so the scalar conversions do not need intrinsics or inline assembly. The standard library implements the scalar intrinsics like `vcvts_s32_f32` with `as` too. Unlike inline assembly, `as` lets the compiler fold constants and vectorize loops.
+//
+// FCVTZS needs the floating point registers, which the soft float targets like aarch64-unknown-none-softfloat disable together with the `neon` target feature.
+
+// The default target module already converts with these instructions on aarch64, also to 8 and 16 bit integers through i32. This module reuses its scalar conversions and only adds the rounding conversions and the packed kernels.
+pub use crate::target_default::{behavior, implementation};
+
+// aarch64 has conversion instructions for every rounding direction: FCVTMS and FCVTMU round toward negative infinity, FCVTPS and FCVTPU toward positive infinity, FCVTAS and FCVTAU to nearest with ties away from zero and FCVTNS and FCVTNU to nearest with ties to even. The compiler does not fuse a separate rounding and conversion into them, so we use them with inline assembly. See `generated assembly/aarch64_neon/rounding`.
+// The portable-deterministic feature does not use the instructions, see crate::rounding.
+#[cfg(not(any(feature = "force-default", feature = "portable-deterministic", kani)))]
+pub mod rounding {
+    use core::arch::asm;
+
+    // Like the other conversions, the 8 and 16 bit outputs are converted to 32 bits and truncated.
+    macro_rules! create_function {
+        ($name:ident, $Float:ty, $Integer:ty, $Register:ty, $instruction:literal) => {
+            #[inline(always)]
+            pub fn $name(float: $Float) -> $Integer {
+                let integer: $Register;
+                // SAFETY: The instruction only reads and writes the given registers.
+                unsafe {
+                    asm!($instruction, out(reg) integer, in(vreg) float, options(pure, nomem, nostack, preserves_flags));
+                }
+                integer as $Integer
+            }
+        };
+    }
+
+    create_function! {f32_to_i8_floor, f32, i8, i32, "fcvtms {0:w}, {1:s}"}
+    create_function! {f32_to_i8_ceil, f32, i8, i32, "fcvtps {0:w}, {1:s}"}
+    create_function! {f32_to_i8_round, f32, i8, i32, "fcvtas {0:w}, {1:s}"}
+    create_function! {f32_to_i8_round_ties_even, f32, i8, i32, "fcvtns {0:w}, {1:s}"}
+    create_function! {f32_to_u8_floor, f32, u8, u32, "fcvtmu {0:w}, {1:s}"}
+    create_function! {f32_to_u8_ceil, f32, u8, u32, "fcvtpu {0:w}, {1:s}"}
+    create_function! {f32_to_u8_round, f32, u8, u32, "fcvtau {0:w}, {1:s}"}
+    create_function! {f32_to_u8_round_ties_even, f32, u8, u32, "fcvtnu {0:w}, {1:s}"}
+    create_function! {f32_to_i16_floor, f32, i16, i32, "fcvtms {0:w}, {1:s}"}
+    create_function! {f32_to_i16_ceil, f32, i16, i32, "fcvtps {0:w}, {1:s}"}
+    create_function! {f32_to_i16_round, f32, i16, i32, "fcvtas {0:w}, {1:s}"}
+    create_function! {f32_to_i16_round_ties_even, f32, i16, i32, "fcvtns {0:w}, {1:s}"}
+    create_function! {f32_to_u16_floor, f32, u16, u32, "fcvtmu {0:w}, {1:s}"}
+    create_function! {f32_to_u16_ceil, f32, u16, u32, "fcvtpu {0:w}, {1:s}"}
+    create_function! {f32_to_u16_round, f32, u16, u32, "fcvtau {0:w}, {1:s}"}
+    create_function! {f32_to_u16_round_ties_even, f32, u16, u32, "fcvtnu {0:w}, {1:s}"}
+    create_function! {f32_to_i32_floor, f32, i32, i32, "fcvtms {0:w}, {1:s}"}
+    create_function! {f32_to_i32_ceil, f32, i32, i32, "fcvtps {0:w}, {1:s}"}
+    create_function! {f32_to_i32_round, f32, i32, i32, "fcvtas {0:w}, {1:s}"}
+    create_function! {f32_to_i32_round_ties_even, f32, i32, i32, "fcvtns {0:w}, {1:s}"}
+    create_function! {f32_to_u32_floor, f32, u32, u32, "fcvtmu {0:w}, {1:s}"}
+    create_function! {f32_to_u32_ceil, f32, u32, u32, "fcvtpu {0:w}, {1:s}"}
+    create_function! {f32_to_u32_round, f32, u32, u32, "fcvtau {0:w}, {1:s}"}
+    create_function! {f32_to_u32_round_ties_even, f32, u32, u32, "fcvtnu {0:w}, {1:s}"}
+    create_function! {f32_to_i64_floor, f32, i64, i64, "fcvtms {0:x}, {1:s}"}
+    create_function! {f32_to_i64_ceil, f32, i64, i64, "fcvtps {0:x}, {1:s}"}
+    create_function! {f32_to_i64_round, f32, i64, i64, "fcvtas {0:x}, {1:s}"}
+    create_function! {f32_to_i64_round_ties_even, f32, i64, i64, "fcvtns {0:x}, {1:s}"}
+    create_function! {f32_to_u64_floor, f32, u64, u64, "fcvtmu {0:x}, {1:s}"}
+    create_function! {f32_to_u64_ceil, f32, u64, u64, "fcvtpu {0:x}, {1:s}"}
+    create_function! {f32_to_u64_round, f32, u64, u64, "fcvtau {0:x}, {1:s}"}
+    create_function! {f32_to_u64_round_ties_even, f32, u64, u64, "fcvtnu {0:x}, {1:s}"}
+
+    create_function! {f64_to_i8_floor, f64, i8, i32, "fcvtms {0:w}, {1:d}"}
+    create_function! {f64_to_i8_ceil, f64, i8, i32, "fcvtps {0:w}, {1:d}"}
+    create_function! {f64_to_i8_round, f64, i8, i32, "fcvtas {0:w}, {1:d}"}
+    create_function! {f64_to_i8_round_ties_even, f64, i8, i32, "fcvtns {0:w}, {1:d}"}
+    create_function! {f64_to_u8_floor, f64, u8, u32, "fcvtmu {0:w}, {1:d}"}
+    create_function! {f64_to_u8_ceil, f64, u8, u32, "fcvtpu {0:w}, {1:d}"}
+    create_function! {f64_to_u8_round, f64, u8, u32, "fcvtau {0:w}, {1:d}"}
+    create_function! {f64_to_u8_round_ties_even, f64, u8, u32, "fcvtnu {0:w}, {1:d}"}
+    create_function! {f64_to_i16_floor, f64, i16, i32, "fcvtms {0:w}, {1:d}"}
+    create_function! {f64_to_i16_ceil, f64, i16, i32, "fcvtps {0:w}, {1:d}"}
+    create_function! {f64_to_i16_round, f64, i16, i32, "fcvtas {0:w}, {1:d}"}
+    create_function! {f64_to_i16_round_ties_even, f64, i16, i32, "fcvtns {0:w}, {1:d}"}
+    create_function! {f64_to_u16_floor, f64, u16, u32, "fcvtmu {0:w}, {1:d}"}
+    create_function! {f64_to_u16_ceil, f64, u16, u32, "fcvtpu {0:w}, {1:d}"}
+    create_function! {f64_to_u16_round, f64, u16, u32, "fcvtau {0:w}, {1:d}"}
+    create_function! {f64_to_u16_round_ties_even, f64, u16, u32, "fcvtnu {0:w}, {1:d}"}
+    create_function! {f64_to_i32_floor, f64, i32, i32, "fcvtms {0:w}, {1:d}"}
+    create_function! {f64_to_i32_ceil, f64, i32, i32, "fcvtps {0:w}, {1:d}"}
+    create_function! {f64_to_i32_round, f64, i32, i32, "fcvtas {0:w}, {1:d}"}
+    create_function! {f64_to_i32_round_ties_even, f64, i32, i32, "fcvtns {0:w}, {1:d}"}
+    create_function! {f64_to_u32_floor, f64, u32, u32, "fcvtmu {0:w}, {1:d}"}
+    create_function! {f64_to_u32_ceil, f64, u32, u32, "fcvtpu {0:w}, {1:d}"}
+    create_function! {f64_to_u32_round, f64, u32, u32, "fcvtau {0:w}, {1:d}"}
+    create_function! {f64_to_u32_round_ties_even, f64, u32, u32, "fcvtnu {0:w}, {1:d}"}
+    create_function! {f64_to_i64_floor, f64, i64, i64, "fcvtms {0:x}, {1:d}"}
+    create_function! {f64_to_i64_ceil, f64, i64, i64, "fcvtps {0:x}, {1:d}"}
+    create_function! {f64_to_i64_round, f64, i64, i64, "fcvtas {0:x}, {1:d}"}
+    create_function! {f64_to_i64_round_ties_even, f64, i64, i64, "fcvtns {0:x}, {1:d}"}
+    create_function! {f64_to_u64_floor, f64, u64, u64, "fcvtmu {0:x}, {1:d}"}
+    create_function! {f64_to_u64_ceil, f64, u64, u64, "fcvtpu {0:x}, {1:d}"}
+    create_function! {f64_to_u64_round, f64, u64, u64, "fcvtau {0:x}, {1:d}"}
+    create_function! {f64_to_u64_round_ties_even, f64, u64, u64, "fcvtnu {0:x}, {1:d}"}
+
+    // There are no instructions for 128 bit integers.
+    pub use crate::software::rounding::{
+        f32_to_i128_ceil, f32_to_i128_floor, f32_to_i128_round, f32_to_i128_round_ties_even,
+        f32_to_u128_ceil, f32_to_u128_floor, f32_to_u128_round, f32_to_u128_round_ties_even,
+        f64_to_i128_ceil, f64_to_i128_floor, f64_to_i128_round, f64_to_i128_round_ties_even,
+        f64_to_u128_ceil, f64_to_u128_floor, f64_to_u128_round, f64_to_u128_round_ties_even,
+    };
+}
+
+// Packed conversions. FCVTZS and FCVTZU saturate in every lane like the scalar instructions, so the packed results are the same as the scalar results for every input. The narrowing to 8 and 16 bits truncates like the scalar conversions through i32, also in the stereo kernel. The saturating conversions narrow with unsigned saturation instead. The `simulate-unspecified` feature only changes the scalar conversions.
+pub mod kernel {
+    use core::arch::aarch64::*;
+
+    use crate::kernel::{Kernel, StereoKernel, TransposeKernel};
+
+    #[inline(always)]
+    fn load_f32(floats: &[f32]) -> float32x4_t {
+        unsafe { vld1q_f32(floats.as_ptr()) }
+    }
+
+    /// Convert four f32 to i32 with the FCVTZS instruction.
+    pub struct F32ToI32;
+
+    impl Kernel<4> for F32ToI32 {
+        type Float = f32;
+        type Integer = i32;
+
+        #[inline(always)]
+        fn convert(floats: [f32; 4]) -> [i32; 4] {
+            let mut integers = [0i32; 4];
+            unsafe {
+                vst1q_s32(
+                    integers.as_mut_ptr(),
+                    crate::float32x4_to_int32x4(load_f32(&floats)),
+                )
+            };
+            integers
+        }
+
+        #[inline(always)]
+        fn convert_saturating(floats: [f32; 4]) -> [i32; 4] {
+            Self::convert(floats)
+        }
+
+        // FCVTNS rounds to nearest with ties to even in the same instruction.
+        #[inline(always)]
+        fn convert_round_ties_even(floats: [f32; 4]) -> [i32; 4] {
+            let mut integers = [0i32; 4];
+            unsafe { vst1q_s32(integers.as_mut_ptr(), vcvtnq_s32_f32(load_f32(&floats))) };
+            integers
+        }
+    }
+
+    /// Convert two f64 to i32 with the FCVTZS instruction to i64 and the SQXTN instruction, which narrows with signed saturation like the scalar conversion.
+    pub struct F64ToI32;
+
+    impl F64ToI32 {
+        #[inline(always)]
+        fn narrow(integers_register: int64x2_t) -> [i32; 2] {
+            let mut integers = [0i32; 2];
+            unsafe { vst1_s32(integers.as_mut_ptr(), vqmovn_s64(integers_register)) };
+            integers
+        }
+    }
+
+    impl Kernel<2> for F64ToI32 {
+        type Float = f64;
+        type Integer = i32;
+
+        #[inline(always)]
+        fn convert(floats: [f64; 2]) -> [i32; 2] {
+            let floats_register = unsafe { vld1q_f64(floats.as_ptr()) };
+            Self::narrow(crate::float64x2_to_int64x2(floats_register))
+        }
+
+        #[inline(always)]
+        fn convert_saturating(floats: [f64; 2]) -> [i32; 2] {
+            Self::convert(floats)
+        }
+
+        #[inline(always)]
+        fn convert_round_ties_even(floats: [f64; 2]) -> [i32; 2] {
+            let floats_register = unsafe { vld1q_f64(floats.as_ptr()) };
+            Self::narrow(unsafe { vcvtnq_s64_f64(floats_register) })
+        }
+    }
+
+    /// Convert sixteen f32 to i32 with the FCVTZS instruction, or FCVTNS for `round`, and narrow them to i16 with the XTN instruction.
+    #[inline(always)]
+    fn f32x16_to_i16x16(floats: &[f32; 16], round: bool) -> [int16x8_t; 2] {
+        let convert = |index: usize| {
+            let floats_register = load_f32(&floats[index..index + 4]);
+            let integers = if round {
+                unsafe { vcvtnq_s32_f32(floats_register) }
+            } else {
+                crate::float32x4_to_int32x4(floats_register)
+            };
+            unsafe { vmovn_s32(integers) }
+        };
+        unsafe {
+            [
+                vcombine_s16(convert(0), convert(4)),
+                vcombine_s16(convert(8), convert(12)),
+            ]
+        }
+    }
+
+    /// Convert sixteen f32 to u32 with the FCVTZU instruction and narrow them to u16 with the UQXTN instruction, which saturates like the standard `as` conversion.
+    #[inline(always)]
+    fn f32x16_to_u16x16_saturating(floats: &[f32; 16]) -> [uint16x8_t; 2] {
+        let convert = |index: usize| unsafe {
+            vqmovn_u32(crate::float32x4_to_uint32x4(load_f32(
+                &floats[index..index + 4],
+            )))
+        };
+        unsafe {
+            [
+                vcombine_u16(convert(0), convert(4)),
+                vcombine_u16(convert(8), convert(12)),
+            ]
+        }
+    }
+
+    /// Convert sixteen f32 to u8 through i32 like the scalar conversion.
+    pub struct F32ToU8;
+
+    impl F32ToU8 {
+        #[inline(always)]
+        fn narrow([low, high]: [int16x8_t; 2]) -> [u8; 16] {
+            let mut integers = [0u8; 16];
+            unsafe {
+                let narrowed = vcombine_s8(vmovn_s16(low), vmovn_s16(high));
+                vst1q_u8(integers.as_mut_ptr(), vreinterpretq_u8_s8(narrowed));
+            }
+            integers
+        }
+    }
+
+    impl Kernel<16> for F32ToU8 {
+        type Float = f32;
+        type Integer = u8;
+
+        #[inline(always)]
+        fn convert(floats: [f32; 16]) -> [u8; 16] {
+            Self::narrow(f32x16_to_i16x16(&floats, false))
+        }
+
+        #[inline(always)]
+        fn convert_saturating(floats: [f32; 16]) -> [u8; 16] {
+            let [low, high] = f32x16_to_u16x16_saturating(&floats);
+            let mut integers = [0u8; 16];
+            unsafe {
+                vst1q_u8(
+                    integers.as_mut_ptr(),
+                    vcombine_u8(vqmovn_u16(low), vqmovn_u16(high)),
+                )
+            };
+            integers
+        }
+
+        #[inline(always)]
+        fn convert_round_ties_even(floats: [f32; 16]) -> [u8; 16] {
+            Self::narrow(f32x16_to_i16x16(&floats, true))
+        }
+    }
+
+    /// Convert sixteen f32 to u16 through i32 like the scalar conversion.
+    pub struct F32ToU16;
+
+    impl F32ToU16 {
+        #[inline(always)]
+        fn store([low, high]: [uint16x8_t; 2]) -> [u16; 16] {
+            let mut integers = [0u16; 16];
+            unsafe {
+                vst1q_u16(integers.as_mut_ptr(), low);
+                vst1q_u16(integers[8..].as_mut_ptr(), high);
+            }
+            integers
+        }
+    }
+
+    impl Kernel<16> for F32ToU16 {
+        type Float = f32;
+        type Integer = u16;
+
+        #[inline(always)]
+        fn convert(floats: [f32; 16]) -> [u16; 16] {
+            Self::store(
+                f32x16_to_i16x16(&floats, false)
+                    .map(|integers| unsafe { vreinterpretq_u16_s16(integers) }),
+            )
+        }
+
+        #[inline(always)]
+        fn convert_saturating(floats: [f32; 16]) -> [u16; 16] {
+            Self::store(f32x16_to_u16x16_saturating(&floats))
+        }
+
+        #[inline(always)]
+        fn convert_round_ties_even(floats: [f32; 16]) -> [u16; 16] {
+            Self::store(
+                f32x16_to_i16x16(&floats, true)
+                    .map(|integers| unsafe { vreinterpretq_u16_s16(integers) }),
+            )
+        }
+    }
+
+    /// Convert four stereo frames to interleaved i16 with the FCVTZS and XTN instructions and store them with the ST2 instruction, which interleaves the two registers.
+    pub struct StereoF32ToI16;
+
+    impl StereoKernel<4> for StereoF32ToI16 {
+        #[inline(always)]
+        fn convert_interleaved(left: [f32; 4], right: [f32; 4]) -> [[i16; 2]; 4] {
+            // XTN truncates like the scalar conversion through i32, so out of range samples get the same results as with f32_to_i16.
+            let narrow = |floats: [f32; 4]| unsafe {
+                vmovn_s32(crate::float32x4_to_int32x4(load_f32(&floats)))
+            };
+            let mut frames = [[0i16; 2]; 4];
+            unsafe {
+                vst2_s16(
+                    frames.as_mut_ptr().cast(),
+                    int16x4x2_t(narrow(left), narrow(right)),
+                )
+            };
+            frames
+        }
+    }
+
+    /// Convert sixteen f32 to i32 with the predicated SVE FCVTZS instruction at the vector length of the CPU.
+    ///
+    /// SVE vectors have 128 to 2048 bits, so the loop converts the sixteen floats in one to four iterations. WHILELO sets the predicate to the lanes that are left, which handles lengths that are not a multiple of the vector length. FRINTN rounds to nearest with ties to even for `round`. The SVE intrinsics are not stable, so this uses inline assembly.
+    #[cfg(target_feature = "sve")]
+    pub struct SveF32ToI32;
+
+    #[cfg(target_feature = "sve")]
+    macro_rules! sve_convert {
+        ($floats:expr, $($round:literal)?) => {{
+            let floats: [f32; 16] = $floats;
+            let mut integers = [0i32; 16];
+            // SAFETY: The loads and stores are predicated to the sixteen elements of the arrays. The asm clobbers z0, whose low bits are v0, and p0. Rust does not keep values in the high bits of the z registers or in the predicate registers.
+            unsafe {
+                core::arch::asm!(
+                    "mov {index}, #0",
+                    "whilelo p0.s, {index}, {len}",
+                    "2:",
+                    "ld1w {{ z0.s }}, p0/z, [{src}, {index}, lsl #2]",
+                    $($round,)?
+                    "fcvtzs z0.s, p0/m, z0.s",
+                    "st1w {{ z0.s }}, p0, [{dst}, {index}, lsl #2]",
+                    "incw {index}",
+                    "whilelo p0.s, {index}, {len}",
+                    "b.first 2b",
+                    src = in(reg) floats.as_ptr(),
+                    dst = in(reg) integers.as_mut_ptr(),
+                    len = in(reg) integers.len(),
+                    index = out(reg) _,
+                    out("v0") _,
+                    out("p0") _,
+                    options(nostack),
+                );
+            }
+            integers
+        }};
+    }
+
+    #[cfg(target_feature = "sve")]
+    impl Kernel<16> for SveF32ToI32 {
+        type Float = f32;
+        type Integer = i32;
+
+        #[inline(always)]
+        fn convert(floats: [f32; 16]) -> [i32; 16] {
+            sve_convert!(floats,)
+        }
+
+        // FCVTZS saturates and converts NaN to 0 like the standard `as` operator.
+        #[inline(always)]
+        fn convert_saturating(floats: [f32; 16]) -> [i32; 16] {
+            Self::convert(floats)
+        }
+
+        #[inline(always)]
+        fn convert_round_ties_even(floats: [f32; 16]) -> [i32; 16] {
+            sve_convert!(floats, "frintn z0.s, p0/m, z0.s")
+        }
+    }
+
+    /// Convert a block of four rows of four f32 to i32 with the FCVTZS instruction and transpose it with the TRN1 and TRN2 instructions.
+    pub struct TransposeF32ToI32;
+
+    impl TransposeKernel<4> for TransposeF32ToI32 {
+        type Float = f32;
+        type Integer = i32;
+
+        #[inline(always)]
+        fn convert_transposed(rows: [[f32; 4]; 4]) -> [[i32; 4]; 4] {
+            let [a, b, c, d] = rows.map(|row| crate::float32x4_to_int32x4(load_f32(&row)));
+            let columns = unsafe {
+                // a0 b0 a2 b2, a1 b1 a3 b3, c0 d0 c2 d2 and c1 d1 c3 d3.
+                let ab_even = vreinterpretq_s64_s32(vtrn1q_s32(a, b));
+                let ab_odd = vreinterpretq_s64_s32(vtrn2q_s32(a, b));
+                let cd_even = vreinterpretq_s64_s32(vtrn1q_s32(c, d));
+                let cd_odd = vreinterpretq_s64_s32(vtrn2q_s32(c, d));
+                // Combining the 64 bit halves gives the columns.
+                [
+                    vtrn1q_s64(ab_even, cd_even),
+                    vtrn1q_s64(ab_odd, cd_odd),
+                    vtrn2q_s64(ab_even, cd_even),
+                    vtrn2q_s64(ab_odd, cd_odd),
+                ]
+            };
+            columns.map(|column| {
+                let mut integers = [0i32; 4];
+                unsafe { vst1q_s32(integers.as_mut_ptr(), vreinterpretq_s32_s64(column)) };
+                integers
+            })
+        }
+    }
+}
+
+// see crate::target_default::FALLBACK
+#[allow(dead_code)]
+pub const FALLBACK: () = ();
+
+pub const fn is_specialized(conversion: crate::Conversion) -> bool {
+    use crate::Conversion::*;
+
+    // The scalar conversions are the ones of the default target module, but the rounding module replaces the rounding conversions of every conversion except the 128 bit ones, which use crate::software::rounding.
+    cfg!(not(any(
+        feature = "force-default",
+        feature = "portable-deterministic",
+        kani
+    ))) && !matches!(conversion, F32ToI128 | F32ToU128 | F64ToI128 | F64ToU128)
+}
@@ -1,34 +1,127 @@
-// There is an inner module to separate the implementation from the interface.
+use crate::software::standard;
 
 macro_rules! create_function {
-    ($name:ident, $Input:ty, $Output: ty) => {
+    ($name:ident, $Input:ty, $Output: ty, $via_i32:expr) => {
         #[inline(always)]
-        pub fn $name(float: $Input) -> $Output {
-            float as _
+        fn $name(float: $Input) -> $Output {
+            #[cfg(feature = "simulate-unspecified")]
+            if !<$Output as crate::sealed::Sealed<$Input>>::in_range(float) {
+                // The bit pattern of the "integer indefinite" value of x86, which is not the saturated value and not 0 for NaN.
+                return 1 << (<$Output>::BITS - 1);
+            }
+            if $via_i32 {
+                float as i32 as _
+            } else {
+                standard::$name(float)
+            }
         }
     };
 }
 
-pub mod implementation {
-    create_function! {f32_to_i8, f32, i8}
-    create_function! {f32_to_u8, f32, u8}
-    create_function! {f32_to_i16, f32, i16}
-    create_function! {f32_to_u16, f32, u16}
-    create_function! {f32_to_i32, f32, i32}
-    create_function! {f32_to_u32, f32, u32}
-    create_function! {f32_to_i64, f32, i64}
-    create_function! {f32_to_u64, f32, u64}
-    create_function! {f32_to_i128, f32, i128}
-    create_function! {f32_to_u128, f32, u128}
-
-    create_function! {f64_to_i8, f64, i8}
-    create_function! {f64_to_u8, f64, u8}
-    create_function! {f64_to_i16, f64, i16}
-    create_function! {f64_to_u16, f64, u16}
-    create_function! {f64_to_i32, f64, i32}
-    create_function! {f64_to_u32, f64, u32}
-    create_function! {f64_to_i64, f64, i64}
-    create_function! {f64_to_u64, f64, u64}
-    create_function! {f64_to_i128, f64, i128}
-    create_function! {f64_to_u128, f64, u128}
+// The compiler saturates 8 and 16 bit outputs by clamping the float before converting it. On ARM, aarch64 and RISC-V the conversion to i32 already saturates in hardware and clamping costs more than the conversion. Converting to i32 and truncating gives the same result for inputs in range. See `generated assembly/aarch64_default`.
+//
+// On x86 the direct conversion is cheaper because cvttss2si needs extra instructions to saturate i32 but not for the clamped float. Without a hardware FPU both ways go through the same software conversion. The 32 bit ARM and RISC-V targets might only have a single precision FPU, so f64 uses the chain only on aarch64. We cannot detect the FPU with cfg on stable Rust.
+const F32_VIA_I32: bool = cfg!(any(
+    target_arch = "aarch64",
+    target_arch = "arm",
+    target_arch = "riscv32",
+    target_arch = "riscv64"
+));
+const F64_VIA_I32: bool = cfg!(target_arch = "aarch64");
+
+create_function! {f32_to_i8, f32, i8, F32_VIA_I32}
+create_function! {f32_to_u8, f32, u8, F32_VIA_I32}
+create_function! {f32_to_i16, f32, i16, F32_VIA_I32}
+create_function! {f32_to_u16, f32, u16, F32_VIA_I32}
+create_function! {f32_to_i32, f32, i32, false}
+create_function! {f32_to_u32, f32, u32, false}
+create_function! {f32_to_i64, f32, i64, false}
+create_function! {f32_to_u64, f32, u64, false}
+create_function! {f32_to_i128, f32, i128, false}
+create_function! {f32_to_u128, f32, u128, false}
+
+create_function! {f64_to_i8, f64, i8, F64_VIA_I32}
+create_function! {f64_to_u8, f64, u8, F64_VIA_I32}
+create_function! {f64_to_i16, f64, i16, F64_VIA_I32}
+create_function! {f64_to_u16, f64, u16, F64_VIA_I32}
+create_function! {f64_to_i32, f64, i32, false}
+create_function! {f64_to_u32, f64, u32, false}
+create_function! {f64_to_i64, f64, i64, false}
+create_function! {f64_to_u64, f64, u64, false}
+create_function! {f64_to_i128, f64, i128, false}
+create_function! {f64_to_u128, f64, u128, false}
+
+create_implementation! {
+    f32_to_i8: f32_to_i8,
+    f32_to_u8: f32_to_u8,
+    f32_to_i16: f32_to_i16,
+    f32_to_u16: f32_to_u16,
+    f32_to_i32: f32_to_i32,
+    f32_to_u32: f32_to_u32,
+    f32_to_i64: f32_to_i64,
+    f32_to_u64: f32_to_u64,
+    f32_to_i128: f32_to_i128,
+    f32_to_u128: f32_to_u128,
+    f64_to_i8: f64_to_i8,
+    f64_to_u8: f64_to_u8,
+    f64_to_i16: f64_to_i16,
+    f64_to_u16: f64_to_u16,
+    f64_to_i32: f64_to_i32,
+    f64_to_u32: f64_to_u32,
+    f64_to_i64: f64_to_i64,
+    f64_to_u64: f64_to_u64,
+    f64_to_i128: f64_to_i128,
+    f64_to_u128: f64_to_u128,
+}
+
+// Unused if the aarch64_neon module is active, which only uses the scalar conversions of this module.
+#[allow(dead_code)]
+pub mod kernel {
+    pub type F32ToI32 = crate::kernel::Scalar<f32, i32>;
+    pub type F64ToI32 = crate::kernel::Scalar<f64, i32>;
+    pub type F32ToU8 = crate::kernel::Scalar<f32, u8>;
+    pub type F32ToU16 = crate::kernel::Scalar<f32, u16>;
+    pub type StereoF32ToI16 = crate::kernel::Scalar<f32, i16>;
+    pub type TransposeF32ToI32 = crate::kernel::Scalar<f32, i32>;
+}
+
+/// Used by the `warn_fallback` macro. Only this module is deprecated, so the macro warns if this module is active.
+// Unused if the module is not active but available through the backends module.
+#[allow(dead_code)]
+#[cfg_attr(
+    feature = "warn-fallback",
+    deprecated = "fast-float-to-integer has no specialized implementation for this target. The conversions are not faster than the standard `as` operator."
+)]
+pub const FALLBACK: () = ();
+
+// Unused if the aarch64_neon module is active.
+#[allow(dead_code)]
+pub const fn is_specialized(_: crate::Conversion) -> bool {
+    false
+}
+
+pub const fn behavior(conversion: crate::Conversion) -> crate::Behavior {
+    use crate::{Behavior, Conversion::*, Nan, OutOfRange};
+
+    // Code that is tested with the feature must not rely on any out of range result.
+    if cfg!(feature = "simulate-unspecified") {
+        return Behavior {
+            out_of_range: OutOfRange::Unspecified,
+            nan: Nan::Unspecified,
+        };
+    }
+    let via_i32 = match conversion {
+        F32ToI8 | F32ToU8 | F32ToI16 | F32ToU16 => F32_VIA_I32,
+        F64ToI8 | F64ToU8 | F64ToI16 | F64ToU16 => F64_VIA_I32,
+        _ => false,
+    };
+    if via_i32 {
+        // The truncated bits of the saturated i32.
+        Behavior {
+            out_of_range: OutOfRange::Unspecified,
+            nan: Nan::Zero,
+        }
+    } else {
+        Behavior::SATURATING
+    }
 }
@@ -0,0 +1,91 @@
+// The conversions of the `portable-deterministic` feature. They decode the bit pattern of the float with the integer arithmetic of the soft-float conversions. Integer arithmetic gives the same results on every target, so unlike the conversion instructions of the hardware, even the results for out of range inputs and NaN are the same everywhere.
+
+use crate::software::soft_float;
+
+macro_rules! create_function {
+    ($name:ident, $bits_name:ident, $Float:ty, $Integer:ty) => {
+        #[inline(always)]
+        fn $name(float: $Float) -> $Integer {
+            soft_float::$bits_name(float.to_bits())
+        }
+    };
+}
+
+create_function! {f32_to_i8, f32_bits_to_i8, f32, i8}
+create_function! {f32_to_u8, f32_bits_to_u8, f32, u8}
+create_function! {f32_to_i16, f32_bits_to_i16, f32, i16}
+create_function! {f32_to_u16, f32_bits_to_u16, f32, u16}
+create_function! {f32_to_i32, f32_bits_to_i32, f32, i32}
+create_function! {f32_to_u32, f32_bits_to_u32, f32, u32}
+create_function! {f32_to_i64, f32_bits_to_i64, f32, i64}
+create_function! {f32_to_u64, f32_bits_to_u64, f32, u64}
+create_function! {f32_to_i128, f32_bits_to_i128, f32, i128}
+create_function! {f32_to_u128, f32_bits_to_u128, f32, u128}
+
+create_function! {f64_to_i8, f64_bits_to_i8, f64, i8}
+create_function! {f64_to_u8, f64_bits_to_u8, f64, u8}
+create_function! {f64_to_i16, f64_bits_to_i16, f64, i16}
+create_function! {f64_to_u16, f64_bits_to_u16, f64, u16}
+create_function! {f64_to_i32, f64_bits_to_i32, f64, i32}
+create_function! {f64_to_u32, f64_bits_to_u32, f64, u32}
+create_function! {f64_to_i64, f64_bits_to_i64, f64, i64}
+create_function! {f64_to_u64, f64_bits_to_u64, f64, u64}
+create_function! {f64_to_i128, f64_bits_to_i128, f64, i128}
+create_function! {f64_to_u128, f64_bits_to_u128, f64, u128}
+
+create_implementation! {
+    f32_to_i8: f32_to_i8,
+    f32_to_u8: f32_to_u8,
+    f32_to_i16: f32_to_i16,
+    f32_to_u16: f32_to_u16,
+    f32_to_i32: f32_to_i32,
+    f32_to_u32: f32_to_u32,
+    f32_to_i64: f32_to_i64,
+    f32_to_u64: f32_to_u64,
+    f32_to_i128: f32_to_i128,
+    f32_to_u128: f32_to_u128,
+    f64_to_i8: f64_to_i8,
+    f64_to_u8: f64_to_u8,
+    f64_to_i16: f64_to_i16,
+    f64_to_u16: f64_to_u16,
+    f64_to_i32: f64_to_i32,
+    f64_to_u32: f64_to_u32,
+    f64_to_i64: f64_to_i64,
+    f64_to_u64: f64_to_u64,
+    f64_to_i128: f64_to_i128,
+    f64_to_u128: f64_to_u128,
+}
+
+// The packed conversion instructions differ between targets, so every kernel converts lane by lane with the functions above.
+pub mod kernel {
+    pub type F32ToI32 = crate::kernel::Scalar<f32, i32>;
+    pub type F64ToI32 = crate::kernel::Scalar<f64, i32>;
+    pub type F32ToU8 = crate::kernel::Scalar<f32, u8>;
+    pub type F32ToU16 = crate::kernel::Scalar<f32, u16>;
+    pub type StereoF32ToI16 = crate::kernel::Scalar<f32, i16>;
+    pub type TransposeF32ToI32 = crate::kernel::Scalar<f32, i32>;
+}
+
+/// Used by the `warn_fallback` macro. The conversions are not the standard `as` operator, so this is not deprecated.
+pub const FALLBACK: () = ();
+
+// The software conversions are not faster than the standard `as` operator on targets with an FPU.
+pub const fn is_specialized(_: crate::Conversion) -> bool {
+    false
+}
+
+pub const fn behavior(conversion: crate::Conversion) -> crate::Behavior {
+    use crate::{Behavior, Conversion::*, Nan, OutOfRange};
+
+    // The results are the same on every target, but most of them depend on the bits of the input, so the table cannot describe them with a single value. Out of range inputs shift the mantissa by the exponent modulo the width of the wide type. Infinities have no mantissa bits besides the implicit one, which is shifted out, so they convert to 0. Finite out of range inputs keep some bits.
+    //
+    // NaN has the maximal exponent. The shift puts 9 zero bits below the f32 mantissa and 12 below the f64 mantissa, so the 8 bit outputs are 0 for every payload. The wider outputs keep bits of the payload.
+    let nan = match conversion {
+        F32ToI8 | F32ToU8 | F64ToI8 | F64ToU8 => Nan::Zero,
+        _ => Nan::Unspecified,
+    };
+    Behavior {
+        out_of_range: OutOfRange::Unspecified,
+        nan,
+    }
+}
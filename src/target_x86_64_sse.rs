@@ -1,188 +1,442 @@
-use core::arch::x86_64::{_mm_cvttsd_si64, _mm_cvttss_si64, _mm_loadu_pd, _mm_loadu_ps};
+use core::arch::x86_64::{
+    _mm_cvttsd_si32, _mm_cvttsd_si64, _mm_cvttss_si32, _mm_cvttss_si64, _mm_set_sd, _mm_set_ss,
+};
 
-use crate::{power_of_two_f32, power_of_two_f64};
+use crate::software::standard;
 
 /// Convert f32 to i64 using the CVTTSS2SI instruction. If the input f32 is out of range of the output i64, then the result is i64::MIN.
 #[inline(always)]
 fn f32_to_i64(float: f32) -> i64 {
     // The compiler optimizes this function into a single instruction without the need for inline assembly.
-
-    let floats = [float, 0., 0., 0.];
-    let floats_pointer = floats.as_ptr();
-    let floats_register = unsafe { _mm_loadu_ps(floats_pointer) };
+    //
+    // The instruction only reads the lowest lane. MOVSS semantics of _mm_set_ss tell the compiler that the other lanes are 0 without building them in memory. Loading a zero padded array can leave the padding in the assembly in some inlining contexts. If the float is already in a register, then no instruction is needed to move it into the lowest lane.
+    let floats_register = unsafe { _mm_set_ss(float) };
     unsafe { _mm_cvttss_si64(floats_register) }
 }
 
-// For f32_to_i32 we could use CVTTSS2SI with 32 bit output (_mm_cvttss_si64) instead of the 64 bit output. That might be faster.
-
-// We can't use the same approach for u64 output because the conversion instruction only works on i64. This is a problem for floats that exceed i64::MAX. We cannot handle this with one instruction, but we can still do better than the as operator.
-
-// This approach branches into a special case if the input is too large. The branchless approach below is faster and is the one we use. We keep this code around for documentation purposes.
-#[inline(always)]
-fn _f32_to_u64_branchful(float: f32) -> u64 {
-    const THRESHOLD_FLOAT: f32 = power_of_two_f32(63);
-    const THRESHOLD_INTEGER: u64 = 2u64.pow(63);
-
-    let in_range = float <= THRESHOLD_FLOAT;
-    if in_range {
-        f32_to_i64(float) as u64
-    } else {
-        // Subtract the threshold from the float. The result is >= 0 because the input is larger than the subtrahend. The result is <= i64::MAX because `u64::MAX - i64::MAX == i64::MAX`.
-        let in_range_float = float - THRESHOLD_FLOAT;
-        let integer = f32_to_i64(in_range_float) as u64;
-        // Overflow is benign because it can only occur for invalid inputs.
-        integer.overflowing_add(THRESHOLD_INTEGER).0
-    }
-}
-
-// This approach avoids the branch. It is faster than the branchful approach.
+/// Convert f32 to i32 using the CVTTSS2SI instruction with 32 bit output. If the input f32 is out of range of the output i32, then the result is i32::MIN.
+///
+/// This matches the CVTTPS2DQ instruction of the packed kernel, so the slice functions give the same results as the scalar function for every input.
 #[inline(always)]
-fn f32_to_u64_branchless(float: f32) -> u64 {
-    const THRESHOLD: f32 = power_of_two_f32(63);
-
-    let integer1 = f32_to_i64(float);
-    let integer2 = f32_to_i64(float - THRESHOLD);
-    // If the input is larger than i64::MAX, then integer1 is i64::MIN. This value has 1 as the leftmost bit and 0 as the remaining bits. Right shift on signed values is arithmetic, not logical [1]. We end up with all 0 (in range) or all 1 (out of range).
-    let too_large = integer1 >> 63;
-    // # If the input is not too large:
-    //
-    // Integer1 has the correct value. The mask is all 0, which makes the Or result in integer1.
-    //
-    // # If the input is too large:
-    //
-    // Integer1 is i64::MIN and the mask is all 1. The Or results in `i64::MIN | integer2`. integer2 has the correct result minus 2**63. This is the correct result without the leftmost bit. The Or adds the missing leftmost bit back.
-    (integer1 | (integer2 & too_large)) as u64
-
-    // [1] https://doc.rust-lang.org/reference/expressions/operator-expr.html#arithmetic-and-logical-binary-operators
+fn f32_to_i32(float: f32) -> i32 {
+    // see f32_to_i64
+    let floats_register = unsafe { _mm_set_ss(float) };
+    unsafe { _mm_cvttss_si32(floats_register) }
 }
 
+// The instruction only converts to i64. See crate::software for how we handle floats that exceed i64::MAX.
 #[inline(always)]
 fn f32_to_u64(float: f32) -> u64 {
-    f32_to_u64_branchless(float)
-}
-
-// Repeat for f64.
-
-#[inline(always)]
-fn f64_to_i64(float: f64) -> i64 {
-    // see convert_f32
-
-    let floats = [float, 0.];
-    let floats_pointer = floats.as_ptr();
-    let floats_register = unsafe { _mm_loadu_pd(floats_pointer) };
-    unsafe { _mm_cvttsd_si64(floats_register) }
-}
-
-#[inline(always)]
-fn f64_to_u64(float: f64) -> u64 {
-    // see f32_to_u64
-
-    const THRESHOLD: f64 = power_of_two_f64(63);
-
-    let integer1 = f64_to_i64(float);
-    let integer2 = f64_to_i64(float - THRESHOLD);
-    let too_large = integer1 >> 63;
-    (integer1 | (integer2 & too_large)) as u64
+    #[cfg(not(feature = "register-constants"))]
+    return crate::software::f32_to_u64_via_i64(float, f32_to_i64);
+    #[cfg(feature = "register-constants")]
+    return crate::software::f32_to_u64_via_i64_with_threshold(
+        float,
+        register_constants::f32_pow2_63(),
+        f32_to_i64,
+    );
 }
 
-pub mod implementation {
-    #[inline(always)]
-    pub fn f32_to_i8(float: f32) -> i8 {
-        super::f32_to_i64(float) as _
-    }
-
-    #[inline(always)]
-    pub fn f32_to_u8(float: f32) -> u8 {
-        super::f32_to_i64(float) as _
-    }
-
-    #[inline(always)]
-    pub fn f32_to_i16(float: f32) -> i16 {
-        super::f32_to_i64(float) as _
-    }
-
-    #[inline(always)]
-    pub fn f32_to_u16(float: f32) -> u16 {
-        super::f32_to_i64(float) as _
-    }
-
-    #[inline(always)]
-    pub fn f32_to_i32(float: f32) -> i32 {
-        super::f32_to_i64(float) as _
-    }
+// The u64 conversions subtract 2^63. The compiler loads the constant from the constant pool in memory, which is one cache line that can miss when the conversion runs rarely. With the `register-constants` feature we move the constant into the register from an immediate in the instruction stream instead. This costs an extra instruction when the cache line is hot.
+#[cfg(feature = "register-constants")]
+mod register_constants {
+    use core::arch::asm;
 
+    /// 2^63 as f32, built with MOV of an immediate into an integer register and MOVD into an SSE register.
     #[inline(always)]
-    pub fn f32_to_u32(float: f32) -> u32 {
-        super::f32_to_i64(float) as _
+    pub fn f32_pow2_63() -> f32 {
+        // Exponent 63 with a bias of 127 and an empty mantissa.
+        const BITS: u32 = (63 + 127) << 23;
+        let float: f32;
+        // SAFETY: The instruction only moves bits between registers. The asm block hides the constant from the compiler, which would otherwise fold it back into a load.
+        unsafe {
+            asm!("movd {float}, {bits:e}", bits = in(reg) BITS, float = lateout(xmm_reg) float, options(pure, nomem, nostack, preserves_flags));
+        }
+        float
     }
 
+    /// 2^63 as f64, built with MOV of an immediate into an integer register and MOVQ into an SSE register.
     #[inline(always)]
-    pub fn f32_to_i64(float: f32) -> i64 {
-        super::f32_to_i64(float) as _
+    pub fn f64_pow2_63() -> f64 {
+        // Exponent 63 with a bias of 1023 and an empty mantissa.
+        const BITS: u64 = (63 + 1023) << 52;
+        let float: f64;
+        // SAFETY: see f32_pow2_63
+        unsafe {
+            asm!("movq {float}, {bits}", bits = in(reg) BITS, float = lateout(xmm_reg) float, options(pure, nomem, nostack, preserves_flags));
+        }
+        float
     }
+}
 
-    #[inline(always)]
-    pub fn f32_to_u64(float: f32) -> u64 {
-        super::f32_to_u64(float) as _
+// With AVX-512 the CVTTSS2USI instruction converts to unsigned integers directly. For u8 and u16 we clamp the u32 result, which is cheaper than converting to i64 and matches the saturating narrowing of the packed kernels.
+cfg_if::cfg_if! {
+    if #[cfg(target_feature = "avx512f")] {
+        /// Convert f32 to u32 using the VCVTTSS2USI instruction. If the input f32 is out of range of the output u32, then the result is u32::MAX.
+        // Rust versions before 1.89, where the intrinsics became stable, do not set the avx512f cfg even with `-C target-feature=+avx512f` because the target feature was unstable. The check_avx512 CI job compiles this code with 1.89.
+        #[allow(clippy::incompatible_msrv)]
+        #[inline(always)]
+        fn f32_to_u32_avx512(float: f32) -> u32 {
+            use core::arch::x86_64::_mm_cvttss_u32;
+
+            // see f32_to_i64
+            let floats_register = unsafe { _mm_set_ss(float) };
+            unsafe { _mm_cvttss_u32(floats_register) }
+        }
+
+        #[inline(always)]
+        fn f32_to_u8(float: f32) -> u8 {
+            f32_to_u32_avx512(float).min(u8::MAX.into()) as u8
+        }
+
+        #[inline(always)]
+        fn f32_to_u16(float: f32) -> u16 {
+            f32_to_u32_avx512(float).min(u16::MAX.into()) as u16
+        }
+    } else {
+        #[inline(always)]
+        fn f32_to_u8(float: f32) -> u8 {
+            f32_to_i64(float) as _
+        }
+
+        #[inline(always)]
+        fn f32_to_u16(float: f32) -> u16 {
+            f32_to_i64(float) as _
+        }
     }
+}
 
-    #[inline(always)]
-    pub fn f32_to_i128(float: f32) -> i128 {
-        float as _
-    }
+// Repeat for f64.
 
-    #[inline(always)]
-    pub fn f32_to_u128(float: f32) -> u128 {
-        float as _
-    }
+#[inline(always)]
+fn f64_to_i64(float: f64) -> i64 {
+    // see f32_to_i64
 
-    #[inline(always)]
-    pub fn f64_to_i8(float: f64) -> i8 {
-        f64_to_i64(float) as _
-    }
+    let floats_register = unsafe { _mm_set_sd(float) };
+    unsafe { _mm_cvttsd_si64(floats_register) }
+}
 
-    #[inline(always)]
-    pub fn f64_to_u8(float: f64) -> u8 {
-        super::f64_to_i64(float) as _
-    }
+#[inline(always)]
+fn f64_to_i32(float: f64) -> i32 {
+    // see f32_to_i32
+    let floats_register = unsafe { _mm_set_sd(float) };
+    unsafe { _mm_cvttsd_si32(floats_register) }
+}
 
-    #[inline(always)]
-    pub fn f64_to_i16(float: f64) -> i16 {
-        super::f64_to_i64(float) as _
-    }
+#[inline(always)]
+fn f64_to_u64(float: f64) -> u64 {
+    #[cfg(not(feature = "register-constants"))]
+    return crate::software::f64_to_u64_via_i64(float, f64_to_i64);
+    #[cfg(feature = "register-constants")]
+    return crate::software::f64_to_u64_via_i64_with_threshold(
+        float,
+        register_constants::f64_pow2_63(),
+        f64_to_i64,
+    );
+}
 
-    #[inline(always)]
-    pub fn f64_to_u16(float: f64) -> u16 {
-        super::f64_to_i64(float) as _
-    }
+create_implementation! {
+    f32_to_i8: f32_to_i64,
+    f32_to_u8: f32_to_u8,
+    f32_to_i16: f32_to_i64,
+    f32_to_u16: f32_to_u16,
+    f32_to_i32: f32_to_i32,
+    f32_to_u32: f32_to_i64,
+    f32_to_i64: f32_to_i64,
+    f32_to_u64: f32_to_u64,
+    f32_to_i128: standard::f32_to_i128,
+    f32_to_u128: standard::f32_to_u128,
+    f64_to_i8: f64_to_i64,
+    f64_to_u8: f64_to_i64,
+    f64_to_i16: f64_to_i64,
+    f64_to_u16: f64_to_i64,
+    f64_to_i32: f64_to_i32,
+    f64_to_u32: f64_to_i64,
+    f64_to_i64: f64_to_i64,
+    f64_to_u64: f64_to_u64,
+    f64_to_i128: standard::f64_to_i128,
+    f64_to_u128: standard::f64_to_u128,
+}
 
-    #[inline(always)]
-    pub fn f64_to_i32(float: f64) -> i32 {
-        super::f64_to_i64(float) as _
+// ROUNDSS and ROUNDSD need SSE4.1. Without it the rounding conversions use crate::software::rounding. The module is only used when this target module is active, see crate::rounding.
+#[cfg(all(
+    target_feature = "sse4.1",
+    not(any(feature = "force-default", feature = "portable-deterministic", kani))
+))]
+create_sse41_rounding!();
+
+// Packed conversions. They need SSE2.
+pub mod kernel {
+    cfg_if::cfg_if! {
+        if #[cfg(target_feature = "sse2")] {
+            use core::arch::x86_64::{
+                __m128i, _mm_cvttps_epi32, _mm_loadu_pd, _mm_loadu_ps, _mm_packs_epi32,
+                _mm_slli_epi32, _mm_srai_epi32, _mm_storeu_si128, _mm_unpackhi_epi32,
+                _mm_unpackhi_epi64, _mm_unpacklo_epi32, _mm_unpacklo_epi64,
+            };
+
+            use crate::kernel::{Kernel, StereoKernel, TransposeKernel};
+
+            #[inline(always)]
+            fn store_i32(integers_register: __m128i) -> [i32; 4] {
+                let mut integers = [0i32; 4];
+                unsafe { _mm_storeu_si128(integers.as_mut_ptr().cast(), integers_register) };
+                integers
+            }
+
+            /// Convert four f32 to i32 with the CVTTPS2DQ instruction.
+            pub struct F32ToI32;
+
+            impl Kernel<4> for F32ToI32 {
+                type Float = f32;
+                type Integer = i32;
+
+                #[inline(always)]
+                fn convert(floats: [f32; 4]) -> [i32; 4] {
+                    let floats_register = unsafe { _mm_loadu_ps(floats.as_ptr()) };
+                    store_i32(crate::m128_to_i32x4(floats_register))
+                }
+
+                #[inline(always)]
+                fn convert_saturating(floats: [f32; 4]) -> [i32; 4] {
+                    let floats_register = unsafe { _mm_loadu_ps(floats.as_ptr()) };
+                    store_i32(crate::m128_to_i32x4_saturating(floats_register))
+                }
+
+                #[inline(always)]
+                fn convert_round_ties_even(floats: [f32; 4]) -> [i32; 4] {
+                    let floats_register = unsafe { _mm_loadu_ps(floats.as_ptr()) };
+                    store_i32(crate::m128_to_i32x4_round_ties_even(floats_register))
+                }
+            }
+
+            /// Convert two f64 to i32 with the CVTTPD2DQ instruction.
+            pub struct F64ToI32;
+
+            impl Kernel<2> for F64ToI32 {
+                type Float = f64;
+                type Integer = i32;
+
+                #[inline(always)]
+                fn convert(floats: [f64; 2]) -> [i32; 2] {
+                    let floats_register = unsafe { _mm_loadu_pd(floats.as_ptr()) };
+                    let integers = store_i32(crate::m128d_to_i32x2(floats_register));
+                    [integers[0], integers[1]]
+                }
+
+                #[inline(always)]
+                fn convert_saturating(floats: [f64; 2]) -> [i32; 2] {
+                    let floats_register = unsafe { _mm_loadu_pd(floats.as_ptr()) };
+                    let integers = store_i32(crate::m128d_to_i32x2_saturating(floats_register));
+                    [integers[0], integers[1]]
+                }
+
+                #[inline(always)]
+                fn convert_round_ties_even(floats: [f64; 2]) -> [i32; 2] {
+                    let floats_register = unsafe { _mm_loadu_pd(floats.as_ptr()) };
+                    let integers = store_i32(crate::m128d_to_i32x2_round_ties_even(floats_register));
+                    [integers[0], integers[1]]
+                }
+            }
+
+            /// Convert four stereo frames to interleaved i16 with the CVTTPS2DQ, PUNPCKLDQ/PUNPCKHDQ, PSLLD/PSRAD and PACKSSDW instructions.
+            pub struct StereoF32ToI16;
+
+            impl StereoKernel<4> for StereoF32ToI16 {
+                #[inline(always)]
+                fn convert_interleaved(left: [f32; 4], right: [f32; 4]) -> [[i16; 2]; 4] {
+                    let left = unsafe { _mm_cvttps_epi32(_mm_loadu_ps(left.as_ptr())) };
+                    let right = unsafe { _mm_cvttps_epi32(_mm_loadu_ps(right.as_ptr())) };
+                    // The unpack instructions interleave the lower and upper two lanes of both registers.
+                    let first = unsafe { _mm_unpacklo_epi32(left, right) };
+                    let second = unsafe { _mm_unpackhi_epi32(left, right) };
+                    // Sign extending the lower 16 bits truncates like the scalar conversion for results in range of i32. The narrowing with signed saturation then keeps every value unchanged.
+                    let truncate = |integers: __m128i| unsafe {
+                        _mm_srai_epi32(_mm_slli_epi32(integers, 16), 16)
+                    };
+                    let packed = unsafe { _mm_packs_epi32(truncate(first), truncate(second)) };
+                    let mut frames = [[0i16; 2]; 4];
+                    unsafe { _mm_storeu_si128(frames.as_mut_ptr().cast(), packed) };
+                    frames
+                }
+            }
+
+            /// Convert a block of four rows of four f32 to i32 with the CVTTPS2DQ instruction and transpose it with the PUNPCKLDQ/PUNPCKHDQ and PUNPCKLQDQ/PUNPCKHQDQ instructions.
+            pub struct TransposeF32ToI32;
+
+            impl TransposeKernel<4> for TransposeF32ToI32 {
+                type Float = f32;
+                type Integer = i32;
+
+                #[inline(always)]
+                fn convert_transposed(rows: [[f32; 4]; 4]) -> [[i32; 4]; 4] {
+                    let [a, b, c, d] =
+                        rows.map(|row| unsafe { _mm_cvttps_epi32(_mm_loadu_ps(row.as_ptr())) });
+                    // a0 b0 a1 b1, c0 d0 c1 d1, a2 b2 a3 b3 and c2 d2 c3 d3.
+                    let ab_low = unsafe { _mm_unpacklo_epi32(a, b) };
+                    let cd_low = unsafe { _mm_unpacklo_epi32(c, d) };
+                    let ab_high = unsafe { _mm_unpackhi_epi32(a, b) };
+                    let cd_high = unsafe { _mm_unpackhi_epi32(c, d) };
+                    // Combining the 64 bit halves gives the columns.
+                    [
+                        store_i32(unsafe { _mm_unpacklo_epi64(ab_low, cd_low) }),
+                        store_i32(unsafe { _mm_unpackhi_epi64(ab_low, cd_low) }),
+                        store_i32(unsafe { _mm_unpacklo_epi64(ab_high, cd_high) }),
+                        store_i32(unsafe { _mm_unpackhi_epi64(ab_high, cd_high) }),
+                    ]
+                }
+            }
+        } else {
+            pub type F32ToI32 = crate::kernel::Scalar<f32, i32>;
+            pub type F64ToI32 = crate::kernel::Scalar<f64, i32>;
+            pub type StereoF32ToI16 = crate::kernel::Scalar<f32, i16>;
+            pub type TransposeF32ToI32 = crate::kernel::Scalar<f32, i32>;
+        }
     }
 
-    #[inline(always)]
-    pub fn f64_to_u32(float: f64) -> u32 {
-        super::f64_to_i64(float) as _
+    // With AVX-512, VCVTTPS2UDQ converts sixteen f32 to u32 and VPMOVUSDB/VPMOVUSDW narrow them with unsigned saturation.
+    cfg_if::cfg_if! {
+        if #[cfg(target_feature = "avx512f")] {
+            mod avx512 {
+                // See the comment on f32_to_u32_avx512 above.
+                #![allow(clippy::incompatible_msrv)]
+
+                use core::arch::x86_64::{
+                    __m512, __m512i, _mm256_storeu_si256, _mm512_cvtusepi32_epi16,
+                    _mm512_cvtusepi32_epi8, _mm512_cvttps_epu32, _mm512_loadu_ps, _mm512_max_ps,
+                    _mm512_setzero_ps, _mm_storeu_si128,
+                };
+
+                /// Convert sixteen f32 to u32. Out of range and NaN lanes are u32::MAX.
+                #[inline(always)]
+                fn convert_u32(floats: __m512) -> __m512i {
+                    unsafe { _mm512_cvttps_epu32(floats) }
+                }
+
+                /// Like `convert_u32` but negative and NaN lanes are 0.
+                #[inline(always)]
+                fn convert_u32_saturating(floats: __m512) -> __m512i {
+                    // If either operand is NaN, MAXPS returns the second operand.
+                    let floats = unsafe { _mm512_max_ps(floats, _mm512_setzero_ps()) };
+                    convert_u32(floats)
+                }
+
+                #[inline(always)]
+                fn narrow_u8(integers: __m512i) -> [u8; 16] {
+                    let mut result = [0u8; 16];
+                    let narrowed = unsafe { _mm512_cvtusepi32_epi8(integers) };
+                    unsafe { _mm_storeu_si128(result.as_mut_ptr().cast(), narrowed) };
+                    result
+                }
+
+                #[inline(always)]
+                fn narrow_u16(integers: __m512i) -> [u16; 16] {
+                    let mut result = [0u16; 16];
+                    let narrowed = unsafe { _mm512_cvtusepi32_epi16(integers) };
+                    unsafe { _mm256_storeu_si256(result.as_mut_ptr().cast(), narrowed) };
+                    result
+                }
+
+                /// Convert sixteen f32 to u8 with the VCVTTPS2UDQ and VPMOVUSDB instructions.
+                pub struct F32ToU8;
+
+                impl crate::kernel::Kernel<16> for F32ToU8 {
+                    type Float = f32;
+                    type Integer = u8;
+
+                    #[inline(always)]
+                    fn convert(floats: [f32; 16]) -> [u8; 16] {
+                        let floats_register = unsafe { _mm512_loadu_ps(floats.as_ptr()) };
+                        narrow_u8(convert_u32(floats_register))
+                    }
+
+                    #[inline(always)]
+                    fn convert_saturating(floats: [f32; 16]) -> [u8; 16] {
+                        let floats_register = unsafe { _mm512_loadu_ps(floats.as_ptr()) };
+                        narrow_u8(convert_u32_saturating(floats_register))
+                    }
+                }
+
+                /// Convert sixteen f32 to u16 with the VCVTTPS2UDQ and VPMOVUSDW instructions.
+                pub struct F32ToU16;
+
+                impl crate::kernel::Kernel<16> for F32ToU16 {
+                    type Float = f32;
+                    type Integer = u16;
+
+                    #[inline(always)]
+                    fn convert(floats: [f32; 16]) -> [u16; 16] {
+                        let floats_register = unsafe { _mm512_loadu_ps(floats.as_ptr()) };
+                        narrow_u16(convert_u32(floats_register))
+                    }
+
+                    #[inline(always)]
+                    fn convert_saturating(floats: [f32; 16]) -> [u16; 16] {
+                        let floats_register = unsafe { _mm512_loadu_ps(floats.as_ptr()) };
+                        narrow_u16(convert_u32_saturating(floats_register))
+                    }
+                }
+            }
+
+            pub use avx512::{F32ToU16, F32ToU8};
+        } else {
+            pub type F32ToU8 = crate::kernel::Scalar<f32, u8>;
+            pub type F32ToU16 = crate::kernel::Scalar<f32, u16>;
+        }
     }
+}
 
-    #[inline(always)]
-    pub fn f64_to_i64(float: f64) -> i64 {
-        super::f64_to_i64(float) as _
-    }
+// see crate::target_default::FALLBACK
+#[allow(dead_code)]
+pub const FALLBACK: () = ();
 
-    #[inline(always)]
-    pub fn f64_to_u64(float: f64) -> u64 {
-        super::f64_to_u64(float) as _
-    }
+pub const fn is_specialized(conversion: crate::Conversion) -> bool {
+    use crate::Conversion::*;
 
-    #[inline(always)]
-    pub fn f64_to_i128(float: f64) -> i128 {
-        float as _
-    }
+    // The 128 bit conversions use the standard conversion.
+    !matches!(conversion, F32ToI128 | F32ToU128 | F64ToI128 | F64ToU128)
+}
 
-    #[inline(always)]
-    pub fn f64_to_u128(float: f64) -> u128 {
-        float as _
+pub const fn behavior(conversion: crate::Conversion) -> crate::Behavior {
+    use crate::{Behavior, Conversion::*, Nan, OutOfRange};
+
+    // The CVTT instructions return i64::MIN for out of range inputs and NaN. For smaller outputs we truncate the i64. The bits of i64::MIN that remain after truncating are 0. The i32 conversions use the 32 bit instructions, which return i32::MIN.
+    match conversion {
+        F32ToI8 | F32ToI16 | F32ToU32 | F64ToI8 | F64ToU8 | F64ToI16 | F64ToU16 | F64ToU32 => {
+            Behavior {
+                out_of_range: OutOfRange::Unspecified,
+                nan: Nan::Zero,
+            }
+        }
+        F32ToI32 | F64ToI32 => Behavior {
+            out_of_range: OutOfRange::Sentinel(i32::MIN as i128),
+            nan: Nan::Sentinel(i32::MIN as i128),
+        },
+        // With AVX-512 we clamp the u32 result of VCVTTSS2USI, which is u32::MAX for out of range inputs and NaN.
+        #[cfg(target_feature = "avx512f")]
+        F32ToU8 => Behavior {
+            out_of_range: OutOfRange::Sentinel(u8::MAX as i128),
+            nan: Nan::Sentinel(u8::MAX as i128),
+        },
+        #[cfg(target_feature = "avx512f")]
+        F32ToU16 => Behavior {
+            out_of_range: OutOfRange::Sentinel(u16::MAX as i128),
+            nan: Nan::Sentinel(u16::MAX as i128),
+        },
+        #[cfg(not(target_feature = "avx512f"))]
+        F32ToU8 | F32ToU16 => Behavior {
+            out_of_range: OutOfRange::Unspecified,
+            nan: Nan::Zero,
+        },
+        F32ToI64 | F64ToI64 => Behavior {
+            out_of_range: OutOfRange::Sentinel(i64::MIN as i128),
+            nan: Nan::Sentinel(i64::MIN as i128),
+        },
+        // Both conversions in the u64 trick return i64::MIN for NaN.
+        F32ToU64 | F64ToU64 => Behavior {
+            out_of_range: OutOfRange::Unspecified,
+            nan: Nan::Sentinel(i64::MIN as u64 as i128),
+        },
+        F32ToI128 | F32ToU128 | F64ToI128 | F64ToU128 => Behavior::SATURATING,
     }
 }
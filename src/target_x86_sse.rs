@@ -1,149 +1,230 @@
-use core::arch::x86::{_mm_cvttsd_si32, _mm_cvttss_si32, _mm_loadu_pd, _mm_loadu_ps};
+use core::arch::x86::{_mm_cvttsd_si32, _mm_cvttss_si32, _mm_set_sd, _mm_set_ss};
 
-use crate::{power_of_two_f32, power_of_two_f64};
+use crate::software::standard;
 
 #[inline(always)]
 fn f32_to_i32(float: f32) -> i32 {
     // see crate::x86_64_sse::f32_to_i64
 
-    let floats = [float, 0., 0., 0.];
-    let floats_pointer = floats.as_ptr();
-    let floats_register = unsafe { _mm_loadu_ps(floats_pointer) };
+    let floats_register = unsafe { _mm_set_ss(float) };
     unsafe { _mm_cvttss_si32(floats_register) }
 }
 
 #[inline(always)]
 fn f32_to_u32(float: f32) -> u32 {
-    // see crate::x86_64_sse::f32_to_u64
-
-    const THRESHOLD: f32 = power_of_two_f32(31);
-
-    let integer1 = f32_to_i32(float);
-    let integer2 = f32_to_i32(float - THRESHOLD);
-    let too_large = integer1 >> 31;
-    (integer1 | (integer2 & too_large)) as u32
+    crate::software::f32_to_u32_via_i32(float, f32_to_i32)
 }
 
 #[inline(always)]
 fn f64_to_i32(float: f64) -> i32 {
     // see crate::x86_64_sse::f64_to_i64
 
-    let floats = [float, 0.];
-    let floats_pointer = floats.as_ptr();
-    let floats_register = unsafe { _mm_loadu_pd(floats_pointer) };
+    let floats_register = unsafe { _mm_set_sd(float) };
     unsafe { _mm_cvttsd_si32(floats_register) }
 }
 
 #[inline(always)]
 fn f64_to_u32(float: f64) -> u32 {
-    // see crate::x86_64_sse::f64_to_u64
-
-    const THRESHOLD: f64 = power_of_two_f64(31);
-
-    let integer1 = f64_to_i32(float);
-    let integer2 = f64_to_i32(float - THRESHOLD);
-    let too_large = integer1 >> 31;
-    (integer1 | (integer2 & too_large)) as u32
+    crate::software::f64_to_u32_via_i32(float, f64_to_i32)
 }
 
-pub mod implementation {
-    #[inline(always)]
-    pub fn f32_to_i8(float: f32) -> i8 {
-        super::f32_to_i32(float) as _
-    }
-
-    #[inline(always)]
-    pub fn f32_to_u8(float: f32) -> u8 {
-        super::f32_to_i32(float) as _
-    }
-
-    #[inline(always)]
-    pub fn f32_to_i16(float: f32) -> i16 {
-        super::f32_to_i32(float) as _
-    }
-
-    #[inline(always)]
-    pub fn f32_to_u16(float: f32) -> u16 {
-        super::f32_to_i32(float) as _
-    }
-
-    #[inline(always)]
-    pub fn f32_to_i32(float: f32) -> i32 {
-        super::f32_to_i32(float) as _
-    }
-
-    #[inline(always)]
-    pub fn f32_to_u32(float: f32) -> u32 {
-        super::f32_to_u32(float) as _
-    }
-
-    #[inline(always)]
-    pub fn f32_to_i64(float: f32) -> i64 {
-        float as _
-    }
-
-    #[inline(always)]
-    pub fn f32_to_u64(float: f32) -> u64 {
-        float as _
-    }
-
-    #[inline(always)]
-    pub fn f32_to_i128(float: f32) -> i128 {
-        float as _
-    }
-
-    #[inline(always)]
-    pub fn f32_to_u128(float: f32) -> u128 {
-        float as _
-    }
-
-    #[inline(always)]
-    pub fn f64_to_i8(float: f64) -> i8 {
-        super::f64_to_i32(float) as _
-    }
-
-    #[inline(always)]
-    pub fn f64_to_u8(float: f64) -> u8 {
-        super::f64_to_i32(float) as _
-    }
-
-    #[inline(always)]
-    pub fn f64_to_i16(float: f64) -> i16 {
-        super::f64_to_i32(float) as _
-    }
-
-    #[inline(always)]
-    pub fn f64_to_u16(float: f64) -> u16 {
-        super::f64_to_i32(float) as _
-    }
-
-    #[inline(always)]
-    pub fn f64_to_i32(float: f64) -> i32 {
-        super::f64_to_i32(float) as _
-    }
+create_implementation! {
+    f32_to_i8: f32_to_i32,
+    f32_to_u8: f32_to_i32,
+    f32_to_i16: f32_to_i32,
+    f32_to_u16: f32_to_i32,
+    f32_to_i32: f32_to_i32,
+    f32_to_u32: f32_to_u32,
+    f32_to_i64: standard::f32_to_i64,
+    f32_to_u64: standard::f32_to_u64,
+    f32_to_i128: standard::f32_to_i128,
+    f32_to_u128: standard::f32_to_u128,
+    f64_to_i8: f64_to_i32,
+    f64_to_u8: f64_to_i32,
+    f64_to_i16: f64_to_i32,
+    f64_to_u16: f64_to_i32,
+    f64_to_i32: f64_to_i32,
+    f64_to_u32: f64_to_u32,
+    f64_to_i64: standard::f64_to_i64,
+    f64_to_u64: standard::f64_to_u64,
+    f64_to_i128: standard::f64_to_i128,
+    f64_to_u128: standard::f64_to_u128,
+}
 
-    #[inline(always)]
-    pub fn f64_to_u32(float: f64) -> u32 {
-        super::f64_to_u32(float) as _
-    }
+// ROUNDSS and ROUNDSD need SSE4.1. Without it the rounding conversions use crate::software::rounding. The module is only used when this target module is active, see crate::rounding.
+#[cfg(all(
+    target_feature = "sse4.1",
+    not(any(feature = "force-default", feature = "portable-deterministic", kani))
+))]
+create_sse41_rounding!();
+
+// see crate::x86_64_sse::kernel
+pub mod kernel {
+    cfg_if::cfg_if! {
+        if #[cfg(target_feature = "sse2")] {
+            use core::arch::x86::{
+                __m128i, _mm_cvttps_epi32, _mm_loadu_pd, _mm_loadu_ps, _mm_packs_epi32,
+                _mm_slli_epi32, _mm_srai_epi32, _mm_storeu_si128, _mm_unpackhi_epi32,
+                _mm_unpackhi_epi64, _mm_unpacklo_epi32, _mm_unpacklo_epi64,
+            };
+
+            use crate::kernel::{Kernel, StereoKernel, TransposeKernel};
+
+            #[inline(always)]
+            fn store_i32(integers_register: __m128i) -> [i32; 4] {
+                let mut integers = [0i32; 4];
+                unsafe { _mm_storeu_si128(integers.as_mut_ptr().cast(), integers_register) };
+                integers
+            }
+
+            /// Convert four f32 to i32 with the CVTTPS2DQ instruction.
+            pub struct F32ToI32;
+
+            impl Kernel<4> for F32ToI32 {
+                type Float = f32;
+                type Integer = i32;
+
+                #[inline(always)]
+                fn convert(floats: [f32; 4]) -> [i32; 4] {
+                    let floats_register = unsafe { _mm_loadu_ps(floats.as_ptr()) };
+                    store_i32(crate::m128_to_i32x4(floats_register))
+                }
+
+                #[inline(always)]
+                fn convert_saturating(floats: [f32; 4]) -> [i32; 4] {
+                    let floats_register = unsafe { _mm_loadu_ps(floats.as_ptr()) };
+                    store_i32(crate::m128_to_i32x4_saturating(floats_register))
+                }
+
+                #[inline(always)]
+                fn convert_round_ties_even(floats: [f32; 4]) -> [i32; 4] {
+                    let floats_register = unsafe { _mm_loadu_ps(floats.as_ptr()) };
+                    store_i32(crate::m128_to_i32x4_round_ties_even(floats_register))
+                }
+            }
+
+            /// Convert two f64 to i32 with the CVTTPD2DQ instruction.
+            pub struct F64ToI32;
+
+            impl Kernel<2> for F64ToI32 {
+                type Float = f64;
+                type Integer = i32;
+
+                #[inline(always)]
+                fn convert(floats: [f64; 2]) -> [i32; 2] {
+                    let floats_register = unsafe { _mm_loadu_pd(floats.as_ptr()) };
+                    let integers = store_i32(crate::m128d_to_i32x2(floats_register));
+                    [integers[0], integers[1]]
+                }
+
+                #[inline(always)]
+                fn convert_saturating(floats: [f64; 2]) -> [i32; 2] {
+                    let floats_register = unsafe { _mm_loadu_pd(floats.as_ptr()) };
+                    let integers = store_i32(crate::m128d_to_i32x2_saturating(floats_register));
+                    [integers[0], integers[1]]
+                }
+
+                #[inline(always)]
+                fn convert_round_ties_even(floats: [f64; 2]) -> [i32; 2] {
+                    let floats_register = unsafe { _mm_loadu_pd(floats.as_ptr()) };
+                    let integers = store_i32(crate::m128d_to_i32x2_round_ties_even(floats_register));
+                    [integers[0], integers[1]]
+                }
+            }
+
+            /// Convert four stereo frames to interleaved i16 with the CVTTPS2DQ, PUNPCKLDQ/PUNPCKHDQ, PSLLD/PSRAD and PACKSSDW instructions.
+            pub struct StereoF32ToI16;
+
+            impl StereoKernel<4> for StereoF32ToI16 {
+                #[inline(always)]
+                fn convert_interleaved(left: [f32; 4], right: [f32; 4]) -> [[i16; 2]; 4] {
+                    let left = unsafe { _mm_cvttps_epi32(_mm_loadu_ps(left.as_ptr())) };
+                    let right = unsafe { _mm_cvttps_epi32(_mm_loadu_ps(right.as_ptr())) };
+                    // The unpack instructions interleave the lower and upper two lanes of both registers.
+                    let first = unsafe { _mm_unpacklo_epi32(left, right) };
+                    let second = unsafe { _mm_unpackhi_epi32(left, right) };
+                    // Sign extending the lower 16 bits truncates like the scalar conversion through i32. The narrowing with signed saturation then keeps every value unchanged.
+                    let truncate = |integers: __m128i| unsafe {
+                        _mm_srai_epi32(_mm_slli_epi32(integers, 16), 16)
+                    };
+                    let packed = unsafe { _mm_packs_epi32(truncate(first), truncate(second)) };
+                    let mut frames = [[0i16; 2]; 4];
+                    unsafe { _mm_storeu_si128(frames.as_mut_ptr().cast(), packed) };
+                    frames
+                }
+            }
+
+            /// Convert a block of four rows of four f32 to i32 with the CVTTPS2DQ instruction and transpose it with the PUNPCKLDQ/PUNPCKHDQ and PUNPCKLQDQ/PUNPCKHQDQ instructions.
+            pub struct TransposeF32ToI32;
+
+            impl TransposeKernel<4> for TransposeF32ToI32 {
+                type Float = f32;
+                type Integer = i32;
+
+                #[inline(always)]
+                fn convert_transposed(rows: [[f32; 4]; 4]) -> [[i32; 4]; 4] {
+                    let [a, b, c, d] =
+                        rows.map(|row| unsafe { _mm_cvttps_epi32(_mm_loadu_ps(row.as_ptr())) });
+                    // a0 b0 a1 b1, c0 d0 c1 d1, a2 b2 a3 b3 and c2 d2 c3 d3.
+                    let ab_low = unsafe { _mm_unpacklo_epi32(a, b) };
+                    let cd_low = unsafe { _mm_unpacklo_epi32(c, d) };
+                    let ab_high = unsafe { _mm_unpackhi_epi32(a, b) };
+                    let cd_high = unsafe { _mm_unpackhi_epi32(c, d) };
+                    // Combining the 64 bit halves gives the columns.
+                    [
+                        store_i32(unsafe { _mm_unpacklo_epi64(ab_low, cd_low) }),
+                        store_i32(unsafe { _mm_unpackhi_epi64(ab_low, cd_low) }),
+                        store_i32(unsafe { _mm_unpacklo_epi64(ab_high, cd_high) }),
+                        store_i32(unsafe { _mm_unpackhi_epi64(ab_high, cd_high) }),
+                    ]
+                }
+            }
+        } else {
+            pub type F32ToI32 = crate::kernel::Scalar<f32, i32>;
+            pub type F64ToI32 = crate::kernel::Scalar<f64, i32>;
+            pub type StereoF32ToI16 = crate::kernel::Scalar<f32, i16>;
+            pub type TransposeF32ToI32 = crate::kernel::Scalar<f32, i32>;
+        }
+    }
+
+    pub type F32ToU8 = crate::kernel::Scalar<f32, u8>;
+    pub type F32ToU16 = crate::kernel::Scalar<f32, u16>;
+}
 
-    #[inline(always)]
-    pub fn f64_to_i64(float: f64) -> i64 {
-        float as _
-    }
+// see crate::target_default::FALLBACK
+#[allow(dead_code)]
+pub const FALLBACK: () = ();
 
-    #[inline(always)]
-    pub fn f64_to_u64(float: f64) -> u64 {
-        float as _
-    }
+pub const fn is_specialized(conversion: crate::Conversion) -> bool {
+    use crate::Conversion::*;
 
-    #[inline(always)]
-    pub fn f64_to_i128(float: f64) -> i128 {
-        float as _
-    }
+    // The 64 bit and 128 bit conversions use the standard conversion.
+    !matches!(
+        conversion,
+        F32ToI64 | F32ToU64 | F32ToI128 | F32ToU128 | F64ToI64 | F64ToU64 | F64ToI128 | F64ToU128
+    )
+}
 
-    #[inline(always)]
-    pub fn f64_to_u128(float: f64) -> u128 {
-        float as _
+pub const fn behavior(conversion: crate::Conversion) -> crate::Behavior {
+    use crate::{Behavior, Conversion::*, Nan, OutOfRange};
+
+    // see crate::x86_64_sse::behavior
+    match conversion {
+        F32ToI8 | F32ToU8 | F32ToI16 | F32ToU16 | F64ToI8 | F64ToU8 | F64ToI16 | F64ToU16 => {
+            Behavior {
+                out_of_range: OutOfRange::Unspecified,
+                nan: Nan::Zero,
+            }
+        }
+        F32ToI32 | F64ToI32 => Behavior {
+            out_of_range: OutOfRange::Sentinel(i32::MIN as i128),
+            nan: Nan::Sentinel(i32::MIN as i128),
+        },
+        F32ToU32 | F64ToU32 => Behavior {
+            out_of_range: OutOfRange::Unspecified,
+            nan: Nan::Sentinel(i32::MIN as u32 as i128),
+        },
+        F32ToI64 | F32ToU64 | F32ToI128 | F32ToU128 | F64ToI64 | F64ToU64 | F64ToI128
+        | F64ToU128 => Behavior::SATURATING,
     }
 }
@@ -0,0 +1,45 @@
+// Conversions that tolerate accumulated floating point error. A computation that should result in 3 can result in 2.9999999, which truncates to 2.
+
+use crate::*;
+
+macro_rules! create_function {
+    ($name:ident, $convert:ident, $Float:ty, $Integer:ty) => {
+        /// Convert the input floating point value to the output integer type. Inputs that are at most `epsilon` closer to 0 than an integer convert to that integer.
+        ///
+        /// This is the truncation of `float + epsilon` for positive inputs and of `float - epsilon` for negative inputs. `epsilon` should not be negative. The addition rounds, so the tolerance is only as precise as the float type at the magnitude of the input.
+        ///
+        /// If the adjusted value is out of range of the output type, then the result is unspecified. Otherwise, the result is the same as the standard `as` conversion of the adjusted value.
+        #[inline(always)]
+        pub fn $name(float: $Float, epsilon: $Float) -> $Integer {
+            // Moving away from 0 keeps the truncation direction. The compiler turns this into a select without branches.
+            let adjusted = if float < 0. {
+                float - epsilon
+            } else {
+                float + epsilon
+            };
+            $convert(adjusted)
+        }
+    };
+}
+
+create_function! {f32_to_i8_tolerant, f32_to_i8, f32, i8}
+create_function! {f32_to_u8_tolerant, f32_to_u8, f32, u8}
+create_function! {f32_to_i16_tolerant, f32_to_i16, f32, i16}
+create_function! {f32_to_u16_tolerant, f32_to_u16, f32, u16}
+create_function! {f32_to_i32_tolerant, f32_to_i32, f32, i32}
+create_function! {f32_to_u32_tolerant, f32_to_u32, f32, u32}
+create_function! {f32_to_i64_tolerant, f32_to_i64, f32, i64}
+create_function! {f32_to_u64_tolerant, f32_to_u64, f32, u64}
+create_function! {f32_to_i128_tolerant, f32_to_i128, f32, i128}
+create_function! {f32_to_u128_tolerant, f32_to_u128, f32, u128}
+
+create_function! {f64_to_i8_tolerant, f64_to_i8, f64, i8}
+create_function! {f64_to_u8_tolerant, f64_to_u8, f64, u8}
+create_function! {f64_to_i16_tolerant, f64_to_i16, f64, i16}
+create_function! {f64_to_u16_tolerant, f64_to_u16, f64, u16}
+create_function! {f64_to_i32_tolerant, f64_to_i32, f64, i32}
+create_function! {f64_to_u32_tolerant, f64_to_u32, f64, u32}
+create_function! {f64_to_i64_tolerant, f64_to_i64, f64, i64}
+create_function! {f64_to_u64_tolerant, f64_to_u64, f64, u64}
+create_function! {f64_to_i128_tolerant, f64_to_i128, f64, i128}
+create_function! {f64_to_u128_tolerant, f64_to_u128, f64, u128}
@@ -0,0 +1,107 @@
+// Events that explain which code path the conversions take. They help to find out why a deployment is slower than expected, for example because it was compiled without a target feature or because the slices are too short for the packed kernels.
+//
+// The events go to a hook instead of the `log` or `tracing` crates so that the crate does not depend on either. Forwarding an event to them is one line in the hook.
+
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Once, RwLock,
+    },
+};
+
+/// An event of the `trace` feature. See [`set_trace_hook`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TraceEvent {
+    /// The implementation that was selected at compile time. The crate has no runtime dispatch, so this is the implementation of every conversion.
+    Selected {
+        /// The name of the target module, for example `target_x86_64_sse`. `target_default` uses the standard `as` operator, see [`is_specialized`](crate::is_specialized).
+        target: &'static str,
+        /// Whether the `small-code` feature makes the slice functions convert one element at a time.
+        small_code: bool,
+    },
+    /// A slice function converted a slice with the scalar path although the conversion has a packed kernel. This happens when the slice is shorter than one kernel call or with the `small-code` feature.
+    ScalarSlice {
+        /// The length of the slice.
+        len: usize,
+        /// The number of elements that the kernel converts at a time.
+        lanes: usize,
+    },
+}
+
+impl fmt::Display for TraceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            TraceEvent::Selected {
+                target,
+                small_code,
+            } => {
+                write!(f, "selected {target}")?;
+                if small_code {
+                    write!(f, ", slices are converted one element at a time")?;
+                }
+                Ok(())
+            }
+            TraceEvent::ScalarSlice { len, lanes } => write!(
+                f,
+                "converted a slice of {len} elements with the scalar path instead of the kernel for {lanes} elements"
+            ),
+        }
+    }
+}
+
+static HOOK: RwLock<Option<fn(&TraceEvent)>> = RwLock::new(None);
+
+/// Receive the events of the `trace` feature.
+///
+/// Every kind of event is emitted once per process, the first time it happens. The events are emitted by the slice functions, which check a flag per call. The scalar conversions are not instrumented because the check would cost more than the conversion.
+///
+/// Without a hook the events are printed to standard error. Set the hook before the first conversion to not miss events. To forward the events to `log` or `tracing`:
+///
+/// ```ignore
+/// fast_float_to_integer::set_trace_hook(|event| log::info!("fast-float-to-integer: {event}"));
+/// ```
+pub fn set_trace_hook(hook: fn(&TraceEvent)) {
+    *HOOK.write().unwrap_or_else(|err| err.into_inner()) = Some(hook);
+}
+
+fn emit(event: TraceEvent) {
+    let hook = *HOOK.read().unwrap_or_else(|err| err.into_inner());
+    match hook {
+        Some(hook) => hook(&event),
+        None => eprintln!("fast-float-to-integer: {event}"),
+    }
+}
+
+#[cold]
+fn first_slice_call() {
+    emit(TraceEvent::Selected {
+        target: crate::ACTIVE_TARGET,
+        small_code: cfg!(feature = "small-code"),
+    });
+}
+
+static SCALAR_SLICE_EMITTED: AtomicBool = AtomicBool::new(false);
+
+#[cold]
+fn scalar_slice(len: usize, lanes: usize) {
+    // Two threads can pass the load at the same time. Only the one that swaps first emits.
+    if !SCALAR_SLICE_EMITTED.swap(true, Ordering::Relaxed) {
+        emit(TraceEvent::ScalarSlice { len, lanes });
+    }
+}
+
+/// Emit the events for a call of a slice function.
+#[inline(always)]
+pub(crate) fn slice_call(len: usize, lanes: usize) {
+    static SELECTED: Once = Once::new();
+    SELECTED.call_once(first_slice_call);
+    // Kernels with one lane are the scalar path.
+    if lanes > 1
+        && (cfg!(feature = "small-code") || len < lanes)
+        && !SCALAR_SLICE_EMITTED.load(Ordering::Relaxed)
+    {
+        scalar_slice(len, lanes);
+    }
+}
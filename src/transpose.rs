@@ -0,0 +1,39 @@
+// Conversion of matrices into their transpose, for example to convert float matrices into integer textures with swapped rows and columns. Converting and transposing in one pass reads and writes the matrices once instead of twice.
+
+use crate::FastFrom;
+
+/// Convert the row major `rows` x `cols` matrix `src` into its transpose, the row major `cols` x `rows` matrix `dst`.
+///
+/// The element in row `r` and column `c` of `src` is converted and written to row `c` and column `r` of `dst`. Equivalently, `dst` is `src` in column major order.
+///
+/// The matrix is converted in square blocks, so that both matrices are accessed in cache lines. On x86 with SSE2 the f32 to i32 conversion converts and transposes blocks of four rows of four elements in registers with the CVTTPS2DQ instruction and unpack instructions. On aarch64 with NEON it uses the FCVTZS and TRN instructions.
+///
+/// # Panics
+///
+/// Panics if `src` or `dst` do not have `rows * cols` elements.
+///
+/// ```
+/// let src = [1.5f32, 2.5, 3.5, -4.5, -5.5, -6.5];
+/// let mut dst = [0i32; 6];
+/// fast_float_to_integer::convert_transpose(&src, 2, 3, &mut dst);
+/// assert_eq!(dst, [1, -4, 2, -5, 3, -6]);
+/// ```
+#[inline]
+pub fn convert_transpose<Float, Integer>(
+    src: &[Float],
+    rows: usize,
+    cols: usize,
+    dst: &mut [Integer],
+) where
+    Float: Copy,
+    Integer: FastFrom<Float>,
+{
+    let len = rows.checked_mul(cols).expect("matrix size overflows");
+    assert_eq!(src.len(), len, "source does not have rows * cols elements");
+    assert_eq!(
+        dst.len(),
+        len,
+        "destination does not have rows * cols elements"
+    );
+    Integer::convert_transpose(src, rows, cols, dst);
+}
@@ -0,0 +1,54 @@
+// Conversions from f64 that first demote the input to f32. f32 conversions process more values per instruction than f64 conversions on targets with packed instructions.
+
+use crate::FastFrom;
+
+macro_rules! create_function {
+    ($name:ident, $Integer:ty) => {
+        /// Convert the input f64 to f32 and then convert the f32 to the output integer type.
+        ///
+        /// The demotion rounds the input to the nearest f32. This is a different result than direct conversion when the input is not exactly representable as f32. For example, `0.99999999` rounds to `1.0` and converts to 1 instead of 0, and `16777217.0` rounds to `16777216.0`. Only use this when the inputs are known to fit comfortably in f32 precision.
+        ///
+        /// If the demoted value is out of range of the output type, then the result is unspecified. Otherwise, the result is the same as `float as f32 as Integer`.
+        #[inline(always)]
+        pub fn $name(float: f64) -> $Integer {
+            <$Integer>::fast_from(float as f32)
+        }
+    };
+}
+
+create_function! {f64_to_i8_via_f32, i8}
+create_function! {f64_to_u8_via_f32, u8}
+create_function! {f64_to_i16_via_f32, i16}
+create_function! {f64_to_u16_via_f32, u16}
+create_function! {f64_to_i32_via_f32, i32}
+create_function! {f64_to_u32_via_f32, u32}
+
+/// Like [`convert_slice`](crate::convert_slice) but every element is converted like [`f64_to_i32_via_f32`].
+///
+/// The elements are demoted in small chunks into a buffer on the stack and then converted with the f32 kernels. See [`f64_to_i32_via_f32`] for the precision caveat.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+///
+/// ```
+/// let src = [1.5f64, -2.5, 0.99999999];
+/// let mut dst = [0i32; 3];
+/// fast_float_to_integer::convert_slice_via_f32(&src, &mut dst);
+/// assert_eq!(dst, [1, -2, 1]);
+/// ```
+#[inline]
+pub fn convert_slice_via_f32<Integer>(src: &[f64], dst: &mut [Integer])
+where
+    Integer: FastFrom<f32>,
+{
+    assert_eq!(src.len(), dst.len(), "slices have different lengths");
+    let mut buffer = [0f32; crate::slice::CHUNK];
+    for (src, dst) in src.chunks(buffer.len()).zip(dst.chunks_mut(buffer.len())) {
+        let buffer = &mut buffer[..src.len()];
+        for (src, buffer) in src.iter().zip(buffer.iter_mut()) {
+            *buffer = *src as f32;
+        }
+        Integer::convert_slice(buffer, dst);
+    }
+}
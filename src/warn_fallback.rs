@@ -0,0 +1,21 @@
+// A warning for builds that do not get a speedup.
+
+#[doc(hidden)]
+#[allow(deprecated)]
+pub use crate::active_target::FALLBACK as __FALLBACK;
+
+/// Warn at compile time if the active target has no specialized implementation.
+///
+/// With the `warn-fallback` feature, invoking this macro in your crate causes a deprecation warning if this crate uses the standard `as` operator for every conversion on the target you compile for. Without the feature the macro does nothing. The macro never causes an error unless you deny warnings.
+///
+/// The warning has to come from your crate because Cargo hides the warnings of dependencies. Invoke the macro once at the top level of a module:
+///
+/// ```
+/// fast_float_to_integer::warn_fallback!();
+/// ```
+#[macro_export]
+macro_rules! warn_fallback {
+    () => {
+        const _: () = $crate::__FALLBACK;
+    };
+}
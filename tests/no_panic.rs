@@ -0,0 +1,263 @@
+// Check at link time that the public conversion functions cannot panic. This is the approach of the no_panic crate.
+//
+// Every function is called between creating and forgetting a guard whose destructor calls a function that does not exist. If the function can panic, then unwinding drops the guard, the call to the missing function stays in the binary and linking fails with the name of the missing function as the error. If the function cannot panic, then the optimizer removes the unwinding path with the call.
+//
+// This needs optimizations, so in debug builds the guard does nothing and the tests only call the functions. `cargo xtask test` and CI build this test in release mode. The `small-code` feature stops inlining the conversions, which hides from the optimizer that they cannot panic, so do not run this test with it.
+//
+// Functions that document a panic, like the slice functions for slices of different lengths and the `expect-finite` functions, are not checked.
+
+use std::hint::black_box;
+
+struct Guard;
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        #[cfg(not(debug_assertions))]
+        {
+            extern "C" {
+                #[link_name = "\n\nERROR: a function in tests/no_panic.rs can panic\n\n"]
+                fn function_can_panic() -> !;
+            }
+            unsafe { function_can_panic() }
+        }
+    }
+}
+
+macro_rules! create_no_panic_test {
+    ($name:ident, $Input:ty, [$($function:expr),+ $(,)?]) => {
+        #[test]
+        fn $name() {
+            $(
+                {
+                    #[inline(never)]
+                    fn check(input: $Input) {
+                        let guard = Guard;
+                        black_box($function(input));
+                        std::mem::forget(guard);
+                    }
+                    check(black_box(<$Input>::default()));
+                }
+            )+
+        }
+    };
+}
+
+use fast_float_to_integer::*;
+
+create_no_panic_test! {conversions_f32, f32, [
+    f32_to_i8, f32_to_u8, f32_to_i16, f32_to_u16,
+    f32_to_i32, f32_to_u32, f32_to_i64, f32_to_u64,
+    f32_to_i128, f32_to_u128,
+]}
+
+create_no_panic_test! {clamp_nearest_f32, f32, [
+    f32_to_i8_clamp_nearest, f32_to_u8_clamp_nearest, f32_to_i16_clamp_nearest, f32_to_u16_clamp_nearest,
+    f32_to_i32_clamp_nearest, f32_to_u32_clamp_nearest, f32_to_i64_clamp_nearest, f32_to_u64_clamp_nearest,
+    f32_to_i128_clamp_nearest, f32_to_u128_clamp_nearest,
+]}
+
+create_no_panic_test! {negative_to_zero_f32, f32, [
+    f32_to_u8_negative_to_zero, f32_to_u16_negative_to_zero, f32_to_u32_negative_to_zero, f32_to_u64_negative_to_zero,
+    f32_to_u128_negative_to_zero,
+]}
+
+create_no_panic_test! {tolerant_f32, f32, [
+    |float| f32_to_i8_tolerant(float, 1e-3), |float| f32_to_u8_tolerant(float, 1e-3), |float| f32_to_i16_tolerant(float, 1e-3), |float| f32_to_u16_tolerant(float, 1e-3),
+    |float| f32_to_i32_tolerant(float, 1e-3), |float| f32_to_u32_tolerant(float, 1e-3), |float| f32_to_i64_tolerant(float, 1e-3), |float| f32_to_u64_tolerant(float, 1e-3),
+    |float| f32_to_i128_tolerant(float, 1e-3), |float| f32_to_u128_tolerant(float, 1e-3),
+]}
+
+create_no_panic_test! {scaled_const_f32, f32, [
+    f32_to_i8_scaled_const::<3, 2>, f32_to_u8_scaled_const::<3, 2>, f32_to_i16_scaled_const::<3, 2>, f32_to_u16_scaled_const::<3, 2>,
+    f32_to_i32_scaled_const::<3, 2>, f32_to_u32_scaled_const::<3, 2>, f32_to_i64_scaled_const::<3, 2>, f32_to_u64_scaled_const::<3, 2>,
+    f32_to_i128_scaled_const::<3, 2>, f32_to_u128_scaled_const::<3, 2>,
+]}
+
+create_no_panic_test! {conversion_error_f32, f32, [
+    conversion_error_f32_to_i8, conversion_error_f32_to_u8, conversion_error_f32_to_i16, conversion_error_f32_to_u16,
+    conversion_error_f32_to_i32, conversion_error_f32_to_u32, conversion_error_f32_to_i64, conversion_error_f32_to_u64,
+    conversion_error_f32_to_i128, conversion_error_f32_to_u128,
+]}
+
+create_no_panic_test! {bits_f32, u32, [
+    f32_bits_to_i8, f32_bits_to_u8, f32_bits_to_i16, f32_bits_to_u16,
+    f32_bits_to_i32, f32_bits_to_u32, f32_bits_to_i64, f32_bits_to_u64,
+    f32_bits_to_i128, f32_bits_to_u128,
+]}
+
+create_no_panic_test! {grid_f32, f32, [
+    f32_to_grid_floor::<16, 1>, f32_to_grid_round::<1, 4>,
+]}
+
+create_no_panic_test! {conversions_f64, f64, [
+    f64_to_i8, f64_to_u8, f64_to_i16, f64_to_u16,
+    f64_to_i32, f64_to_u32, f64_to_i64, f64_to_u64,
+    f64_to_i128, f64_to_u128,
+]}
+
+create_no_panic_test! {clamp_nearest_f64, f64, [
+    f64_to_i8_clamp_nearest, f64_to_u8_clamp_nearest, f64_to_i16_clamp_nearest, f64_to_u16_clamp_nearest,
+    f64_to_i32_clamp_nearest, f64_to_u32_clamp_nearest, f64_to_i64_clamp_nearest, f64_to_u64_clamp_nearest,
+    f64_to_i128_clamp_nearest, f64_to_u128_clamp_nearest,
+]}
+
+create_no_panic_test! {negative_to_zero_f64, f64, [
+    f64_to_u8_negative_to_zero, f64_to_u16_negative_to_zero, f64_to_u32_negative_to_zero, f64_to_u64_negative_to_zero,
+    f64_to_u128_negative_to_zero,
+]}
+
+create_no_panic_test! {tolerant_f64, f64, [
+    |float| f64_to_i8_tolerant(float, 1e-3), |float| f64_to_u8_tolerant(float, 1e-3), |float| f64_to_i16_tolerant(float, 1e-3), |float| f64_to_u16_tolerant(float, 1e-3),
+    |float| f64_to_i32_tolerant(float, 1e-3), |float| f64_to_u32_tolerant(float, 1e-3), |float| f64_to_i64_tolerant(float, 1e-3), |float| f64_to_u64_tolerant(float, 1e-3),
+    |float| f64_to_i128_tolerant(float, 1e-3), |float| f64_to_u128_tolerant(float, 1e-3),
+]}
+
+create_no_panic_test! {scaled_const_f64, f64, [
+    f64_to_i8_scaled_const::<3, 2>, f64_to_u8_scaled_const::<3, 2>, f64_to_i16_scaled_const::<3, 2>, f64_to_u16_scaled_const::<3, 2>,
+    f64_to_i32_scaled_const::<3, 2>, f64_to_u32_scaled_const::<3, 2>, f64_to_i64_scaled_const::<3, 2>, f64_to_u64_scaled_const::<3, 2>,
+    f64_to_i128_scaled_const::<3, 2>, f64_to_u128_scaled_const::<3, 2>,
+]}
+
+create_no_panic_test! {decimal_scaled_f64, f64, [
+    f64_to_i32_decimal_scaled::<2>, f64_to_i64_decimal_scaled::<2>, f64_to_i64_decimal_scaled::<38>,
+]}
+
+create_no_panic_test! {conversion_error_f64, f64, [
+    conversion_error_f64_to_i8, conversion_error_f64_to_u8, conversion_error_f64_to_i16, conversion_error_f64_to_u16,
+    conversion_error_f64_to_i32, conversion_error_f64_to_u32, conversion_error_f64_to_i64, conversion_error_f64_to_u64,
+    conversion_error_f64_to_i128, conversion_error_f64_to_u128,
+]}
+
+create_no_panic_test! {bits_f64, u64, [
+    f64_bits_to_i8, f64_bits_to_u8, f64_bits_to_i16, f64_bits_to_u16,
+    f64_bits_to_i32, f64_bits_to_u32, f64_bits_to_i64, f64_bits_to_u64,
+    f64_bits_to_i128, f64_bits_to_u128,
+]}
+
+create_no_panic_test! {grid_f64, f64, [
+    f64_to_grid_floor::<16, 1>, f64_to_grid_round::<1, 4>,
+]}
+
+create_no_panic_test! {via_f32, f64, [
+    f64_to_i8_via_f32, f64_to_u8_via_f32, f64_to_i16_via_f32, f64_to_u16_via_f32,
+    f64_to_i32_via_f32, f64_to_u32_via_f32,
+]}
+
+create_no_panic_test! {normalized, f32, [
+    unorm_f32_to_u8, unorm_f32_to_u16, snorm_f32_to_i8, snorm_f32_to_i16,
+    srgb_f32_to_u8, depth_f32_to_d24, depth_f32_to_d16,
+]}
+
+create_no_panic_test! {pack_r11g11b10, [f32; 3], [
+    pack_unorm_r11g11b10,
+]}
+
+create_no_panic_test! {pack_r10g10b10a2, [f32; 4], [
+    pack_unorm_r10g10b10a2,
+]}
+
+create_no_panic_test! {nan_boxed, u64, [
+    nan_boxed_to_i8, nan_boxed_to_u8, nan_boxed_to_i16, nan_boxed_to_u16,
+    nan_boxed_to_i32, nan_boxed_to_u32, nan_boxed_to_i64, nan_boxed_to_u64,
+    nan_boxed_to_i128, nan_boxed_to_u128,
+]}
+
+create_no_panic_test! {with_fract_f32, f32, [f32_to_i32_with_fract, f32_to_u32_with_fract]}
+
+create_no_panic_test! {with_fract_f64, f64, [f64_to_i32_with_fract, f64_to_u32_with_fract]}
+
+create_no_panic_test! {seconds, f64, [f64_to_seconds_nanos, f64_to_duration]}
+
+// Only release builds are checked, where the debug assertion does not exist.
+create_no_panic_test! {pretruncated_f32, f32, [
+    f32_to_i8_pretruncated, f32_to_u8_pretruncated, f32_to_i16_pretruncated, f32_to_u16_pretruncated,
+    f32_to_i32_pretruncated, f32_to_u32_pretruncated, f32_to_i64_pretruncated, f32_to_u64_pretruncated,
+    f32_to_i128_pretruncated, f32_to_u128_pretruncated,
+]}
+
+create_no_panic_test! {pretruncated_f64, f64, [
+    f64_to_i8_pretruncated, f64_to_u8_pretruncated, f64_to_i16_pretruncated, f64_to_u16_pretruncated,
+    f64_to_i32_pretruncated, f64_to_u32_pretruncated, f64_to_i64_pretruncated, f64_to_u64_pretruncated,
+    f64_to_i128_pretruncated, f64_to_u128_pretruncated,
+]}
+
+create_no_panic_test! {small_f32, f32, [
+    f32_to_i8_small, f32_to_u8_small, f32_to_i16_small, f32_to_u16_small,
+    f32_to_i32_small, f32_to_u32_small, f32_to_i64_small, f32_to_u64_small,
+    f32_to_i128_small, f32_to_u128_small,
+]}
+
+create_no_panic_test! {small_f64, f64, [
+    f64_to_i8_small, f64_to_u8_small, f64_to_i16_small, f64_to_u16_small,
+    f64_to_i32_small, f64_to_u32_small, f64_to_i64_small, f64_to_u64_small,
+    f64_to_i128_small, f64_to_u128_small,
+]}
+
+create_no_panic_test! {floor_f32, f32, [
+    f32_to_i8_floor, f32_to_u8_floor, f32_to_i16_floor, f32_to_u16_floor,
+    f32_to_i32_floor, f32_to_u32_floor, f32_to_i64_floor, f32_to_u64_floor,
+    f32_to_i128_floor, f32_to_u128_floor,
+]}
+
+create_no_panic_test! {ceil_f32, f32, [
+    f32_to_i8_ceil, f32_to_u8_ceil, f32_to_i16_ceil, f32_to_u16_ceil,
+    f32_to_i32_ceil, f32_to_u32_ceil, f32_to_i64_ceil, f32_to_u64_ceil,
+    f32_to_i128_ceil, f32_to_u128_ceil,
+]}
+
+create_no_panic_test! {round_f32, f32, [
+    f32_to_i8_round, f32_to_u8_round, f32_to_i16_round, f32_to_u16_round,
+    f32_to_i32_round, f32_to_u32_round, f32_to_i64_round, f32_to_u64_round,
+    f32_to_i128_round, f32_to_u128_round,
+]}
+
+create_no_panic_test! {round_ties_even_f32, f32, [
+    f32_to_i8_round_ties_even, f32_to_u8_round_ties_even, f32_to_i16_round_ties_even, f32_to_u16_round_ties_even,
+    f32_to_i32_round_ties_even, f32_to_u32_round_ties_even, f32_to_i64_round_ties_even, f32_to_u64_round_ties_even,
+    f32_to_i128_round_ties_even, f32_to_u128_round_ties_even,
+]}
+
+create_no_panic_test! {floor_f64, f64, [
+    f64_to_i8_floor, f64_to_u8_floor, f64_to_i16_floor, f64_to_u16_floor,
+    f64_to_i32_floor, f64_to_u32_floor, f64_to_i64_floor, f64_to_u64_floor,
+    f64_to_i128_floor, f64_to_u128_floor,
+]}
+
+create_no_panic_test! {ceil_f64, f64, [
+    f64_to_i8_ceil, f64_to_u8_ceil, f64_to_i16_ceil, f64_to_u16_ceil,
+    f64_to_i32_ceil, f64_to_u32_ceil, f64_to_i64_ceil, f64_to_u64_ceil,
+    f64_to_i128_ceil, f64_to_u128_ceil,
+]}
+
+create_no_panic_test! {round_f64, f64, [
+    f64_to_i8_round, f64_to_u8_round, f64_to_i16_round, f64_to_u16_round,
+    f64_to_i32_round, f64_to_u32_round, f64_to_i64_round, f64_to_u64_round,
+    f64_to_i128_round, f64_to_u128_round,
+]}
+
+create_no_panic_test! {round_ties_even_f64, f64, [
+    f64_to_i8_round_ties_even, f64_to_u8_round_ties_even, f64_to_i16_round_ties_even, f64_to_u16_round_ties_even,
+    f64_to_i32_round_ties_even, f64_to_u32_round_ties_even, f64_to_i64_round_ties_even, f64_to_u64_round_ties_even,
+    f64_to_i128_round_ties_even, f64_to_u128_round_ties_even,
+]}
+
+#[cfg(feature = "arbitrary-int")]
+create_no_panic_test! {arbitrary_int_f32, f32, [f32_to_arbitrary_int::<arbitrary_int::u12>, f32_to_arbitrary_int::<arbitrary_int::i4>]}
+
+#[cfg(feature = "arbitrary-int")]
+create_no_panic_test! {arbitrary_int_f64, f64, [f64_to_arbitrary_int::<arbitrary_int::u24>, f64_to_arbitrary_int::<arbitrary_int::i63>]}
+
+// The policies of the generic entry point, including the checked and saturating conversions.
+create_no_panic_test! {convert_with_f32, f32, [
+    convert_with::<Unspecified, f32, u8>, convert_with::<Unspecified, f32, i32>, convert_with::<Unspecified, f32, u64>, convert_with::<Unspecified, f32, i128>,
+    convert_with::<Saturate, f32, u8>, convert_with::<Saturate, f32, i32>, convert_with::<Saturate, f32, u64>, convert_with::<Saturate, f32, i128>,
+    convert_with::<Wrap, f32, u8>, convert_with::<Wrap, f32, i32>, convert_with::<Wrap, f32, u64>, convert_with::<Wrap, f32, i128>,
+    convert_with::<Checked, f32, u8>, convert_with::<Checked, f32, i32>, convert_with::<Checked, f32, u64>, convert_with::<Checked, f32, i128>,
+]}
+
+create_no_panic_test! {convert_with_f64, f64, [
+    convert_with::<Unspecified, f64, i8>, convert_with::<Unspecified, f64, u32>, convert_with::<Unspecified, f64, i64>, convert_with::<Unspecified, f64, u128>,
+    convert_with::<Saturate, f64, i8>, convert_with::<Saturate, f64, u32>, convert_with::<Saturate, f64, i64>, convert_with::<Saturate, f64, u128>,
+    convert_with::<Wrap, f64, i8>, convert_with::<Wrap, f64, u32>, convert_with::<Wrap, f64, i64>, convert_with::<Wrap, f64, u128>,
+    convert_with::<Checked, f64, i8>, convert_with::<Checked, f64, u32>, convert_with::<Checked, f64, i64>, convert_with::<Checked, f64, u128>,
+]}
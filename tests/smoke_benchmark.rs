@@ -0,0 +1,74 @@
+// A lightweight benchmark that can run under qemu, where the criterion benchmarks cannot. It does not produce precise numbers. It reports how much slower or faster each conversion is than the standard `as` operator, which shows pathological regressions like a bad implementation being selected. It does not fail on slow timings, because the tests run in parallel and wall-clock times on loaded, shared or emulated machines are too noisy for that. The instruction budgets of `cargo xtask asm` catch a wrongly selected fallback deterministically.
+//
+// Timing is only meaningful with optimizations. `cargo xtask test` runs this test in release mode.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+// See the benchmark crate for why we create a dependency between the converted numbers. We take the fastest of several runs to reduce noise.
+macro_rules! measure {
+    ($function:expr, $floats:expr) => {{
+        let mut fastest = Duration::MAX;
+        for _ in 0..20 {
+            let start = Instant::now();
+            let mut result = 0;
+            for float in black_box($floats) {
+                result ^= $function(*float);
+            }
+            black_box(result);
+            fastest = fastest.min(start.elapsed());
+        }
+        fastest
+    }};
+}
+
+macro_rules! create_smoke_benchmark {
+    ($name:ident, $function:path, $Float:ty, $Integer:ty) => {
+        #[test]
+        fn $name() {
+            let floats: Vec<$Float> = (0..10_000).map(|i| (i % 100) as $Float + 0.5).collect();
+            for float in &floats {
+                assert_eq!($function(*float), *float as $Integer);
+            }
+
+            let fast = measure!($function, floats.as_slice());
+            let standard = measure!(|float: $Float| float as $Integer, floats.as_slice());
+            // `cargo xtask speedup-report` reads this line.
+            println!(
+                "speedup-report {} {} {}",
+                stringify!($name),
+                fast.as_nanos(),
+                standard.as_nanos()
+            );
+            println!(
+                "{}: fast {fast:?} standard {standard:?} ratio {:.2}",
+                stringify!($name),
+                fast.as_secs_f64() / standard.as_secs_f64()
+            );
+        }
+    };
+}
+
+create_smoke_benchmark! {f32_to_i8, fast_float_to_integer::f32_to_i8, f32, i8}
+create_smoke_benchmark! {f32_to_u8, fast_float_to_integer::f32_to_u8, f32, u8}
+create_smoke_benchmark! {f32_to_i16, fast_float_to_integer::f32_to_i16, f32, i16}
+create_smoke_benchmark! {f32_to_u16, fast_float_to_integer::f32_to_u16, f32, u16}
+create_smoke_benchmark! {f32_to_i32, fast_float_to_integer::f32_to_i32, f32, i32}
+create_smoke_benchmark! {f32_to_u32, fast_float_to_integer::f32_to_u32, f32, u32}
+create_smoke_benchmark! {f32_to_i64, fast_float_to_integer::f32_to_i64, f32, i64}
+create_smoke_benchmark! {f32_to_u64, fast_float_to_integer::f32_to_u64, f32, u64}
+create_smoke_benchmark! {f32_to_i128, fast_float_to_integer::f32_to_i128, f32, i128}
+create_smoke_benchmark! {f32_to_u128, fast_float_to_integer::f32_to_u128, f32, u128}
+
+create_smoke_benchmark! {f64_to_i8, fast_float_to_integer::f64_to_i8, f64, i8}
+create_smoke_benchmark! {f64_to_u8, fast_float_to_integer::f64_to_u8, f64, u8}
+create_smoke_benchmark! {f64_to_i16, fast_float_to_integer::f64_to_i16, f64, i16}
+create_smoke_benchmark! {f64_to_u16, fast_float_to_integer::f64_to_u16, f64, u16}
+create_smoke_benchmark! {f64_to_i32, fast_float_to_integer::f64_to_i32, f64, i32}
+create_smoke_benchmark! {f64_to_u32, fast_float_to_integer::f64_to_u32, f64, u32}
+create_smoke_benchmark! {f64_to_i64, fast_float_to_integer::f64_to_i64, f64, i64}
+create_smoke_benchmark! {f64_to_u64, fast_float_to_integer::f64_to_u64, f64, u64}
+create_smoke_benchmark! {f64_to_i128, fast_float_to_integer::f64_to_i128, f64, i128}
+create_smoke_benchmark! {f64_to_u128, fast_float_to_integer::f64_to_u128, f64, u128}
@@ -160,3 +160,2508 @@ create_interesting_floats_test! {interesting_f64_i64, interesting_floats_f64, fa
 create_interesting_floats_test! {interesting_f64_u64, interesting_floats_f64, fast_float_to_integer::f64_to_u64, u64}
 create_interesting_floats_test! {interesting_f64_i128, interesting_floats_f64, fast_float_to_integer::f64_to_i128, i128}
 create_interesting_floats_test! {interesting_f64_u128, interesting_floats_f64, fast_float_to_integer::f64_to_u128, u128}
+
+#[test]
+fn convert_slice_pairs() {
+    let src: Vec<f32> = interesting_floats_f32()
+        .filter(|float| InRange::<i16>::in_range(*float))
+        .collect();
+    let src = &src[..src.len() / 2 * 2];
+    let mut dst = vec![(0i16, 0i16); src.len() / 2];
+    fast_float_to_integer::convert_slice_pairs(src, &mut dst);
+    for (src, dst) in src.chunks_exact(2).zip(&dst) {
+        assert_eq!(*dst, (src[0] as i16, src[1] as i16));
+    }
+
+    let (first, second) = src.split_at(src.len() / 2);
+    fast_float_to_integer::convert_slices_to_pairs(first, second, &mut dst[..first.len()]);
+    for ((first, second), dst) in first.iter().zip(second).zip(&dst) {
+        assert_eq!(*dst, (*first as i16, *second as i16));
+    }
+
+    // Longer than several chunks and with a packed kernel.
+    let src: Vec<f32> = (0..602).map(|i| (i - 300) as f32 * 1.75).collect();
+    let mut dst = vec![(0i32, 0i32); 301];
+    fast_float_to_integer::convert_slice_pairs(&src, &mut dst);
+    for (src, dst) in src.chunks_exact(2).zip(&dst) {
+        assert_eq!(*dst, (src[0] as i32, src[1] as i32));
+    }
+    let (first, second) = src.split_at(301);
+    fast_float_to_integer::convert_slices_to_pairs(first, second, &mut dst);
+    for ((first, second), dst) in first.iter().zip(second).zip(&dst) {
+        assert_eq!(*dst, (*first as i32, *second as i32));
+    }
+}
+
+#[test]
+fn convert_slice_uninit() {
+    let src: Vec<f64> = interesting_floats_f64()
+        .filter(|float| InRange::<u32>::in_range(*float))
+        .collect();
+    let mut dst = Vec::<u32>::with_capacity(src.len());
+    let converted = fast_float_to_integer::convert_slice_uninit(&src, dst.spare_capacity_mut());
+    for (src, dst) in src.iter().zip(converted.iter()) {
+        assert_eq!(*dst, *src as u32);
+    }
+
+    // f32 to i32 has a packed kernel on some targets, which stores directly into the uninitialized memory. The length is not a multiple of the lane count.
+    let src: Vec<f32> = (0..103).map(|i| i as f32 * -1.5).collect();
+    let mut dst = Vec::<i32>::with_capacity(src.len());
+    let converted = fast_float_to_integer::convert_slice_uninit(&src, dst.spare_capacity_mut());
+    for (src, dst) in src.iter().zip(converted.iter()) {
+        assert_eq!(*dst, *src as i32);
+    }
+}
+
+#[test]
+fn kernel() {
+    use fast_float_to_integer::kernel::{F32ToI32, F64ToI32, Kernel};
+
+    assert_eq!(F32ToI32::convert([1.5, -2.5, 3.0, -0.5]), [1, -2, 3, 0]);
+    assert_eq!(F64ToI32::convert([1.5, -2.5]), [1, -2]);
+
+    // Lengths that are not a multiple of the lane count exercise the remainder handling.
+    let src: Vec<f32> = interesting_floats_f32()
+        .filter(|float| InRange::<i32>::in_range(*float))
+        .take(103)
+        .collect();
+    let mut dst = vec![0i32; src.len()];
+    fast_float_to_integer::convert_slice(&src, &mut dst);
+    for (src, dst) in src.iter().zip(&dst) {
+        assert_eq!(*dst, *src as i32, "{src:.0}");
+    }
+
+    let src: Vec<f64> = interesting_floats_f64()
+        .filter(|float| InRange::<i32>::in_range(*float))
+        .take(101)
+        .collect();
+    let mut dst = vec![0i32; src.len()];
+    fast_float_to_integer::convert_slice(&src, &mut dst);
+    for (src, dst) in src.iter().zip(&dst) {
+        assert_eq!(*dst, *src as i32, "{src:.0}");
+    }
+
+    let src: Vec<f32> = interesting_floats_f32()
+        .filter(|float| InRange::<u16>::in_range(*float))
+        .take(37)
+        .collect();
+    let mut dst = vec![0u16; src.len()];
+    fast_float_to_integer::convert_slice(&src, &mut dst);
+    for (src, dst) in src.iter().zip(&dst) {
+        assert_eq!(*dst, *src as u16, "{src:.0}");
+    }
+}
+
+macro_rules! create_saturating_convert_slice_test {
+    ($name:ident, $interesting_floats_function:ident, $Float:ty, $Integer:ty) => {
+        #[test]
+        fn $name() {
+            let src: Vec<$Float> = $interesting_floats_function()
+                .chain([<$Float>::NAN, <$Float>::INFINITY, <$Float>::NEG_INFINITY])
+                .collect();
+            let mut dst = vec![0 as $Integer; src.len()];
+            fast_float_to_integer::saturating_convert_slice(&src, &mut dst);
+            for (src, dst) in src.iter().zip(&dst) {
+                assert_eq!(*dst, *src as $Integer, "{src:.0}");
+            }
+        }
+    };
+}
+
+create_saturating_convert_slice_test! {saturating_convert_slice_f32_i32, interesting_floats_f32, f32, i32}
+create_saturating_convert_slice_test! {saturating_convert_slice_f32_u8, interesting_floats_f32, f32, u8}
+create_saturating_convert_slice_test! {saturating_convert_slice_f32_u16, interesting_floats_f32, f32, u16}
+create_saturating_convert_slice_test! {saturating_convert_slice_f64_i32, interesting_floats_f64, f64, i32}
+create_saturating_convert_slice_test! {saturating_convert_slice_f64_u64, interesting_floats_f64, f64, u64}
+
+// Like the differential tests below, but with the interesting floats, which contain every boundary of the NEON kernels.
+macro_rules! create_neon_kernel_test {
+    ($name:ident, $interesting_floats_function:ident, $Float:ty, $Integer:ty) => {
+        #[cfg(all(
+            target_arch = "aarch64",
+            target_feature = "neon",
+            not(any(feature = "force-default", feature = "portable-deterministic"))
+        ))]
+        #[test]
+        fn $name() {
+            use fast_float_to_integer::FastFrom;
+
+            let src: Vec<$Float> = $interesting_floats_function()
+                .chain([<$Float>::NAN, <$Float>::INFINITY, <$Float>::NEG_INFINITY])
+                .collect();
+            let mut dst = vec![0 as $Integer; src.len()];
+            let mut rounded = vec![0 as $Integer; src.len()];
+            fast_float_to_integer::convert_slice(&src, &mut dst);
+            fast_float_to_integer::round_ties_even_convert_slice(&src, &mut rounded);
+            for ((src, dst), rounded) in src.iter().zip(&dst).zip(&rounded) {
+                assert_eq!(*dst, <$Integer>::fast_from(*src), "{src:.0}");
+                let expected = <$Integer>::fast_from(round_ties_even(*src as f64) as $Float);
+                assert_eq!(*rounded, expected, "{src:.0}");
+            }
+        }
+    };
+}
+
+create_neon_kernel_test! {neon_kernel_f32_i32, interesting_floats_f32, f32, i32}
+create_neon_kernel_test! {neon_kernel_f32_u8, interesting_floats_f32, f32, u8}
+create_neon_kernel_test! {neon_kernel_f32_u16, interesting_floats_f32, f32, u16}
+create_neon_kernel_test! {neon_kernel_f64_i32, interesting_floats_f64, f64, i32}
+
+/// A deterministic pseudo random number generator for the property tests. This is xorshift64*. It avoids a dependency and makes failures reproducible.
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Half of the values are arbitrary bit patterns including NaN, infinities and huge values. The other half are small enough to be in range of most integer types.
+    fn float<Float: RandomFloat>(&mut self) -> Float {
+        if self.next() % 2 == 0 {
+            Float::from_random_bits(self.next())
+        } else {
+            let integer = self.next() as i64 >> (self.next() % 64);
+            let fraction = (self.next() % 1024) as f64 / 1024.;
+            Float::from_f64(integer as f64 + fraction)
+        }
+    }
+}
+
+trait RandomFloat: Copy {
+    fn from_random_bits(bits: u64) -> Self;
+    fn from_f64(float: f64) -> Self;
+}
+
+impl RandomFloat for f32 {
+    fn from_random_bits(bits: u64) -> Self {
+        f32::from_bits(bits as u32)
+    }
+
+    fn from_f64(float: f64) -> Self {
+        float as f32
+    }
+}
+
+impl RandomFloat for f64 {
+    fn from_random_bits(bits: u64) -> Self {
+        f64::from_bits(bits)
+    }
+
+    fn from_f64(float: f64) -> Self {
+        float
+    }
+}
+
+// `convert_slice` guarantees exactly the same results as the scalar functions for every input, not only for in range inputs. The packed kernels process several lanes at once and the remainder with the scalar function, so a difference between them shows up as results that depend on the position in the slice.
+macro_rules! create_slice_differential_test {
+    ($name:ident, $convert_custom:path, $Float:ty, $Integer:ty) => {
+        #[test]
+        fn $name() {
+            let mut random = Random(0x9e37_79b9_7f4a_7c15);
+            for _ in 0..500 {
+                // Random lengths cover the head, whole kernels and the tail. Random offsets into the buffer cover starts that are not aligned to the vector size.
+                let length = (random.next() % 70) as usize;
+                let offset = (random.next() % 4) as usize;
+                let buffer: Vec<$Float> = (0..offset + length).map(|_| random.float()).collect();
+                let src = &buffer[offset..];
+                let mut dst = vec![0 as $Integer; length];
+
+                fast_float_to_integer::convert_slice(src, &mut dst);
+                let expected: Vec<$Integer> =
+                    src.iter().map(|float| $convert_custom(*float)).collect();
+                assert_eq!(dst, expected, "{src:?}");
+
+                fast_float_to_integer::saturating_convert_slice(src, &mut dst);
+                let expected: Vec<$Integer> = src.iter().map(|float| *float as $Integer).collect();
+                assert_eq!(dst, expected, "{src:?}");
+
+                fast_float_to_integer::round_ties_even_convert_slice(src, &mut dst);
+                let expected: Vec<$Integer> = src
+                    .iter()
+                    .map(|float| {
+                        // The round trip through f64 can change the payload of NaN, which the portable-deterministic conversions depend on.
+                        let rounded = if float.is_nan() {
+                            *float
+                        } else {
+                            round_ties_even(*float as f64) as $Float
+                        };
+                        $convert_custom(rounded)
+                    })
+                    .collect();
+                assert_eq!(dst, expected, "{src:?}");
+            }
+        }
+    };
+}
+
+create_slice_differential_test! {slice_differential_f32_i32, fast_float_to_integer::f32_to_i32, f32, i32}
+create_slice_differential_test! {slice_differential_f32_u8, fast_float_to_integer::f32_to_u8, f32, u8}
+create_slice_differential_test! {slice_differential_f32_i16, fast_float_to_integer::f32_to_i16, f32, i16}
+create_slice_differential_test! {slice_differential_f32_u16, fast_float_to_integer::f32_to_u16, f32, u16}
+create_slice_differential_test! {slice_differential_f32_u64, fast_float_to_integer::f32_to_u64, f32, u64}
+create_slice_differential_test! {slice_differential_f64_i32, fast_float_to_integer::f64_to_i32, f64, i32}
+create_slice_differential_test! {slice_differential_f64_u32, fast_float_to_integer::f64_to_u32, f64, u32}
+create_slice_differential_test! {slice_differential_f64_i64, fast_float_to_integer::f64_to_i64, f64, i64}
+
+// Like the slice functions, the transpose must give the same results as the scalar function for every input. The shapes cover whole blocks, partial blocks at the right and at the bottom, and empty matrices.
+macro_rules! create_transpose_test {
+    ($name:ident, $convert:path, $Float:ty, $Integer:ty) => {
+        #[test]
+        fn $name() {
+            let mut random = Random(0x853c_49e6_748f_ea9b);
+            for rows in [0, 1, 3, 4, 8, 9, 17] {
+                for cols in [0, 1, 4, 7, 8, 16, 21] {
+                    let src: Vec<$Float> = (0..rows * cols).map(|_| random.float()).collect();
+                    let mut dst = vec![0 as $Integer; rows * cols];
+                    fast_float_to_integer::convert_transpose(&src, rows, cols, &mut dst);
+                    for row in 0..rows {
+                        for col in 0..cols {
+                            let float = src[row * cols + col];
+                            assert_eq!(
+                                dst[col * rows + row],
+                                $convert(float),
+                                "{rows} {cols} {float}"
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    };
+}
+
+create_transpose_test! {transpose_f32_i32, fast_float_to_integer::f32_to_i32, f32, i32}
+create_transpose_test! {transpose_f32_u8, fast_float_to_integer::f32_to_u8, f32, u8}
+create_transpose_test! {transpose_f64_i64, fast_float_to_integer::f64_to_i64, f64, i64}
+
+#[test]
+#[should_panic = "source does not have rows * cols elements"]
+fn transpose_wrong_length() {
+    fast_float_to_integer::convert_transpose(&[1f32; 5], 2, 3, &mut [0i32; 6]);
+}
+
+// The parallel conversion must write the same values as the serial one for every chunk size, including the unspecified results of out of range inputs and NaN.
+#[cfg(feature = "rayon")]
+macro_rules! create_par_convert_slice_test {
+    ($name:ident, $Float:ty, $Integer:ty) => {
+        #[test]
+        fn $name() {
+            let mut random = Random(0x2545_f491_4f6c_dd1d);
+            let src: Vec<$Float> = (0..10_007).map(|_| random.float()).collect();
+            let mut expected = vec![0 as $Integer; src.len()];
+            fast_float_to_integer::convert_slice(&src, &mut expected);
+
+            let mut dst = vec![0 as $Integer; src.len()];
+            fast_float_to_integer::par_convert_slice(&src, &mut dst);
+            assert_eq!(dst, expected);
+            for chunk_size in [1, 3, 17, 1000, usize::MAX] {
+                let mut dst = vec![0 as $Integer; src.len()];
+                fast_float_to_integer::par_convert_slice_with_chunk_size(
+                    &src, &mut dst, chunk_size,
+                );
+                assert_eq!(dst, expected, "{chunk_size}");
+            }
+        }
+    };
+}
+
+#[cfg(feature = "rayon")]
+create_par_convert_slice_test! {par_convert_slice_f32_i32, f32, i32}
+#[cfg(feature = "rayon")]
+create_par_convert_slice_test! {par_convert_slice_f32_u8, f32, u8}
+#[cfg(feature = "rayon")]
+create_par_convert_slice_test! {par_convert_slice_f32_u64, f32, u64}
+#[cfg(feature = "rayon")]
+create_par_convert_slice_test! {par_convert_slice_f64_i32, f64, i32}
+
+#[test]
+fn stereo_differential() {
+    // NaN is unspecified after the clamping, so only finite and infinite samples are compared.
+    let mut random = Random(0x2545_f491_4f6c_dd1d);
+    let mut sample = || loop {
+        let float: f32 = random.float();
+        if !float.is_nan() {
+            // Most random floats are outside of [-1, 1]. Scale some into it.
+            break if float.abs() > 1. && random.next() % 2 == 0 {
+                1. / float
+            } else {
+                float
+            };
+        }
+    };
+    for length in 0..40 {
+        let left: Vec<f32> = (0..length).map(|_| sample()).collect();
+        let right: Vec<f32> = (0..length).map(|_| sample()).collect();
+        let mut out = vec![0i16; 2 * length];
+        fast_float_to_integer::convert_stereo_f32_to_i16_interleaved(&left, &right, &mut out);
+        let expected: Vec<i16> = left
+            .iter()
+            .zip(&right)
+            .flat_map(|(left, right)| [*left, *right])
+            .map(|sample| fast_float_to_integer::f32_to_i16(sample.clamp(-1., 1.) * 32767.))
+            .collect();
+        assert_eq!(out, expected, "{left:?} {right:?}");
+    }
+}
+
+#[test]
+fn convert_in_place() {
+    // More elements than one chunk of the internal buffer.
+    let src: Vec<f32> = interesting_floats_f32()
+        .filter(|float| InRange::<i32>::in_range(*float))
+        .take(150)
+        .collect();
+    let mut values = src.clone();
+    let integers: &mut [i32] = fast_float_to_integer::convert_in_place(&mut values);
+    assert_eq!(integers.len(), src.len());
+    for (src, integer) in src.iter().zip(integers.iter()) {
+        assert_eq!(*integer, *src as i32, "{src:.0}");
+    }
+
+    let mut values = [1.5f64, 2.0f64.powi(63) + 2.0f64.powi(11)];
+    let integers: &mut [u64] = fast_float_to_integer::convert_in_place(&mut values);
+    assert_eq!(integers, [1, (1 << 63) + (1 << 11)]);
+}
+
+#[test]
+fn convert_bytes() {
+    // More elements than one chunk of the internal buffer.
+    let src: Vec<f32> = interesting_floats_f32()
+        .filter(|float| InRange::<i32>::in_range(*float))
+        .take(150)
+        .collect();
+    let expected: Vec<i32> = src.iter().map(|float| *float as i32).collect();
+    let bytes: Vec<u8> = src.iter().flat_map(|float| float.to_le_bytes()).collect();
+    let mut dst = vec![0i32; src.len()];
+    fast_float_to_integer::convert_le_bytes::<f32, i32>(&bytes, &mut dst);
+    assert_eq!(dst, expected);
+    // An unaligned start.
+    let bytes: Vec<u8> = [0u8]
+        .into_iter()
+        .chain(src.iter().flat_map(|float| float.to_be_bytes()))
+        .collect();
+    let mut dst = vec![0i32; src.len()];
+    fast_float_to_integer::convert_be_bytes::<f32, i32>(&bytes[1..], &mut dst);
+    assert_eq!(dst, expected);
+
+    let src: Vec<f64> = interesting_floats_f64()
+        .filter(|float| InRange::<u16>::in_range(*float))
+        .collect();
+    let expected: Vec<u16> = src.iter().map(|float| *float as u16).collect();
+    let bytes: Vec<u8> = src.iter().flat_map(|float| float.to_be_bytes()).collect();
+    let mut dst = vec![0u16; src.len()];
+    fast_float_to_integer::convert_be_bytes::<f64, u16>(&bytes, &mut dst);
+    assert_eq!(dst, expected);
+}
+
+#[test]
+fn convert_ne_bytes() {
+    let src: Vec<f64> = interesting_floats_f64()
+        .filter(|float| InRange::<i64>::in_range(*float))
+        .collect();
+    let expected: Vec<i64> = src.iter().map(|float| *float as i64).collect();
+    // An f64 buffer guarantees the alignment. Both an aligned and an unaligned view of the bytes are converted.
+    let mut buffer = vec![0f64; src.len() + 1];
+    let bytes: Vec<u8> = src.iter().flat_map(|float| float.to_ne_bytes()).collect();
+    let buffer_bytes = unsafe {
+        core::slice::from_raw_parts_mut(buffer.as_mut_ptr().cast::<u8>(), buffer.len() * 8)
+    };
+    for offset in [0, 3] {
+        buffer_bytes[offset..][..bytes.len()].copy_from_slice(&bytes);
+        let mut dst = vec![0i64; src.len()];
+        fast_float_to_integer::convert_ne_bytes::<f64, i64>(
+            &buffer_bytes[offset..][..bytes.len()],
+            &mut dst,
+        );
+        assert_eq!(dst, expected, "{offset}");
+    }
+}
+
+#[test]
+fn convert_atomic_slice() {
+    use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+
+    let src: Vec<f32> = interesting_floats_f32()
+        .filter(|float| InRange::<i16>::in_range(*float))
+        .collect();
+    let atomics: Vec<AtomicU32> = src
+        .iter()
+        .map(|float| AtomicU32::new(float.to_bits()))
+        .collect();
+    let mut dst = vec![0i16; src.len()];
+    fast_float_to_integer::convert_atomic_f32_slice(&atomics, &mut dst);
+    let expected: Vec<i16> = src.iter().map(|float| *float as i16).collect();
+    assert_eq!(dst, expected);
+
+    // A writer stores whole values while the buffer is converted. Every element is one of the values.
+    let atomics: Vec<AtomicU64> = (0..1000)
+        .map(|_| AtomicU64::new(1.5f64.to_bits()))
+        .collect();
+    let mut dst = vec![0u32; atomics.len()];
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            for atomic in atomics.iter().rev() {
+                atomic.store(7.5f64.to_bits(), Ordering::Relaxed);
+            }
+        });
+        fast_float_to_integer::convert_atomic_f64_slice(&atomics, &mut dst);
+    });
+    assert!(dst.iter().all(|integer| [1, 7].contains(integer)));
+}
+
+#[test]
+#[should_panic = "byte slice does not have the size of the destination slice"]
+fn convert_bytes_wrong_length() {
+    fast_float_to_integer::convert_le_bytes::<f32, i32>(&[0; 7], &mut [0; 2]);
+}
+
+#[test]
+#[should_panic = "byte slice does not have the size of the destination slice"]
+fn convert_ne_bytes_wrong_length() {
+    let floats = [0f32; 2];
+    let bytes = unsafe { core::slice::from_raw_parts(floats.as_ptr().cast::<u8>(), 8) };
+    fast_float_to_integer::convert_ne_bytes::<f32, i32>(bytes, &mut [0; 3]);
+}
+
+#[test]
+fn convert_and_reduce() {
+    let src: Vec<f32> = interesting_floats_f32()
+        .filter(|float| InRange::<i32>::in_range(*float))
+        .collect();
+    let expected = src.iter().map(|float| *float as i32);
+    assert_eq!(
+        fast_float_to_integer::convert_and_sum::<f32, i32>(&src),
+        expected.clone().fold(0i32, i32::wrapping_add)
+    );
+    assert_eq!(
+        fast_float_to_integer::convert_and_min::<f32, i32>(&src),
+        expected.clone().min()
+    );
+    assert_eq!(
+        fast_float_to_integer::convert_and_max::<f32, i32>(&src),
+        expected.max()
+    );
+    assert_eq!(
+        fast_float_to_integer::convert_and_min::<f32, i32>(&[]),
+        None
+    );
+    assert_eq!(
+        fast_float_to_integer::convert_and_sum_wide(&src),
+        src.iter().map(|float| *float as i32 as i64).sum::<i64>()
+    );
+}
+
+#[test]
+fn convert_slice_with_stats() {
+    let src: Vec<f64> = interesting_floats_f64().collect();
+    let mut dst = vec![0i16; src.len()];
+    let stats = fast_float_to_integer::convert_slice_with_stats(&src, &mut dst);
+    let in_range: Vec<i16> = src
+        .iter()
+        .zip(&dst)
+        // The crate counts values as in range if they are in range after truncation.
+        .filter(|(float, _)| InRange::<i16>::in_range(float.trunc()))
+        .map(|(float, integer)| {
+            assert_eq!(*integer, *float as i16, "{float:.0}");
+            *integer
+        })
+        .collect();
+    assert_eq!(stats.min, in_range.iter().copied().min());
+    assert_eq!(stats.max, in_range.iter().copied().max());
+    assert_eq!(stats.out_of_range, src.len() - in_range.len());
+
+    let stats = fast_float_to_integer::convert_slice_with_stats::<f32, u8>(&[], &mut []);
+    assert_eq!((stats.min, stats.max, stats.out_of_range), (None, None, 0));
+}
+
+#[test]
+fn convert_slice_select() {
+    let src: Vec<f64> = interesting_floats_f64().collect();
+    let mask: Vec<bool> = src
+        .iter()
+        .map(|float| InRange::<i16>::in_range(*float))
+        .collect();
+    let mut bitmap = vec![0u8; (src.len() + 7) / 8];
+    for (index, selected) in mask.iter().enumerate() {
+        bitmap[index / 8] |= (*selected as u8) << (index % 8);
+    }
+    let check = |dst: &[i16]| {
+        for ((src, dst), selected) in src.iter().zip(dst).zip(&mask) {
+            let expected = if *selected { *src as i16 } else { 7 };
+            assert_eq!(*dst, expected, "{src:.0}");
+        }
+    };
+
+    let mut dst = vec![7i16; src.len()];
+    fast_float_to_integer::convert_slice_select(&src, &mask, &mut dst);
+    check(&dst);
+
+    let mut dst = vec![7i16; src.len()];
+    fast_float_to_integer::convert_slice_select_bitmap(&src, &bitmap, &mut dst);
+    check(&dst);
+}
+
+#[test]
+fn convert_options() {
+    // Every third value is missing and every fifth is NaN. The length is not a multiple of 8 so the last byte of the bitmap is partial.
+    let src: Vec<Option<f32>> = interesting_floats_f32()
+        .filter(|float| InRange::<i32>::in_range(*float))
+        .enumerate()
+        .map(|(index, float)| match (index % 3, index % 5) {
+            (0, _) => None,
+            (_, 0) => Some(f32::NAN),
+            _ => Some(float),
+        })
+        .take(203)
+        .collect();
+    assert_eq!(src.len(), 203);
+    let check = |dst: &[i32], validity: &[u8]| {
+        for (index, (src, dst)) in src.iter().zip(dst).enumerate() {
+            let valid = validity[index / 8] & (1 << (index % 8)) != 0;
+            match src.filter(|float| !float.is_nan()) {
+                Some(float) => {
+                    assert!(valid, "{index}");
+                    assert_eq!(*dst, float as i32, "{float:.0}");
+                }
+                None => {
+                    assert!(!valid, "{index}");
+                    assert_eq!(*dst, -7, "{index}");
+                }
+            }
+        }
+        // The padding bits are 0 and the bytes after the bitmap are untouched.
+        assert_eq!(validity[203 / 8] >> (203 % 8), 0);
+        assert_eq!(validity[(203 + 7) / 8], 0xff);
+    };
+
+    let mut dst = vec![0i32; src.len()];
+    let mut validity = vec![0xffu8; (src.len() + 7) / 8 + 1];
+    fast_float_to_integer::convert_options(&src, -7, &mut dst, &mut validity);
+    check(&dst, &validity);
+
+    let floats: Vec<f32> = src.iter().map(|float| float.unwrap_or(f32::NAN)).collect();
+    let mut dst = vec![0i32; src.len()];
+    let mut validity = vec![0xffu8; (src.len() + 7) / 8 + 1];
+    fast_float_to_integer::convert_slice_nan_to_null(&floats, -7, &mut dst, &mut validity);
+    check(&dst, &validity);
+}
+
+#[test]
+fn via_f32() {
+    let src: Vec<f64> = interesting_floats_f64()
+        .filter(|float| InRange::<i32>::in_range(*float as f32))
+        .collect();
+    let mut dst = vec![0i32; src.len()];
+    fast_float_to_integer::convert_slice_via_f32(&src, &mut dst);
+    for (src, dst) in src.iter().zip(&dst) {
+        assert_eq!(*dst, *src as f32 as i32, "{src:.0}");
+        assert_eq!(fast_float_to_integer::f64_to_i32_via_f32(*src), *dst);
+    }
+}
+
+#[cfg(feature = "unstable-targets")]
+#[test]
+fn backends() {
+    use fast_float_to_integer::backends;
+
+    for float in interesting_floats_f32().filter(|float| InRange::<u64>::in_range(*float)) {
+        assert_eq!(backends::default::f32_to_u64(float), float as u64);
+        #[cfg(all(target_arch = "x86_64", target_feature = "sse"))]
+        assert_eq!(backends::x86_64_sse::f32_to_u64(float), float as u64);
+        #[cfg(all(target_arch = "x86", target_feature = "sse"))]
+        assert_eq!(backends::x86_sse::f32_to_u64(float), float as u64);
+        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+        assert_eq!(backends::aarch64_neon::f32_to_u64(float), float as u64);
+    }
+}
+
+// Compare every backend that is compiled in for the target on the same inputs. This finds differences between the backends without running the tests once per backend.
+macro_rules! create_backends_test {
+    ($name:ident, $interesting_floats_function:ident, $function:ident, $bits_function:ident, $Float:ty, $Integer:ty) => {
+        #[cfg(feature = "unstable-targets")]
+        #[test]
+        fn $name() {
+            use fast_float_to_integer::backends;
+
+            let mut converts: Vec<(&str, fn($Float) -> $Integer)> = vec![
+                ("default", backends::default::$function),
+                ("soft_float", |float| {
+                    backends::soft_float::$bits_function(float.to_bits())
+                }),
+            ];
+            #[cfg(all(target_arch = "x86_64", target_feature = "sse"))]
+            converts.push(("x86_64_sse", backends::x86_64_sse::$function));
+            #[cfg(all(target_arch = "x86", target_feature = "sse"))]
+            converts.push(("x86_sse", backends::x86_sse::$function));
+            #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+            converts.push(("aarch64_neon", backends::aarch64_neon::$function));
+
+            let floats = $interesting_floats_function()
+                .filter(|float| InRange::<$Integer>::in_range(*float));
+            for float in floats {
+                // The standard conversion is the reference. In range, every backend must match it.
+                let expected = float as $Integer;
+                for (backend, convert) in &converts {
+                    assert_eq!(convert(float), expected, "{backend} {float}");
+                }
+            }
+        }
+    };
+}
+
+create_backends_test! {backends_f32_i8, interesting_floats_f32, f32_to_i8, f32_bits_to_i8, f32, i8}
+create_backends_test! {backends_f32_u8, interesting_floats_f32, f32_to_u8, f32_bits_to_u8, f32, u8}
+create_backends_test! {backends_f32_i16, interesting_floats_f32, f32_to_i16, f32_bits_to_i16, f32, i16}
+create_backends_test! {backends_f32_u16, interesting_floats_f32, f32_to_u16, f32_bits_to_u16, f32, u16}
+create_backends_test! {backends_f32_i32, interesting_floats_f32, f32_to_i32, f32_bits_to_i32, f32, i32}
+create_backends_test! {backends_f32_u32, interesting_floats_f32, f32_to_u32, f32_bits_to_u32, f32, u32}
+create_backends_test! {backends_f32_i64, interesting_floats_f32, f32_to_i64, f32_bits_to_i64, f32, i64}
+create_backends_test! {backends_f32_u64, interesting_floats_f32, f32_to_u64, f32_bits_to_u64, f32, u64}
+create_backends_test! {backends_f32_i128, interesting_floats_f32, f32_to_i128, f32_bits_to_i128, f32, i128}
+create_backends_test! {backends_f32_u128, interesting_floats_f32, f32_to_u128, f32_bits_to_u128, f32, u128}
+
+create_backends_test! {backends_f64_i8, interesting_floats_f64, f64_to_i8, f64_bits_to_i8, f64, i8}
+create_backends_test! {backends_f64_u8, interesting_floats_f64, f64_to_u8, f64_bits_to_u8, f64, u8}
+create_backends_test! {backends_f64_i16, interesting_floats_f64, f64_to_i16, f64_bits_to_i16, f64, i16}
+create_backends_test! {backends_f64_u16, interesting_floats_f64, f64_to_u16, f64_bits_to_u16, f64, u16}
+create_backends_test! {backends_f64_i32, interesting_floats_f64, f64_to_i32, f64_bits_to_i32, f64, i32}
+create_backends_test! {backends_f64_u32, interesting_floats_f64, f64_to_u32, f64_bits_to_u32, f64, u32}
+create_backends_test! {backends_f64_i64, interesting_floats_f64, f64_to_i64, f64_bits_to_i64, f64, i64}
+create_backends_test! {backends_f64_u64, interesting_floats_f64, f64_to_u64, f64_bits_to_u64, f64, u64}
+create_backends_test! {backends_f64_i128, interesting_floats_f64, f64_to_i128, f64_bits_to_i128, f64, i128}
+create_backends_test! {backends_f64_u128, interesting_floats_f64, f64_to_u128, f64_bits_to_u128, f64, u128}
+
+macro_rules! check_behavior {
+    ($conversion:ident, $convert_custom:path, $Float:ty, $Integer:ty) => {{
+        use fast_float_to_integer::{Conversion, Nan, OutOfRange};
+
+        let behavior = fast_float_to_integer::behavior(Conversion::$conversion);
+        let out_of_range = [
+            1e30 as $Float,
+            -1e30 as $Float,
+            <$Float>::INFINITY,
+            <$Float>::NEG_INFINITY,
+        ];
+        for float in out_of_range {
+            let result = $convert_custom(float);
+            match behavior.out_of_range {
+                OutOfRange::Unspecified => (),
+                OutOfRange::Saturating => assert_eq!(result, float as $Integer, "{float}"),
+                OutOfRange::Sentinel(sentinel) => assert_eq!(result as i128, sentinel, "{float}"),
+            }
+        }
+        let result = $convert_custom(<$Float>::NAN);
+        match behavior.nan {
+            Nan::Unspecified => (),
+            Nan::Zero => assert_eq!(result, 0),
+            Nan::Sentinel(sentinel) => assert_eq!(result as i128, sentinel),
+        }
+    }};
+}
+
+#[test]
+fn behavior() {
+    check_behavior! {F32ToI8, fast_float_to_integer::f32_to_i8, f32, i8}
+    check_behavior! {F32ToU8, fast_float_to_integer::f32_to_u8, f32, u8}
+    check_behavior! {F32ToI16, fast_float_to_integer::f32_to_i16, f32, i16}
+    check_behavior! {F32ToU16, fast_float_to_integer::f32_to_u16, f32, u16}
+    check_behavior! {F32ToI32, fast_float_to_integer::f32_to_i32, f32, i32}
+    check_behavior! {F32ToU32, fast_float_to_integer::f32_to_u32, f32, u32}
+    check_behavior! {F32ToI64, fast_float_to_integer::f32_to_i64, f32, i64}
+    check_behavior! {F32ToU64, fast_float_to_integer::f32_to_u64, f32, u64}
+    check_behavior! {F32ToI128, fast_float_to_integer::f32_to_i128, f32, i128}
+    check_behavior! {F32ToU128, fast_float_to_integer::f32_to_u128, f32, u128}
+
+    check_behavior! {F64ToI8, fast_float_to_integer::f64_to_i8, f64, i8}
+    check_behavior! {F64ToU8, fast_float_to_integer::f64_to_u8, f64, u8}
+    check_behavior! {F64ToI16, fast_float_to_integer::f64_to_i16, f64, i16}
+    check_behavior! {F64ToU16, fast_float_to_integer::f64_to_u16, f64, u16}
+    check_behavior! {F64ToI32, fast_float_to_integer::f64_to_i32, f64, i32}
+    check_behavior! {F64ToU32, fast_float_to_integer::f64_to_u32, f64, u32}
+    check_behavior! {F64ToI64, fast_float_to_integer::f64_to_i64, f64, i64}
+    check_behavior! {F64ToU64, fast_float_to_integer::f64_to_u64, f64, u64}
+    check_behavior! {F64ToI128, fast_float_to_integer::f64_to_i128, f64, i128}
+    check_behavior! {F64ToU128, fast_float_to_integer::f64_to_u128, f64, u128}
+}
+
+// The feature only changes the default target module, which force-default selects on every target unless portable-deterministic selects the portable one.
+#[cfg(all(
+    feature = "simulate-unspecified",
+    feature = "force-default",
+    not(feature = "portable-deterministic")
+))]
+#[test]
+fn simulate_unspecified() {
+    use fast_float_to_integer::*;
+
+    assert_eq!(f32_to_u8(300.), 0x80);
+    assert_eq!(f32_to_i16(-1e10), i16::MIN);
+    assert_eq!(f64_to_i32(f64::NAN), i32::MIN);
+    assert_eq!(f64_to_u64(-1.), 1 << 63);
+    assert_eq!(f32_to_u8(255.5), 255);
+    assert_eq!(f64_to_i64(-0.5), 0);
+    assert_eq!(behavior(Conversion::F32ToI32).nan, Nan::Unspecified);
+}
+
+macro_rules! create_in_range_test {
+    ($name:ident, $interesting_floats_function:ident, $Float:ty, $Integer:ty) => {
+        #[test]
+        fn $name() {
+            use fast_float_to_integer::InRange as Witness;
+
+            let floats = $interesting_floats_function().chain([
+                <$Float>::NAN,
+                <$Float>::INFINITY,
+                <$Float>::NEG_INFINITY,
+            ]);
+            for float in floats {
+                // Unlike the test's InRange trait, the witness accepts inputs that truncate into range like -0.5 for unsigned types. The bounds are powers of two, which are exact in f64.
+                let truncated = float.trunc() as f64;
+                let upper_exclusive = if <$Integer>::MIN == 0 {
+                    2f64.powi(<$Integer>::BITS as i32)
+                } else {
+                    -(<$Integer>::MIN as f64)
+                };
+                let expected = truncated >= <$Integer>::MIN as f64 && truncated < upper_exclusive;
+                let witness = Witness::<$Float, $Integer>::new(float);
+                assert_eq!(witness.is_some(), expected, "{float:.0}");
+                if let Some(witness) = witness {
+                    assert_eq!(witness.convert(), float as $Integer, "{float:.0}");
+                }
+            }
+        }
+    };
+}
+
+create_in_range_test! {in_range_f32_i8, interesting_floats_f32, f32, i8}
+create_in_range_test! {in_range_f32_u16, interesting_floats_f32, f32, u16}
+create_in_range_test! {in_range_f32_i64, interesting_floats_f32, f32, i64}
+create_in_range_test! {in_range_f32_u128, interesting_floats_f32, f32, u128}
+create_in_range_test! {in_range_f64_u32, interesting_floats_f64, f64, u32}
+create_in_range_test! {in_range_f64_i128, interesting_floats_f64, f64, i128}
+
+#[test]
+fn scaled_const() {
+    use fast_float_to_integer::{f32_to_i16_scaled_const, f64_to_i64_scaled_const};
+
+    for float in [0.0f32, 0.5, -0.5, 0.999, -1.0] {
+        assert_eq!(
+            f32_to_i16_scaled_const::<32767, 1>(float),
+            (float * 32767.) as i16
+        );
+    }
+    assert_eq!(
+        f64_to_i64_scaled_const::<1_000_000_000, 1>(1.5),
+        1_500_000_000
+    );
+    assert_eq!(f64_to_i64_scaled_const::<1, 4>(10.), 2);
+    // The factor is the correctly rounded fraction.
+    assert_eq!(
+        f32_to_i16_scaled_const::<1, 3>(3000.),
+        (3000f32 * (1f32 / 3.)) as i16
+    );
+}
+
+#[test]
+fn decimal_scaled() {
+    use fast_float_to_integer::{f64_to_i32_decimal_scaled, f64_to_i64_decimal_scaled};
+
+    assert_eq!(f64_to_i64_decimal_scaled::<2>(12.34), 1234);
+    assert_eq!(f64_to_i64_decimal_scaled::<2>(-12.34), -1234);
+    assert_eq!(f64_to_i64_decimal_scaled::<2>(0.29), 28);
+    assert_eq!(f64_to_i32_decimal_scaled::<0>(-7.9), -7);
+    assert_eq!(f64_to_i32_decimal_scaled::<9>(1.5), 1_500_000_000);
+    assert_eq!(
+        f64_to_i64_decimal_scaled::<18>(1.),
+        1_000_000_000_000_000_000
+    );
+    // The parser rounds the literals correctly. `10f64.powi(33)` and repeated multiplication by 10 differ from them for some of these exponents.
+    let tiny = 1.234_567e-20;
+    assert_eq!(f64_to_i64_decimal_scaled::<23>(tiny), (tiny * 1e23) as i64);
+    assert_eq!(f64_to_i64_decimal_scaled::<33>(tiny), (tiny * 1e33) as i64);
+    assert_eq!(f64_to_i64_decimal_scaled::<37>(tiny), (tiny * 1e37) as i64);
+    assert_eq!(f64_to_i64_decimal_scaled::<38>(tiny), (tiny * 1e38) as i64);
+}
+
+#[test]
+fn conversion_error() {
+    for float in interesting_floats_f64().filter(|float| InRange::<i64>::in_range(*float)) {
+        let error = fast_float_to_integer::conversion_error_f64_to_i64(float);
+        assert_eq!(error, float.fract(), "{float}");
+    }
+    for float in interesting_floats_f32().filter(|float| InRange::<u16>::in_range(*float)) {
+        let error = fast_float_to_integer::conversion_error_f32_to_u16(float);
+        assert_eq!(error, float.fract(), "{float}");
+    }
+}
+
+macro_rules! create_with_fract_test {
+    ($name:ident, $interesting_floats_function:ident, $convert_custom:path, $Integer:ty) => {
+        #[test]
+        fn $name() {
+            for float in
+                $interesting_floats_function().filter(|float| InRange::<$Integer>::in_range(*float))
+            {
+                let (integer, fract) = $convert_custom(float);
+                assert_eq!(integer, float as $Integer, "{float}");
+                assert_eq!(fract, float.fract(), "{float}");
+            }
+        }
+    };
+}
+
+create_with_fract_test! {with_fract_f32_i32, interesting_floats_f32, fast_float_to_integer::f32_to_i32_with_fract, i32}
+create_with_fract_test! {with_fract_f32_u32, interesting_floats_f32, fast_float_to_integer::f32_to_u32_with_fract, u32}
+create_with_fract_test! {with_fract_f64_i32, interesting_floats_f64, fast_float_to_integer::f64_to_i32_with_fract, i32}
+create_with_fract_test! {with_fract_f64_u32, interesting_floats_f64, fast_float_to_integer::f64_to_u32_with_fract, u32}
+
+macro_rules! create_clamp_nearest_test {
+    ($name:ident, $interesting_floats_function:ident, $convert_custom:path, $Float:ty, $Integer:ty) => {
+        #[test]
+        fn $name() {
+            let floats = $interesting_floats_function().chain([
+                <$Float>::INFINITY,
+                <$Float>::NEG_INFINITY,
+                <$Float>::MAX,
+                <$Float>::MIN,
+            ]);
+            for float in floats {
+                assert_eq!($convert_custom(float), float as $Integer, "{float:.0}");
+            }
+        }
+    };
+}
+
+create_clamp_nearest_test! {clamp_nearest_f32_i8, interesting_floats_f32, fast_float_to_integer::f32_to_i8_clamp_nearest, f32, i8}
+create_clamp_nearest_test! {clamp_nearest_f32_u32, interesting_floats_f32, fast_float_to_integer::f32_to_u32_clamp_nearest, f32, u32}
+create_clamp_nearest_test! {clamp_nearest_f32_i64, interesting_floats_f32, fast_float_to_integer::f32_to_i64_clamp_nearest, f32, i64}
+create_clamp_nearest_test! {clamp_nearest_f32_u64, interesting_floats_f32, fast_float_to_integer::f32_to_u64_clamp_nearest, f32, u64}
+create_clamp_nearest_test! {clamp_nearest_f32_u128, interesting_floats_f32, fast_float_to_integer::f32_to_u128_clamp_nearest, f32, u128}
+create_clamp_nearest_test! {clamp_nearest_f64_i32, interesting_floats_f64, fast_float_to_integer::f64_to_i32_clamp_nearest, f64, i32}
+create_clamp_nearest_test! {clamp_nearest_f64_u16, interesting_floats_f64, fast_float_to_integer::f64_to_u16_clamp_nearest, f64, u16}
+create_clamp_nearest_test! {clamp_nearest_f64_i64, interesting_floats_f64, fast_float_to_integer::f64_to_i64_clamp_nearest, f64, i64}
+create_clamp_nearest_test! {clamp_nearest_f64_u64, interesting_floats_f64, fast_float_to_integer::f64_to_u64_clamp_nearest, f64, u64}
+
+macro_rules! create_negative_to_zero_test {
+    ($name:ident, $interesting_floats_function:ident, $convert_custom:path, $Float:ty, $Integer:ty) => {
+        #[test]
+        fn $name() {
+            let floats = $interesting_floats_function().chain([
+                <$Float>::NAN,
+                <$Float>::NEG_INFINITY,
+                <$Float>::MIN,
+                -<$Float>::MIN_POSITIVE,
+                -1e-9,
+            ]);
+            for float in floats {
+                if float < 0. || float.is_nan() {
+                    assert_eq!($convert_custom(float), 0, "{float}");
+                } else if InRange::<$Integer>::in_range(float) {
+                    assert_eq!($convert_custom(float), float as $Integer, "{float}");
+                }
+            }
+        }
+    };
+}
+
+create_negative_to_zero_test! {negative_to_zero_f32_u8, interesting_floats_f32, fast_float_to_integer::f32_to_u8_negative_to_zero, f32, u8}
+create_negative_to_zero_test! {negative_to_zero_f32_u32, interesting_floats_f32, fast_float_to_integer::f32_to_u32_negative_to_zero, f32, u32}
+create_negative_to_zero_test! {negative_to_zero_f32_u64, interesting_floats_f32, fast_float_to_integer::f32_to_u64_negative_to_zero, f32, u64}
+create_negative_to_zero_test! {negative_to_zero_f64_u16, interesting_floats_f64, fast_float_to_integer::f64_to_u16_negative_to_zero, f64, u16}
+create_negative_to_zero_test! {negative_to_zero_f64_u32, interesting_floats_f64, fast_float_to_integer::f64_to_u32_negative_to_zero, f64, u32}
+create_negative_to_zero_test! {negative_to_zero_f64_u128, interesting_floats_f64, fast_float_to_integer::f64_to_u128_negative_to_zero, f64, u128}
+
+macro_rules! create_bits_test {
+    ($name:ident, $interesting_floats_function:ident, $convert_custom:path, $Float:ty, $Integer:ty) => {
+        #[test]
+        fn $name() {
+            let floats = $interesting_floats_function().chain([
+                <$Float>::MIN_POSITIVE,
+                -<$Float>::MIN_POSITIVE,
+                <$Float>::from_bits(1),
+                -0.,
+            ]);
+            for float in floats {
+                if InRange::<$Integer>::in_range(float) {
+                    assert_eq!(
+                        $convert_custom(float.to_bits()),
+                        float as $Integer,
+                        "{float}"
+                    );
+                }
+            }
+        }
+    };
+}
+
+create_bits_test! {bits_f32_i8, interesting_floats_f32, fast_float_to_integer::f32_bits_to_i8, f32, i8}
+create_bits_test! {bits_f32_u16, interesting_floats_f32, fast_float_to_integer::f32_bits_to_u16, f32, u16}
+create_bits_test! {bits_f32_i32, interesting_floats_f32, fast_float_to_integer::f32_bits_to_i32, f32, i32}
+create_bits_test! {bits_f32_u32, interesting_floats_f32, fast_float_to_integer::f32_bits_to_u32, f32, u32}
+create_bits_test! {bits_f32_i64, interesting_floats_f32, fast_float_to_integer::f32_bits_to_i64, f32, i64}
+create_bits_test! {bits_f32_i128, interesting_floats_f32, fast_float_to_integer::f32_bits_to_i128, f32, i128}
+create_bits_test! {bits_f32_u128, interesting_floats_f32, fast_float_to_integer::f32_bits_to_u128, f32, u128}
+create_bits_test! {bits_f64_u8, interesting_floats_f64, fast_float_to_integer::f64_bits_to_u8, f64, u8}
+create_bits_test! {bits_f64_i32, interesting_floats_f64, fast_float_to_integer::f64_bits_to_i32, f64, i32}
+create_bits_test! {bits_f64_i64, interesting_floats_f64, fast_float_to_integer::f64_bits_to_i64, f64, i64}
+create_bits_test! {bits_f64_u64, interesting_floats_f64, fast_float_to_integer::f64_bits_to_u64, f64, u64}
+create_bits_test! {bits_f64_i128, interesting_floats_f64, fast_float_to_integer::f64_bits_to_i128, f64, i128}
+create_bits_test! {bits_f64_u128, interesting_floats_f64, fast_float_to_integer::f64_bits_to_u128, f64, u128}
+
+macro_rules! create_prefetched_test {
+    ($name:ident, $Float:ty, $Integer:ty) => {
+        #[test]
+        fn $name() {
+            use fast_float_to_integer::{
+                convert_slice, convert_slice_prefetched, convert_slice_prefetched_with_distance,
+            };
+
+            for len in [0, 1, 255, 256, 257, 5_000] {
+                let src: Vec<$Float> = (0..len).map(|i| (i % 300) as $Float - 99.5).collect();
+                let mut expected = vec![0 as $Integer; len];
+                convert_slice(&src, &mut expected);
+                let mut dst = vec![0 as $Integer; len];
+                convert_slice_prefetched(&src, &mut dst);
+                assert_eq!(dst, expected);
+                for distance in [0, 1, 64, 100_000, usize::MAX] {
+                    let mut dst = vec![0 as $Integer; len];
+                    convert_slice_prefetched_with_distance(&src, &mut dst, distance);
+                    assert_eq!(dst, expected);
+                }
+            }
+        }
+    };
+}
+
+create_prefetched_test! {prefetched_f32_i32, f32, i32}
+create_prefetched_test! {prefetched_f32_u8, f32, u8}
+create_prefetched_test! {prefetched_f64_i64, f64, i64}
+
+#[test]
+fn convert_bits() {
+    use fast_float_to_integer::{convert_bits, f32_bits_to_i16, f64_bits_to_u64};
+
+    let bits: Vec<u32> = interesting_floats_f32().map(f32::to_bits).collect();
+    let converted: Vec<i16> = convert_bits(bits.iter().copied()).collect();
+    let expected: Vec<i16> = bits.iter().map(|bits| f32_bits_to_i16(*bits)).collect();
+    assert_eq!(converted, expected);
+
+    let bits: Vec<u64> = interesting_floats_f64().map(f64::to_bits).collect();
+    let converted = convert_bits::<u64, _>(bits.iter().copied());
+    assert_eq!(converted.len(), bits.len());
+    let converted: Vec<u64> = converted.rev().collect();
+    let expected: Vec<u64> = bits
+        .iter()
+        .rev()
+        .map(|bits| f64_bits_to_u64(*bits))
+        .collect();
+    assert_eq!(converted, expected);
+}
+
+#[test]
+fn tolerant() {
+    use fast_float_to_integer::{f32_to_u8_tolerant, f64_to_i32_tolerant, f64_to_i64_tolerant};
+
+    assert_eq!(f64_to_i32_tolerant(2.9999999, 1e-6), 3);
+    assert_eq!(f64_to_i32_tolerant(-2.9999999, 1e-6), -3);
+    assert_eq!(f64_to_i32_tolerant(2.999, 1e-6), 2);
+    assert_eq!(f64_to_i32_tolerant(-2.999, 1e-6), -2);
+    assert_eq!(f64_to_i32_tolerant(0.1 + 0.2 - 0.3, 1e-9), 0);
+    assert_eq!(f64_to_i32_tolerant((0.1 + 0.7) * 10., 1e-9), 8);
+    assert_eq!(f32_to_u8_tolerant(254.99998, 1e-4), 255);
+    assert_eq!(f32_to_u8_tolerant(-0.5, 1e-4), 0);
+    // The reference with the standard library.
+    for float in interesting_floats_f64().filter(|float| InRange::<i64>::in_range(*float * 1.5)) {
+        let epsilon = 0.25f64;
+        let expected = (float + epsilon.copysign(float)) as i64;
+        assert_eq!(f64_to_i64_tolerant(float, epsilon), expected, "{float}");
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn convert_chunks() {
+    let chunks: Vec<Vec<f32>> = interesting_floats_f32()
+        .filter(|float| InRange::<i32>::in_range(*float))
+        .collect::<Vec<_>>()
+        .chunks(7)
+        .map(|chunk| chunk.to_vec())
+        .collect();
+    let expected: Vec<Vec<i32>> = chunks
+        .iter()
+        .map(|chunk| chunk.iter().map(|float| *float as i32).collect())
+        .collect();
+    let inline: Vec<Vec<i32>> =
+        fast_float_to_integer::convert_chunks(chunks.clone().into_iter()).collect();
+    assert_eq!(inline, expected);
+    let background: Vec<Vec<i32>> =
+        fast_float_to_integer::convert_chunks_in_background(chunks.into_iter()).collect();
+    assert_eq!(background, expected);
+}
+
+#[cfg(feature = "std")]
+#[test]
+#[should_panic = "source failed"]
+fn convert_chunks_in_background_panic() {
+    let source = (0..3).map(|chunk| {
+        assert!(chunk < 2, "source failed");
+        vec![1f32]
+    });
+    let chunks = fast_float_to_integer::convert_chunks_in_background::<f32, u8, _>(source);
+    assert_eq!(chunks.count(), 2);
+}
+
+#[cfg(feature = "expect-finite")]
+#[test]
+fn expect_finite() {
+    assert_eq!(fast_float_to_integer::f32_to_i32_expect_finite(-1.5), -1);
+    let result =
+        std::panic::catch_unwind(|| fast_float_to_integer::f64_to_u8_expect_finite(f64::NAN));
+    let message = *result.unwrap_err().downcast::<String>().unwrap();
+    assert_eq!(message, "input is NaN with bits 0x7ff8000000000000");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn analyze() {
+    use fast_float_to_integer::{analyze, Recommendation};
+
+    let report = analyze::<f64, i8>(&[0.5, -128.9, 127.9, 64.0, 63.0, 128.0, f64::NAN]);
+    assert_eq!(report.total, 7);
+    assert_eq!(report.in_range, 5);
+    assert_eq!(report.near_threshold, 3);
+    assert_eq!(report.out_of_range, 1);
+    assert_eq!(report.nan, 1);
+    assert_eq!(report.recommendation(), Recommendation::Saturating);
+    assert_eq!(
+        analyze::<f32, u64>(&[]).recommendation(),
+        Recommendation::Fast
+    );
+    assert_eq!(analyze::<f32, u64>(&[]).fraction_in_range(), 0.);
+}
+
+/// Round to the nearest integer with ties to even.
+fn round_ties_even(float: f64) -> f64 {
+    let rounded = float.round();
+    if (rounded - float).abs() == 0.5 && rounded % 2. != 0. {
+        rounded - float.signum()
+    } else {
+        rounded
+    }
+}
+
+macro_rules! create_normalized_test {
+    ($name:ident, $function:path, $slice_function:path, $Integer:ty, $min:literal) => {
+        #[test]
+        fn $name() {
+            let expected = |float: f32| -> $Integer {
+                if float.is_nan() {
+                    return 0;
+                }
+                let scaled = float.clamp($min, 1.) * <$Integer>::MAX as f32;
+                round_ties_even(scaled as f64) as $Integer
+            };
+            let steps = (0..=2 * <$Integer>::MAX as u32)
+                .map(|step| step as f32 / (2. * <$Integer>::MAX as f32) * (1. - $min) + $min);
+            let src: Vec<f32> = interesting_floats_f32()
+                .chain([f32::NAN, f32::INFINITY, f32::NEG_INFINITY])
+                .chain(steps)
+                .collect();
+            for float in src.iter().copied() {
+                assert_eq!($function(float), expected(float), "{float}");
+            }
+            let mut dst = vec![0 as $Integer; src.len()];
+            $slice_function(&src, &mut dst);
+            for (src, dst) in src.iter().zip(&dst) {
+                assert_eq!(*dst, expected(*src), "{src}");
+            }
+        }
+    };
+}
+
+create_normalized_test! {unorm_u8, fast_float_to_integer::unorm_f32_to_u8, fast_float_to_integer::unorm_f32_to_u8_slice, u8, 0.}
+create_normalized_test! {unorm_u16, fast_float_to_integer::unorm_f32_to_u16, fast_float_to_integer::unorm_f32_to_u16_slice, u16, 0.}
+create_normalized_test! {snorm_i8, fast_float_to_integer::snorm_f32_to_i8, fast_float_to_integer::snorm_f32_to_i8_slice, i8, -1.}
+create_normalized_test! {snorm_i16, fast_float_to_integer::snorm_f32_to_i16, fast_float_to_integer::snorm_f32_to_i16_slice, i16, -1.}
+
+macro_rules! create_round_ties_even_test {
+    ($name:ident, $interesting_floats_function:ident, $Float:ty, $Integer:ty) => {
+        #[test]
+        fn $name() {
+            let src: Vec<$Float> = $interesting_floats_function()
+                .chain((-40..40).map(|i| i as $Float / 4.))
+                .filter(|float| InRange::<$Integer>::in_range(round_ties_even(*float as f64)))
+                .collect();
+            let mut dst = vec![0 as $Integer; src.len()];
+            fast_float_to_integer::round_ties_even_convert_slice(&src, &mut dst);
+            for (src, dst) in src.iter().zip(&dst) {
+                assert_eq!(*dst, round_ties_even(*src as f64) as $Integer, "{src}");
+            }
+        }
+    };
+}
+
+create_round_ties_even_test! {round_ties_even_f32_i32, interesting_floats_f32, f32, i32}
+create_round_ties_even_test! {round_ties_even_f32_u8, interesting_floats_f32, f32, u8}
+create_round_ties_even_test! {round_ties_even_f32_u64, interesting_floats_f32, f32, u64}
+create_round_ties_even_test! {round_ties_even_f64_i32, interesting_floats_f64, f64, i32}
+create_round_ties_even_test! {round_ties_even_f64_i64, interesting_floats_f64, f64, i64}
+
+#[test]
+fn normalized() {
+    assert_eq!(fast_float_to_integer::unorm_f32_to_u8(0.5), 128);
+    assert_eq!(fast_float_to_integer::unorm_f32_to_u8(-0.5), 0);
+    assert_eq!(fast_float_to_integer::snorm_f32_to_i8(-1.), -127);
+    assert_eq!(fast_float_to_integer::snorm_f32_to_i8(-2.), -127);
+    assert_eq!(fast_float_to_integer::snorm_f32_to_i16(f32::NAN), 0);
+}
+
+#[test]
+fn depth() {
+    let expected = |float: f32| -> u32 {
+        if float.is_nan() {
+            return 0;
+        }
+        round_ties_even(float.clamp(0., 1.) as f64 * ((1 << 24) - 1) as f64) as u32
+    };
+    let steps = (0..=1 << 23).map(|step| step as f32 / (1 << 23) as f32);
+    let src: Vec<f32> = interesting_floats_f32()
+        .chain([f32::NAN, f32::INFINITY, f32::NEG_INFINITY])
+        .chain(steps)
+        .collect();
+    let mut dst = vec![0u32; src.len()];
+    fast_float_to_integer::depth_f32_to_d24_slice(&src, &mut dst);
+    for (src, dst) in src.iter().zip(&dst) {
+        assert_eq!(*dst, expected(*src), "{src}");
+    }
+    assert_eq!(fast_float_to_integer::depth_f32_to_d24(1.), 0xff_ffff);
+    assert_eq!(fast_float_to_integer::depth_f32_to_d16(1.), u16::MAX);
+}
+
+#[test]
+fn srgb() {
+    let exact = |float: f32| -> f64 {
+        let linear = if float.is_nan() {
+            0.
+        } else {
+            float.clamp(0., 1.) as f64
+        };
+        let encoded = if linear <= 0.0031308 {
+            12.92 * linear
+        } else {
+            1.055 * linear.powf(1. / 2.4) - 0.055
+        };
+        encoded * 255.
+    };
+    let steps = (0..=1 << 20).map(|step| step as f32 / (1 << 20) as f32);
+    let src: Vec<f32> = interesting_floats_f32()
+        .chain([f32::NAN, f32::INFINITY, f32::NEG_INFINITY, 0.0031308])
+        .chain(steps)
+        .collect();
+    let mut dst = vec![0u8; src.len()];
+    fast_float_to_integer::srgb_f32_to_u8_slice(&src, &mut dst);
+    for (src, dst) in src.iter().zip(&dst) {
+        let exact = exact(*src);
+        assert!((*dst as f64 - exact).abs() < 0.55, "{src} {dst} {exact}");
+    }
+}
+
+#[test]
+fn pack_unorm() {
+    let channel = |float: f32, bits: u32| -> u32 {
+        if float.is_nan() {
+            return 0;
+        }
+        let max = (1u32 << bits) - 1;
+        round_ties_even((float.clamp(0., 1.) * max as f32) as f64) as u32
+    };
+    let floats: Vec<f32> = interesting_floats_f32()
+        .chain([f32::NAN, 0.25, 0.5, 0.75, 1. / 3.])
+        .collect();
+    let src: Vec<[f32; 4]> = floats
+        .iter()
+        .zip(floats.iter().rev())
+        .map(|(a, b)| [*a, *b, (*a) * 0.5, *b + 0.25])
+        .collect();
+
+    let src3: Vec<[f32; 3]> = src.iter().map(|[r, g, b, _]| [*r, *g, *b]).collect();
+    let mut dst = vec![0u32; src.len()];
+    fast_float_to_integer::pack_unorm_r11g11b10_slice(&src3, &mut dst);
+    for ([r, g, b], dst) in src3.iter().zip(&dst) {
+        let expected = channel(*r, 11) | channel(*g, 11) << 11 | channel(*b, 10) << 22;
+        assert_eq!(*dst, expected, "{r} {g} {b}");
+    }
+
+    fast_float_to_integer::pack_unorm_r10g10b10a2_slice(&src, &mut dst);
+    for ([r, g, b, a], dst) in src.iter().zip(&dst) {
+        let expected =
+            channel(*r, 10) | channel(*g, 10) << 10 | channel(*b, 10) << 20 | channel(*a, 2) << 30;
+        assert_eq!(*dst, expected, "{r} {g} {b} {a}");
+    }
+}
+
+macro_rules! create_exact_bounds_test {
+    ($name:ident, $Float:ty, $Integer:ty) => {
+        #[test]
+        fn $name() {
+            use fast_float_to_integer::{FastFrom, IntType};
+
+            let min = <$Integer as FastFrom<$Float>>::MIN_EXACT;
+            let max = <$Integer as FastFrom<$Float>>::MAX_EXACT;
+            assert_eq!(min, <$Integer>::MIN as $Float);
+            assert!(InRange::<$Integer>::in_range(max));
+            assert!(!InRange::<$Integer>::in_range(
+                max.next_after(<$Float>::INFINITY).ceil()
+            ));
+            assert_eq!(max.fract(), 0.);
+            assert_eq!(<$Integer as IntType>::BITS, <$Integer>::BITS);
+            assert_eq!(<$Integer as IntType>::SIGNED, <$Integer>::MIN != 0);
+        }
+    };
+}
+
+create_exact_bounds_test! {exact_bounds_f32_i8, f32, i8}
+create_exact_bounds_test! {exact_bounds_f32_u32, f32, u32}
+create_exact_bounds_test! {exact_bounds_f32_i64, f32, i64}
+create_exact_bounds_test! {exact_bounds_f32_u128, f32, u128}
+create_exact_bounds_test! {exact_bounds_f64_i32, f64, i32}
+create_exact_bounds_test! {exact_bounds_f64_u64, f64, u64}
+create_exact_bounds_test! {exact_bounds_f64_i128, f64, i128}
+
+macro_rules! create_convert_with_test {
+    ($name:ident, $interesting_floats_function:ident, $Float:ty, $Integer:ty) => {
+        #[test]
+        fn $name() {
+            use fast_float_to_integer::{
+                convert_with, Checked, Deterministic, Saturate, Unspecified, Wrap,
+            };
+
+            for float in $interesting_floats_function().chain([<$Float>::NAN]) {
+                // The conversions truncate, so -128.5 is in range of i8.
+                let in_range = InRange::<$Integer>::in_range(float.trunc());
+                if in_range {
+                    assert_eq!(
+                        convert_with::<Unspecified, $Float, $Integer>(float),
+                        float as $Integer
+                    );
+                }
+                assert_eq!(
+                    convert_with::<Deterministic, $Float, $Integer>(float),
+                    float as $Integer
+                );
+                assert_eq!(
+                    convert_with::<Saturate, $Float, $Integer>(float),
+                    float as $Integer
+                );
+                assert_eq!(
+                    convert_with::<Checked, $Float, $Integer>(float),
+                    in_range.then_some(float as $Integer)
+                );
+                // Below 2^127 in magnitude, wrapping is the same as truncating the i128 result.
+                if float.is_nan() || float.abs() < (2. as $Float).powi(127) {
+                    assert_eq!(
+                        convert_with::<Wrap, $Float, $Integer>(float),
+                        float as i128 as $Integer,
+                        "{float}"
+                    );
+                }
+            }
+        }
+    };
+}
+
+create_convert_with_test! {convert_with_f32_i8, interesting_floats_f32, f32, i8}
+create_convert_with_test! {convert_with_f32_u32, interesting_floats_f32, f32, u32}
+create_convert_with_test! {convert_with_f64_i64, interesting_floats_f64, f64, i64}
+create_convert_with_test! {convert_with_f64_u128, interesting_floats_f64, f64, u128}
+
+#[test]
+fn convert_with_wrap_huge() {
+    use fast_float_to_integer::{convert_with, Wrap};
+
+    let huge = 2f64.powi(128) + 2f64.powi(76);
+    assert_eq!(convert_with::<Wrap, f64, u128>(huge), 1 << 76);
+    assert_eq!(convert_with::<Wrap, f64, i64>(huge), 0);
+    assert_eq!(
+        convert_with::<Wrap, f64, u128>(-huge),
+        (1u128 << 76).wrapping_neg()
+    );
+    assert_eq!(convert_with::<Wrap, f64, u128>(2f64.powi(127)), 1 << 127);
+    assert_eq!(convert_with::<Wrap, f32, u8>(f32::INFINITY), 0);
+    assert_eq!(convert_with::<Wrap, f32, i16>(-65537.5), -1);
+}
+
+#[test]
+fn converter() {
+    use fast_float_to_integer::{AtLeast, Converter, Deterministic, Saturate, Unspecified, Wrap};
+
+    fn deterministic<G: AtLeast<Deterministic>>(converter: Converter<f64, u8, G>) -> u8 {
+        converter.convert(300.)
+    }
+
+    let saturate = Converter::<f64, u8, Saturate>::new();
+    let wrap = Converter::<f64, u8, Wrap>::default();
+    assert_eq!(deterministic(saturate), 255);
+    assert_eq!(deterministic(wrap), 44);
+    assert_eq!(deterministic(saturate.weaken::<Deterministic>()), 255);
+    let unspecified: Converter<f64, u8, Unspecified> = wrap.weaken();
+    assert_eq!(unspecified.convert(200.5), 200);
+    assert_eq!(core::mem::size_of_val(&unspecified), 0);
+    // The type name of the guarantee is not stable.
+    assert!(format!("{saturate:?}").starts_with("Converter<f64, u8, "));
+}
+
+// The software float rounding of the builder for the saturating and wrapping guarantees is compared to the standard library, including out of range inputs and NaN.
+macro_rules! create_convert_builder_test {
+    ($name:ident, $interesting_floats_function:ident, $Float:ty, $Integer:ty) => {
+        #[test]
+        fn $name() {
+            use fast_float_to_integer::{
+                convert_with, Ceil, Convert, Floor, Round, RoundTiesEven, Saturate, Truncate, Wrap,
+            };
+
+            let floats: Vec<$Float> = $interesting_floats_function()
+                .chain([0.5, 1.5, 2.5, -0.5, -1.5, -2.5, 0.49999997, -0.49999997])
+                .collect();
+            macro_rules! check {
+                ($rounding:expr, $round:expr) => {
+                    let saturate = Convert::new()
+                        .rounding($rounding)
+                        .overflow(Saturate)
+                        .scale(2.);
+                    let wrap = Convert::new().rounding($rounding).overflow(Wrap);
+                    let unspecified = Convert::new().rounding($rounding);
+                    let mut dst = vec![0 as $Integer; floats.len()];
+                    saturate.slice(&floats, &mut dst);
+                    for (float, integer) in floats.iter().zip(&dst) {
+                        let expected = $round(*float * 2.) as $Integer;
+                        assert_eq!(
+                            saturate.one::<$Float, $Integer>(*float),
+                            expected,
+                            "{float}"
+                        );
+                        assert_eq!(*integer, expected, "{float}");
+                        assert_eq!(
+                            wrap.one::<$Float, $Integer>(*float),
+                            convert_with::<Wrap, $Float, $Integer>($round(*float)),
+                            "{float}"
+                        );
+                        if InRange::<$Integer>::in_range($round(*float)) {
+                            assert_eq!(
+                                unspecified.one::<$Float, $Integer>(*float),
+                                $round(*float) as $Integer,
+                                "{float}"
+                            );
+                        }
+                    }
+                    let collected: Vec<$Integer> = saturate.iter(&floats).collect();
+                    assert_eq!(collected, dst);
+                };
+            }
+
+            check!(Truncate, <$Float>::trunc);
+            check!(Floor, <$Float>::floor);
+            check!(Ceil, <$Float>::ceil);
+            check!(Round, <$Float>::round);
+            check!(RoundTiesEven, |float: $Float| round_ties_even(float as f64)
+                as $Float);
+        }
+    };
+}
+
+create_convert_builder_test! {convert_builder_f32_u8, interesting_floats_f32, f32, u8}
+create_convert_builder_test! {convert_builder_f32_i32, interesting_floats_f32, f32, i32}
+create_convert_builder_test! {convert_builder_f64_i64, interesting_floats_f64, f64, i64}
+
+#[test]
+fn index() {
+    use fast_float_to_integer::{f32_to_index, f64_to_index, Saturate, Wrap};
+
+    assert_eq!(f32_to_index::<Saturate>(2.9, 4), 2);
+    assert_eq!(f32_to_index::<Saturate>(7., 4), 3);
+    assert_eq!(f32_to_index::<Saturate>(-7., 4), 0);
+    assert_eq!(f32_to_index::<Saturate>(f32::NAN, 4), 0);
+    assert_eq!(f32_to_index::<Saturate>(f32::INFINITY, 4), 3);
+    assert_eq!(f64_to_index::<Saturate>(1e300, usize::MAX), usize::MAX - 1);
+
+    assert_eq!(f32_to_index::<Wrap>(2.9, 4), 2);
+    assert_eq!(f32_to_index::<Wrap>(7., 4), 3);
+    assert_eq!(f32_to_index::<Wrap>(-1., 4), 3);
+    assert_eq!(f32_to_index::<Wrap>(-4., 4), 0);
+    assert_eq!(f32_to_index::<Wrap>(-0.5, 4), 0);
+    assert_eq!(f32_to_index::<Wrap>(f32::NAN, 4), 0);
+    assert_eq!(f64_to_index::<Wrap>(1e300, 4), 0);
+    for integer in -20i64..20 {
+        assert_eq!(
+            f64_to_index::<Wrap>(integer as f64, 7),
+            integer.rem_euclid(7) as usize
+        );
+    }
+    assert_eq!(
+        f64_to_index::<Wrap>(-(2f64.powi(62)), usize::MAX),
+        (-(2i128.pow(62))).rem_euclid(usize::MAX as i128) as usize
+    );
+    // -2^63 is in range of i64 and wraps like the other in range inputs. 2^63 is out of range.
+    assert_eq!(
+        f64_to_index::<Wrap>(-(2f64.powi(63)), 7),
+        i64::MIN.rem_euclid(7) as usize
+    );
+    assert_eq!(
+        f32_to_index::<Wrap>(-(2f32.powi(63)), 7),
+        i64::MIN.rem_euclid(7) as usize
+    );
+    assert_eq!(f64_to_index::<Wrap>(2f64.powi(63), 7), 0);
+}
+
+// The SSE2 and NEON kernels narrow by truncation like the scalar conversion, also for the out of range results. On x86_64 the scalar conversion goes through i64, so the scaled samples stay in range of i32.
+#[cfg(all(
+    any(
+        all(target_arch = "aarch64", target_feature = "neon"),
+        all(
+            any(target_arch = "x86", target_arch = "x86_64"),
+            target_feature = "sse2"
+        )
+    ),
+    not(any(feature = "simulate-unspecified", feature = "portable-deterministic"))
+))]
+#[test]
+fn convert_stereo_unclamped_out_of_range() {
+    // Two frames of the kernel and a remainder of three frames.
+    let left = [
+        1.5f32,
+        -2.,
+        0.5,
+        40000.,
+        -40000.,
+        3.,
+        f32::NAN,
+        0.25,
+        f32::INFINITY,
+        -f32::INFINITY,
+        1e20,
+    ];
+    let right: Vec<f32> = left.iter().map(|sample| -sample * 1.5).collect();
+    let mut out = vec![0i16; left.len() * 2];
+    fast_float_to_integer::convert_stereo_f32_to_i16_interleaved_unclamped(&left, &right, &mut out);
+    let expected = |sample: f32| fast_float_to_integer::f32_to_i16(sample * 32767.);
+    for (index, frame) in out.chunks_exact(2).enumerate() {
+        assert_eq!(
+            frame,
+            [expected(left[index]), expected(right[index])],
+            "{index}"
+        );
+    }
+}
+
+// Every frame goes through the stereo kernel, so the unspecified results of out of range and NaN samples do not depend on the position of the frame.
+#[test]
+fn convert_stereo_unclamped_position() {
+    let left = [
+        1.5f32,
+        -2.,
+        0.5,
+        1e9,
+        -1e9,
+        3.,
+        f32::NAN,
+        0.25,
+        f32::INFINITY,
+        -1.5,
+        1e20,
+    ];
+    let right: Vec<f32> = left.iter().map(|sample| -sample * 1.5).collect();
+    let convert = |left: &[f32], right: &[f32]| {
+        let mut out = vec![0i16; left.len() * 2];
+        fast_float_to_integer::convert_stereo_f32_to_i16_interleaved_unclamped(
+            left, right, &mut out,
+        );
+        out
+    };
+    let all = convert(&left, &right);
+    for start in 0..left.len() {
+        assert_eq!(
+            convert(&left[start..], &right[start..]),
+            all[2 * start..],
+            "{start}"
+        );
+    }
+}
+
+#[test]
+fn convert_stereo() {
+    let left: Vec<f32> = (0..103).map(|i| (i as f32 / 51. - 1.) * 1.1).collect();
+    let right: Vec<f32> = left.iter().map(|sample| -sample * 0.7).collect();
+    let expected = |sample: f32| (sample.clamp(-1., 1.) * 32767.) as i16;
+    let mut out = vec![0i16; left.len() * 2];
+    fast_float_to_integer::convert_stereo_f32_to_i16_interleaved(&left, &right, &mut out);
+    for (index, frame) in out.chunks_exact(2).enumerate() {
+        assert_eq!(frame, [expected(left[index]), expected(right[index])]);
+    }
+
+    let right: Vec<f32> = right.iter().map(|sample| sample.clamp(-1., 1.)).collect();
+    let left: Vec<f32> = left.iter().map(|sample| sample.clamp(-1., 1.)).collect();
+    fast_float_to_integer::convert_stereo_f32_to_i16_interleaved_unclamped(&left, &right, &mut out);
+    for (index, frame) in out.chunks_exact(2).enumerate() {
+        assert_eq!(frame, [expected(left[index]), expected(right[index])]);
+    }
+}
+
+#[test]
+fn convert_planes() {
+    let planes: Vec<Vec<f32>> = (0..3)
+        .map(|plane| (0..77).map(|i| (i * (plane + 1)) as f32 - 30.5).collect())
+        .collect();
+    let src: Vec<&[f32]> = planes.iter().map(Vec::as_slice).collect();
+    let expected: Vec<Vec<i32>> = planes
+        .iter()
+        .map(|plane| plane.iter().map(|float| *float as i32).collect())
+        .collect();
+
+    let mut out = vec![vec![0i32; 77]; 3];
+    let mut dst: Vec<&mut [i32]> = out.iter_mut().map(Vec::as_mut_slice).collect();
+    fast_float_to_integer::convert_planes(&src, &mut dst);
+    assert_eq!(out, expected);
+
+    #[cfg(feature = "rayon")]
+    {
+        let mut out = vec![vec![0i32; 77]; 3];
+        let mut dst: Vec<&mut [i32]> = out.iter_mut().map(Vec::as_mut_slice).collect();
+        fast_float_to_integer::par_convert_planes(&src, &mut dst);
+        assert_eq!(out, expected);
+    }
+}
+
+macro_rules! create_fixed_point_test {
+    ($name:ident, $function:path, $Integer:ty, $fraction_bits:literal) => {
+        #[test]
+        fn $name() {
+            // The reference implementation of CMSIS-DSP with ARM_MATH_ROUNDING.
+            let expected = |float: f32| -> $Integer {
+                let scaled = float * (1u64 << $fraction_bits) as f32;
+                let rounded = scaled + if float > 0. { 0.5 } else { -0.5 };
+                rounded as $Integer
+            };
+            let src: Vec<f32> = interesting_floats_f32()
+                .chain((-300..300).map(|i| i as f32 / 256.))
+                .chain([0.5, -0.5, 1., -1., 1. / 3.])
+                .collect();
+            let mut dst = vec![0 as $Integer; src.len()];
+            $function(&src, &mut dst);
+            for (src, dst) in src.iter().zip(&dst) {
+                assert_eq!(*dst, expected(*src), "{src}");
+            }
+        }
+    };
+}
+
+create_fixed_point_test! {float_to_q7, fast_float_to_integer::float_to_q7, i8, 7}
+create_fixed_point_test! {float_to_q15, fast_float_to_integer::float_to_q15, i16, 15}
+create_fixed_point_test! {float_to_q31, fast_float_to_integer::float_to_q31, i32, 31}
+
+#[test]
+fn is_specialized() {
+    use fast_float_to_integer::{is_specialized, Conversion};
+
+    // The 128 bit conversions use the standard conversion on every target.
+    assert!(!is_specialized(Conversion::F32ToI128));
+    assert!(!is_specialized(Conversion::F64ToU128));
+    let expected = cfg!(all(
+        any(
+            all(
+                any(target_arch = "x86_64", target_arch = "x86"),
+                target_feature = "sse"
+            ),
+            all(target_arch = "aarch64", target_feature = "neon")
+        ),
+        not(feature = "force-default"),
+        not(feature = "portable-deterministic")
+    ));
+    assert_eq!(is_specialized(Conversion::F32ToI32), expected);
+
+    // Extra target features like AVX must not fall back to the standard conversion. The xtask runs this test with several feature combinations.
+    let specialized = Conversion::ALL
+        .into_iter()
+        .filter(|conversion| is_specialized(*conversion))
+        .count();
+    let expected = if cfg!(any(
+        feature = "force-default",
+        feature = "portable-deterministic"
+    )) {
+        0
+    } else if cfg!(all(target_arch = "x86_64", target_feature = "sse")) {
+        Conversion::ALL.len() - 4
+    } else if cfg!(all(target_arch = "x86", target_feature = "sse")) {
+        Conversion::ALL.len() - 8
+    } else if cfg!(all(target_arch = "aarch64", target_feature = "neon")) {
+        // The scalar conversions are the ones of the default target module, but the rounding conversions use FCVTMS and similar instructions except for 128 bit integers.
+        Conversion::ALL.len() - 4
+    } else {
+        0
+    };
+    assert_eq!(specialized, expected);
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2"
+))]
+#[test]
+fn simd_registers() {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::*;
+
+    use fast_float_to_integer::{
+        m128_to_i32x4, m128_to_i32x4_saturating, m128d_to_i32x2, m128d_to_i32x2_saturating,
+    };
+
+    let floats_f32: Vec<f32> = interesting_floats_f32()
+        .chain([f32::NAN, f32::INFINITY, f32::NEG_INFINITY])
+        .collect();
+    for floats in floats_f32.chunks_exact(4) {
+        let register = unsafe { _mm_loadu_ps(floats.as_ptr()) };
+        let mut integers = [0i32; 4];
+        unsafe { _mm_storeu_si128(integers.as_mut_ptr().cast(), m128_to_i32x4(register)) };
+        for (float, integer) in floats.iter().zip(integers) {
+            if InRange::<i32>::in_range(*float) {
+                assert_eq!(integer, *float as i32, "{float}");
+            }
+        }
+        unsafe {
+            _mm_storeu_si128(
+                integers.as_mut_ptr().cast(),
+                m128_to_i32x4_saturating(register),
+            )
+        };
+        let expected: Vec<i32> = floats.iter().map(|float| *float as i32).collect();
+        assert_eq!(integers.as_slice(), expected, "{floats:?}");
+    }
+
+    let floats_f64: Vec<f64> = interesting_floats_f64()
+        .chain([f64::NAN, f64::INFINITY, f64::NEG_INFINITY])
+        .collect();
+    for floats in floats_f64.chunks_exact(2) {
+        let register = unsafe { _mm_loadu_pd(floats.as_ptr()) };
+        let mut integers = [0i32; 4];
+        unsafe { _mm_storeu_si128(integers.as_mut_ptr().cast(), m128d_to_i32x2(register)) };
+        for (float, integer) in floats.iter().zip(integers) {
+            if InRange::<i32>::in_range(*float) {
+                assert_eq!(integer, *float as i32, "{float}");
+            }
+        }
+        assert_eq!(integers[2..], [0, 0]);
+        unsafe {
+            _mm_storeu_si128(
+                integers.as_mut_ptr().cast(),
+                m128d_to_i32x2_saturating(register),
+            )
+        };
+        assert_eq!(
+            integers,
+            [floats[0] as i32, floats[1] as i32, 0, 0],
+            "{floats:?}"
+        );
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+#[test]
+fn simd_registers() {
+    use std::arch::aarch64::*;
+
+    use fast_float_to_integer::{
+        float32x4_to_int32x4, float32x4_to_uint32x4, float64x2_to_int64x2, float64x2_to_uint64x2,
+    };
+
+    let floats_f32: Vec<f32> = interesting_floats_f32()
+        .chain([f32::NAN, f32::INFINITY, f32::NEG_INFINITY])
+        .collect();
+    for floats in floats_f32.chunks_exact(4) {
+        let register = unsafe { vld1q_f32(floats.as_ptr()) };
+        let mut signed = [0i32; 4];
+        let mut unsigned = [0u32; 4];
+        unsafe { vst1q_s32(signed.as_mut_ptr(), float32x4_to_int32x4(register)) };
+        unsafe { vst1q_u32(unsigned.as_mut_ptr(), float32x4_to_uint32x4(register)) };
+        for ((float, signed), unsigned) in floats.iter().zip(signed).zip(unsigned) {
+            assert_eq!(signed, *float as i32, "{float}");
+            assert_eq!(unsigned, *float as u32, "{float}");
+        }
+    }
+
+    let floats_f64: Vec<f64> = interesting_floats_f64()
+        .chain([f64::NAN, f64::INFINITY, f64::NEG_INFINITY])
+        .collect();
+    for floats in floats_f64.chunks_exact(2) {
+        let register = unsafe { vld1q_f64(floats.as_ptr()) };
+        let mut signed = [0i64; 2];
+        let mut unsigned = [0u64; 2];
+        unsafe { vst1q_s64(signed.as_mut_ptr(), float64x2_to_int64x2(register)) };
+        unsafe { vst1q_u64(unsigned.as_mut_ptr(), float64x2_to_uint64x2(register)) };
+        for ((float, signed), unsigned) in floats.iter().zip(signed).zip(unsigned) {
+            assert_eq!(signed, *float as i64, "{float}");
+            assert_eq!(unsigned, *float as u64, "{float}");
+        }
+    }
+}
+
+// The xtask knows the functions by name. Check that its manifest tracks the API.
+#[allow(dead_code)]
+mod manifest {
+    include!("../xtask/src/manifest.rs");
+}
+
+#[test]
+fn manifest() {
+    use fast_float_to_integer::Conversion;
+
+    // F32ToI8 is f32_to_i8.
+    let expected: Vec<String> = Conversion::ALL
+        .iter()
+        .map(|conversion| {
+            format!("{conversion:?}")
+                .to_lowercase()
+                .replacen("to", "_to_", 1)
+        })
+        .collect();
+    assert_eq!(manifest::CONVERSIONS, expected);
+
+    for family in manifest::FAMILIES {
+        assert!(family.module.starts_with("fast_float_to_integer"));
+        assert_eq!(family.directory.is_empty(), family.suffix.is_empty());
+    }
+
+    for (_, conversions) in manifest::BRANCH_FREE {
+        assert!(conversions
+            .iter()
+            .all(|conversion| manifest::CONVERSIONS.contains(conversion)));
+    }
+}
+
+#[test]
+fn ct() {
+    // The ct module has the same functions as the crate root.
+    use fast_float_to_integer::ct;
+
+    assert_eq!(ct::f32_to_i32(-2.5), -2);
+    assert_eq!(ct::f64_to_u8(255.9), 255);
+    #[cfg(not(target_arch = "x86"))]
+    assert_eq!(ct::f64_to_u64(1.5), 1);
+}
+
+#[cfg(all(feature = "avx512", target_arch = "x86_64"))]
+macro_rules! create_avx512_test {
+    ($name:ident, $interesting_floats_function:ident, $convert_custom:path, $Integer:ty) => {
+        #[test]
+        fn $name() {
+            // Without CPU support the function must not be called.
+            if !is_x86_feature_detected!("avx512f") {
+                return;
+            }
+            for float in $interesting_floats_function() {
+                if InRange::<$Integer>::in_range(float) {
+                    let result = unsafe { $convert_custom(float) };
+                    assert_eq!(result, float as $Integer, "{float:.0}");
+                }
+            }
+        }
+    };
+}
+
+#[cfg(all(feature = "avx512", target_arch = "x86_64"))]
+mod avx512 {
+    use super::*;
+
+    create_avx512_test! {f32_u8, interesting_floats_f32, fast_float_to_integer::f32_to_u8_avx512, u8}
+    create_avx512_test! {f32_u16, interesting_floats_f32, fast_float_to_integer::f32_to_u16_avx512, u16}
+    create_avx512_test! {f32_u32, interesting_floats_f32, fast_float_to_integer::f32_to_u32_avx512, u32}
+    create_avx512_test! {f32_u64, interesting_floats_f32, fast_float_to_integer::f32_to_u64_avx512, u64}
+    create_avx512_test! {f64_u8, interesting_floats_f64, fast_float_to_integer::f64_to_u8_avx512, u8}
+    create_avx512_test! {f64_u16, interesting_floats_f64, fast_float_to_integer::f64_to_u16_avx512, u16}
+    create_avx512_test! {f64_u32, interesting_floats_f64, fast_float_to_integer::f64_to_u32_avx512, u32}
+    create_avx512_test! {f64_u64, interesting_floats_f64, fast_float_to_integer::f64_to_u64_avx512, u64}
+}
+
+// Subnormal floats convert to 0. With DAZ the CPU reads them as 0, which converts to 0 too. This makes the result independent of the floating point mode.
+macro_rules! create_subnormal_test {
+    ($name:ident, $function:ident, $bits_function:ident, $Float:ty, $Integer:ty) => {
+        #[test]
+        fn $name() {
+            use fast_float_to_integer as ffti;
+
+            let smallest = <$Float>::from_bits(1);
+            let largest = <$Float>::from_bits(<$Float>::MIN_POSITIVE.to_bits() - 1);
+            let floats = [smallest, largest, <$Float>::MIN_POSITIVE / 3.]
+                .into_iter()
+                .flat_map(|float| [float, -float])
+                .chain([0., -0.]);
+            for float in floats {
+                assert_eq!(ffti::$function(float), 0, "{float:e}");
+                assert_eq!(ffti::$bits_function(float.to_bits()), 0, "{float:e}");
+                let src = [float; 67];
+                let mut dst: [$Integer; 67] = [1; 67];
+                ffti::convert_slice(&src, &mut dst);
+                assert!(dst.iter().all(|integer| *integer == 0), "{float:e}");
+                dst = [1; 67];
+                ffti::saturating_convert_slice(&src, &mut dst);
+                assert!(dst.iter().all(|integer| *integer == 0), "{float:e}");
+                dst = [1; 67];
+                ffti::round_ties_even_convert_slice(&src, &mut dst);
+                assert!(dst.iter().all(|integer| *integer == 0), "{float:e}");
+            }
+        }
+    };
+}
+
+create_subnormal_test! {subnormal_f32_i8, f32_to_i8, f32_bits_to_i8, f32, i8}
+create_subnormal_test! {subnormal_f32_u8, f32_to_u8, f32_bits_to_u8, f32, u8}
+create_subnormal_test! {subnormal_f32_i16, f32_to_i16, f32_bits_to_i16, f32, i16}
+create_subnormal_test! {subnormal_f32_u16, f32_to_u16, f32_bits_to_u16, f32, u16}
+create_subnormal_test! {subnormal_f32_i32, f32_to_i32, f32_bits_to_i32, f32, i32}
+create_subnormal_test! {subnormal_f32_u32, f32_to_u32, f32_bits_to_u32, f32, u32}
+create_subnormal_test! {subnormal_f32_i64, f32_to_i64, f32_bits_to_i64, f32, i64}
+create_subnormal_test! {subnormal_f32_u64, f32_to_u64, f32_bits_to_u64, f32, u64}
+create_subnormal_test! {subnormal_f32_i128, f32_to_i128, f32_bits_to_i128, f32, i128}
+create_subnormal_test! {subnormal_f32_u128, f32_to_u128, f32_bits_to_u128, f32, u128}
+
+create_subnormal_test! {subnormal_f64_i8, f64_to_i8, f64_bits_to_i8, f64, i8}
+create_subnormal_test! {subnormal_f64_u8, f64_to_u8, f64_bits_to_u8, f64, u8}
+create_subnormal_test! {subnormal_f64_i16, f64_to_i16, f64_bits_to_i16, f64, i16}
+create_subnormal_test! {subnormal_f64_u16, f64_to_u16, f64_bits_to_u16, f64, u16}
+create_subnormal_test! {subnormal_f64_i32, f64_to_i32, f64_bits_to_i32, f64, i32}
+create_subnormal_test! {subnormal_f64_u32, f64_to_u32, f64_bits_to_u32, f64, u32}
+create_subnormal_test! {subnormal_f64_i64, f64_to_i64, f64_bits_to_i64, f64, i64}
+create_subnormal_test! {subnormal_f64_u64, f64_to_u64, f64_bits_to_u64, f64, u64}
+create_subnormal_test! {subnormal_f64_i128, f64_to_i128, f64_bits_to_i128, f64, i128}
+create_subnormal_test! {subnormal_f64_u128, f64_to_u128, f64_bits_to_u128, f64, u128}
+
+// Rust assumes the default floating point environment. Changing MXCSR is not supported by the language, so this test is ignored and only for checking the hardware by hand. It enables FTZ and DAZ and runs the subnormal tests again.
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse"
+))]
+#[test]
+#[ignore]
+#[allow(deprecated)]
+fn subnormal_ftz_daz() {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::{_mm_getcsr, _mm_setcsr};
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::{_mm_getcsr, _mm_setcsr};
+
+    const FTZ: u32 = 1 << 15;
+    const DAZ: u32 = 1 << 6;
+
+    let csr = unsafe { _mm_getcsr() };
+    unsafe { _mm_setcsr(csr | FTZ | DAZ) };
+    // DAZ is in effect if a subnormal compares equal to 0.
+    let is_zero = std::hint::black_box(f32::from_bits(1)) == 0.;
+    subnormal_f32_i32();
+    subnormal_f32_u64();
+    subnormal_f64_i64();
+    subnormal_f64_u32();
+    subnormal_f32_u128();
+    subnormal_f64_i8();
+    unsafe { _mm_setcsr(csr) };
+    assert!(is_zero);
+}
+
+macro_rules! check_table {
+    ($conversion:ident, $interesting_floats_function:ident, $Float:ty, $Integer:ty) => {{
+        use fast_float_to_integer::{Conversion, CONVERSIONS};
+
+        let conversion = Conversion::$conversion;
+        assert_eq!(
+            Conversion::new(conversion.from(), conversion.to()),
+            conversion
+        );
+        for float in $interesting_floats_function() {
+            if InRange::<$Integer>::in_range(float) {
+                let result = CONVERSIONS[conversion](float.to_bits() as u64) as $Integer;
+                assert_eq!(result, float as $Integer, "{float:.0}");
+                assert_eq!(
+                    CONVERSIONS.get(conversion)(float.to_bits() as u64),
+                    result as u128
+                );
+            }
+        }
+    }};
+}
+
+#[test]
+fn conversion_table() {
+    check_table!(F32ToI8, interesting_floats_f32, f32, i8);
+    check_table!(F32ToU8, interesting_floats_f32, f32, u8);
+    check_table!(F32ToI16, interesting_floats_f32, f32, i16);
+    check_table!(F32ToU16, interesting_floats_f32, f32, u16);
+    check_table!(F32ToI32, interesting_floats_f32, f32, i32);
+    check_table!(F32ToU32, interesting_floats_f32, f32, u32);
+    check_table!(F32ToI64, interesting_floats_f32, f32, i64);
+    check_table!(F32ToU64, interesting_floats_f32, f32, u64);
+    check_table!(F32ToI128, interesting_floats_f32, f32, i128);
+    check_table!(F32ToU128, interesting_floats_f32, f32, u128);
+
+    check_table!(F64ToI8, interesting_floats_f64, f64, i8);
+    check_table!(F64ToU8, interesting_floats_f64, f64, u8);
+    check_table!(F64ToI16, interesting_floats_f64, f64, i16);
+    check_table!(F64ToU16, interesting_floats_f64, f64, u16);
+    check_table!(F64ToI32, interesting_floats_f64, f64, i32);
+    check_table!(F64ToU32, interesting_floats_f64, f64, u32);
+    check_table!(F64ToI64, interesting_floats_f64, f64, i64);
+    check_table!(F64ToU64, interesting_floats_f64, f64, u64);
+    check_table!(F64ToI128, interesting_floats_f64, f64, i128);
+    check_table!(F64ToU128, interesting_floats_f64, f64, u128);
+
+    // The upper bits of the input are ignored for f32.
+    let convert = fast_float_to_integer::CONVERSIONS[fast_float_to_integer::Conversion::F32ToI32];
+    assert_eq!(
+        convert(u64::MAX << 32 | (-5f32).to_bits() as u64) as i32,
+        -5
+    );
+}
+
+// The portable-deterministic feature promises the same results on every target, including for out of range inputs and NaN. The xtask runs this test on every target, so the pinned hash compares the results across targets. The inputs are built from bits without float arithmetic, which could differ between targets.
+#[cfg(feature = "portable-deterministic")]
+#[test]
+fn portable_deterministic() {
+    use fast_float_to_integer as ffti;
+
+    let mut random = Random(0x2545_f491_4f6c_dd1d);
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut update = |integer: u128| {
+        for byte in integer.to_le_bytes() {
+            hash = (hash ^ byte as u64).wrapping_mul(0x100_0000_01b3);
+        }
+    };
+    for _ in 0..10_000 {
+        let bits = random.next();
+        // Half of the inputs have an exponent that is in range of most integer types.
+        let (f32_bits, f64_bits) = if random.next() % 2 == 0 {
+            (bits as u32, bits)
+        } else {
+            let exponent = random.next() % 140;
+            (
+                (bits as u32 & 0x807f_ffff) | ((exponent as u32 + 120) << 23),
+                (bits & 0x800f_ffff_ffff_ffff) | ((exponent + 1016) << 52),
+            )
+        };
+        let float = f32::from_bits(f32_bits);
+        update(ffti::f32_to_i8(float) as u128);
+        update(ffti::f32_to_u8(float) as u128);
+        update(ffti::f32_to_i16(float) as u128);
+        update(ffti::f32_to_u16(float) as u128);
+        update(ffti::f32_to_i32(float) as u128);
+        update(ffti::f32_to_u32(float) as u128);
+        update(ffti::f32_to_i64(float) as u128);
+        update(ffti::f32_to_u64(float) as u128);
+        update(ffti::f32_to_i128(float) as u128);
+        update(ffti::f32_to_u128(float));
+        let float = f64::from_bits(f64_bits);
+        update(ffti::f64_to_i8(float) as u128);
+        update(ffti::f64_to_u8(float) as u128);
+        update(ffti::f64_to_i16(float) as u128);
+        update(ffti::f64_to_u16(float) as u128);
+        update(ffti::f64_to_i32(float) as u128);
+        update(ffti::f64_to_u32(float) as u128);
+        update(ffti::f64_to_i64(float) as u128);
+        update(ffti::f64_to_u64(float) as u128);
+        update(ffti::f64_to_i128(float) as u128);
+        update(ffti::f64_to_u128(float));
+    }
+    assert_eq!(hash, 0xfea5_05c9_47c5_6e26);
+}
+
+// behavior reports that NaN converts to 0 for the 8 bit outputs, which must hold for every payload and sign.
+#[cfg(feature = "portable-deterministic")]
+#[test]
+fn portable_deterministic_nan() {
+    use fast_float_to_integer as ffti;
+
+    let mut random = Random(0x9e37_79b9_7f4a_7c15);
+    for _ in 0..10_000 {
+        let bits = random.next();
+        let float = f32::from_bits(bits as u32 | 0x7fc0_0000);
+        assert_eq!(ffti::f32_to_i8(float), 0, "{:x}", float.to_bits());
+        assert_eq!(ffti::f32_to_u8(float), 0, "{:x}", float.to_bits());
+        let float = f64::from_bits(bits | 0x7ff8_0000_0000_0000);
+        assert_eq!(ffti::f64_to_i8(float), 0, "{:x}", float.to_bits());
+        assert_eq!(ffti::f64_to_u8(float), 0, "{:x}", float.to_bits());
+    }
+}
+
+macro_rules! create_grid_test {
+    ($name:ident, $interesting_floats_function:ident, $floor:ident, $round:ident, $Float:ty) => {
+        #[test]
+        fn $name() {
+            use fast_float_to_integer::{$floor, $round};
+
+            // Power of two cell sizes make the division exact, so the reference computes the same product.
+            let floats = $interesting_floats_function()
+                .chain([-0.5, -0.0, 0.25, 7.5, -7.5, -8.0, 8.5, -8.5])
+                .filter(|float| float.abs() < 1e8);
+            for float in floats {
+                assert_eq!(
+                    $floor::<4, 1>(float),
+                    (float / 4.).floor() as i32,
+                    "{float}"
+                );
+                assert_eq!(
+                    $floor::<1, 8>(float),
+                    (float * 8.).floor() as i32,
+                    "{float}"
+                );
+                assert_eq!(
+                    $round::<4, 1>(float),
+                    round_ties_even((float / 4.) as f64) as i32,
+                    "{float}"
+                );
+                assert_eq!(
+                    $round::<1, 8>(float),
+                    round_ties_even((float * 8.) as f64) as i32,
+                    "{float}"
+                );
+            }
+        }
+    };
+}
+
+create_grid_test! {grid_f32, interesting_floats_f32, f32_to_grid_floor, f32_to_grid_round, f32}
+create_grid_test! {grid_f64, interesting_floats_f64, f64_to_grid_floor, f64_to_grid_round, f64}
+
+#[test]
+fn nan_boxed() {
+    use fast_float_to_integer::{nan_boxed_to_i32, nan_boxed_to_u64, NanBoxed};
+
+    for float in interesting_floats_f64().filter(|float| InRange::<i32>::in_range(*float)) {
+        assert_eq!(
+            nan_boxed_to_i32(float.to_bits()),
+            NanBoxed::Number(float as i32),
+            "{float}"
+        );
+    }
+    // Boxed values with either sign and the largest payload.
+    assert_eq!(
+        nan_boxed_to_i32(0x7ff8_0000_0000_0001),
+        NanBoxed::Payload(1)
+    );
+    assert_eq!(
+        nan_boxed_to_i32(0xfff8_0000_0000_0007),
+        NanBoxed::Payload(7)
+    );
+    assert_eq!(
+        nan_boxed_to_u64(0x7fff_ffff_ffff_ffff),
+        NanBoxed::Payload(0x0007_ffff_ffff_ffff)
+    );
+    // The NaN that float operations produce, signaling NaNs and infinities are numbers.
+    for bits in [
+        f64::NAN.to_bits(),
+        (-f64::NAN).to_bits(),
+        0x7ff0_0000_0000_0001,
+        f64::INFINITY.to_bits(),
+        f64::NEG_INFINITY.to_bits(),
+    ] {
+        assert!(
+            matches!(nan_boxed_to_u64(bits), NanBoxed::Number(_)),
+            "{bits:x}"
+        );
+    }
+}
+
+#[test]
+fn seconds_nanos() {
+    use fast_float_to_integer::{f64_to_duration, f64_to_seconds_nanos};
+    use std::time::Duration;
+
+    let floats = interesting_floats_f64()
+        .chain([
+            0.1,
+            -0.1,
+            1e-9,
+            -1e-9,
+            0.4e-9,
+            0.6e-9,
+            1. - 1e-12,
+            -1. + 1e-12,
+        ])
+        .filter(|float| float.abs() < 1e15);
+    for float in floats {
+        let mut whole = float.floor();
+        let mut nanos = round_ties_even((float - whole) * 1e9);
+        if nanos == 1e9 {
+            whole += 1.;
+            nanos = 0.;
+        }
+        let expected = (whole as i64, nanos as u32);
+        assert_eq!(f64_to_seconds_nanos(float), expected, "{float}");
+        if float >= 0. {
+            assert_eq!(
+                f64_to_duration(float),
+                Duration::new(expected.0 as u64, expected.1),
+                "{float}"
+            );
+        }
+    }
+    // Out of range inputs are unspecified but the nanoseconds stay below one second.
+    for float in [f64::NAN, f64::INFINITY, -1e30, 1e30] {
+        assert!(f64_to_seconds_nanos(float).1 < 1_000_000_000, "{float}");
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn measure_conversion_throughput() {
+    let data: Vec<f64> = (0..100).map(|i| i as f64 + 0.5).collect();
+    let throughput = fast_float_to_integer::measure_conversion_throughput(
+        fast_float_to_integer::f64_to_u16,
+        &data,
+    );
+    assert_eq!(throughput.elements, 100);
+    assert!(throughput.nanos_per_element() >= 0.);
+    let report = throughput.to_string();
+    assert!(report.starts_with("100 elements, fastest of "), "{report}");
+
+    let empty =
+        fast_float_to_integer::measure_conversion_throughput(fast_float_to_integer::f32_to_i8, &[]);
+    assert_eq!(empty.nanos_per_element(), 0.);
+}
+
+#[test]
+fn exact_pow2() {
+    use fast_float_to_integer::{exact_pow2, exact_pow2_f32, exact_pow2_f64};
+
+    // Halving from 1 is exact down to the smallest subnormal value.
+    let mut expected = 1f32;
+    for exponent in (-149..=0).rev() {
+        assert_eq!(exact_pow2_f32(exponent), expected, "{exponent}");
+        expected /= 2.;
+    }
+    assert_eq!(expected, 0.);
+    let mut expected = 1f32;
+    for exponent in 0..=127 {
+        assert_eq!(exact_pow2_f32(exponent), expected, "{exponent}");
+        expected *= 2.;
+    }
+    assert_eq!(expected, f32::INFINITY);
+
+    let mut expected = 1f64;
+    for exponent in (-1074..=0).rev() {
+        assert_eq!(exact_pow2_f64(exponent), expected, "{exponent}");
+        assert_eq!(exact_pow2::<f64>(exponent), expected, "{exponent}");
+        expected /= 2.;
+    }
+    assert_eq!(expected, 0.);
+    let mut expected = 1f64;
+    for exponent in 0..=1023 {
+        assert_eq!(exact_pow2_f64(exponent), expected, "{exponent}");
+        assert_eq!(exact_pow2::<f64>(exponent), expected, "{exponent}");
+        expected *= 2.;
+    }
+    assert_eq!(expected, f64::INFINITY);
+
+    for exponent in [-150, 128, i32::MIN, i32::MAX] {
+        assert!(std::panic::catch_unwind(|| exact_pow2_f32(exponent)).is_err());
+    }
+    for exponent in [-1075, 1024, i32::MIN, i32::MAX] {
+        assert!(std::panic::catch_unwind(|| exact_pow2::<f64>(exponent)).is_err());
+    }
+}
+
+macro_rules! create_rounding_test {
+    ($name:ident, $interesting_floats_function:ident, $floor:ident, $ceil:ident, $round:ident, $round_ties_even:ident, $Float:ty, $Integer:ty) => {
+        #[test]
+        fn $name() {
+            use fast_float_to_integer::{$ceil, $floor, $round, $round_ties_even};
+
+            let floats = $interesting_floats_function()
+                .chain([
+                    0.5,
+                    1.5,
+                    2.5,
+                    -0.5,
+                    -1.5,
+                    -2.5,
+                    0.49999997,
+                    -0.49999997,
+                    126.5,
+                    -127.5,
+                ])
+                .filter(|float| float.is_finite());
+            for float in floats {
+                let expected = [
+                    float.floor(),
+                    float.ceil(),
+                    float.round(),
+                    round_ties_even(float as f64) as $Float,
+                ];
+                let actual = [
+                    $floor(float),
+                    $ceil(float),
+                    $round(float),
+                    $round_ties_even(float),
+                ];
+                for (expected, actual) in expected.into_iter().zip(actual) {
+                    if InRange::<$Integer>::in_range(expected) {
+                        assert_eq!(actual, expected as $Integer, "{float}");
+                    }
+                }
+            }
+        }
+    };
+}
+
+create_rounding_test! {rounding_f32_to_i8, interesting_floats_f32, f32_to_i8_floor, f32_to_i8_ceil, f32_to_i8_round, f32_to_i8_round_ties_even, f32, i8}
+create_rounding_test! {rounding_f32_to_u8, interesting_floats_f32, f32_to_u8_floor, f32_to_u8_ceil, f32_to_u8_round, f32_to_u8_round_ties_even, f32, u8}
+create_rounding_test! {rounding_f32_to_i16, interesting_floats_f32, f32_to_i16_floor, f32_to_i16_ceil, f32_to_i16_round, f32_to_i16_round_ties_even, f32, i16}
+create_rounding_test! {rounding_f32_to_u16, interesting_floats_f32, f32_to_u16_floor, f32_to_u16_ceil, f32_to_u16_round, f32_to_u16_round_ties_even, f32, u16}
+create_rounding_test! {rounding_f32_to_i32, interesting_floats_f32, f32_to_i32_floor, f32_to_i32_ceil, f32_to_i32_round, f32_to_i32_round_ties_even, f32, i32}
+create_rounding_test! {rounding_f32_to_u32, interesting_floats_f32, f32_to_u32_floor, f32_to_u32_ceil, f32_to_u32_round, f32_to_u32_round_ties_even, f32, u32}
+create_rounding_test! {rounding_f32_to_i64, interesting_floats_f32, f32_to_i64_floor, f32_to_i64_ceil, f32_to_i64_round, f32_to_i64_round_ties_even, f32, i64}
+create_rounding_test! {rounding_f32_to_u64, interesting_floats_f32, f32_to_u64_floor, f32_to_u64_ceil, f32_to_u64_round, f32_to_u64_round_ties_even, f32, u64}
+create_rounding_test! {rounding_f32_to_i128, interesting_floats_f32, f32_to_i128_floor, f32_to_i128_ceil, f32_to_i128_round, f32_to_i128_round_ties_even, f32, i128}
+create_rounding_test! {rounding_f32_to_u128, interesting_floats_f32, f32_to_u128_floor, f32_to_u128_ceil, f32_to_u128_round, f32_to_u128_round_ties_even, f32, u128}
+create_rounding_test! {rounding_f64_to_i8, interesting_floats_f64, f64_to_i8_floor, f64_to_i8_ceil, f64_to_i8_round, f64_to_i8_round_ties_even, f64, i8}
+create_rounding_test! {rounding_f64_to_u8, interesting_floats_f64, f64_to_u8_floor, f64_to_u8_ceil, f64_to_u8_round, f64_to_u8_round_ties_even, f64, u8}
+create_rounding_test! {rounding_f64_to_i16, interesting_floats_f64, f64_to_i16_floor, f64_to_i16_ceil, f64_to_i16_round, f64_to_i16_round_ties_even, f64, i16}
+create_rounding_test! {rounding_f64_to_u16, interesting_floats_f64, f64_to_u16_floor, f64_to_u16_ceil, f64_to_u16_round, f64_to_u16_round_ties_even, f64, u16}
+create_rounding_test! {rounding_f64_to_i32, interesting_floats_f64, f64_to_i32_floor, f64_to_i32_ceil, f64_to_i32_round, f64_to_i32_round_ties_even, f64, i32}
+create_rounding_test! {rounding_f64_to_u32, interesting_floats_f64, f64_to_u32_floor, f64_to_u32_ceil, f64_to_u32_round, f64_to_u32_round_ties_even, f64, u32}
+create_rounding_test! {rounding_f64_to_i64, interesting_floats_f64, f64_to_i64_floor, f64_to_i64_ceil, f64_to_i64_round, f64_to_i64_round_ties_even, f64, i64}
+create_rounding_test! {rounding_f64_to_u64, interesting_floats_f64, f64_to_u64_floor, f64_to_u64_ceil, f64_to_u64_round, f64_to_u64_round_ties_even, f64, u64}
+create_rounding_test! {rounding_f64_to_i128, interesting_floats_f64, f64_to_i128_floor, f64_to_i128_ceil, f64_to_i128_round, f64_to_i128_round_ties_even, f64, i128}
+create_rounding_test! {rounding_f64_to_u128, interesting_floats_f64, f64_to_u128_floor, f64_to_u128_ceil, f64_to_u128_round, f64_to_u128_round_ties_even, f64, u128}
+
+macro_rules! create_pretruncated_test {
+    ($name:ident, $interesting_floats_function:ident, $pretruncated:ident, $Integer:ty) => {
+        #[test]
+        fn $name() {
+            use fast_float_to_integer::$pretruncated;
+
+            let floats = $interesting_floats_function()
+                .filter(|float| float.fract() == 0. && InRange::<$Integer>::in_range(*float));
+            for float in floats {
+                assert_eq!($pretruncated(float), float as $Integer, "{float}");
+            }
+        }
+    };
+}
+
+create_pretruncated_test! {pretruncated_f32_i8, interesting_floats_f32, f32_to_i8_pretruncated, i8}
+create_pretruncated_test! {pretruncated_f32_u32, interesting_floats_f32, f32_to_u32_pretruncated, u32}
+create_pretruncated_test! {pretruncated_f32_u64, interesting_floats_f32, f32_to_u64_pretruncated, u64}
+create_pretruncated_test! {pretruncated_f64_i32, interesting_floats_f64, f64_to_i32_pretruncated, i32}
+create_pretruncated_test! {pretruncated_f64_i64, interesting_floats_f64, f64_to_i64_pretruncated, i64}
+create_pretruncated_test! {pretruncated_f64_u128, interesting_floats_f64, f64_to_u128_pretruncated, u128}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic = "the float has a fractional part"]
+fn pretruncated_fraction() {
+    fast_float_to_integer::f32_to_i32_pretruncated(1.5);
+}
+
+#[test]
+fn checked_convert_slice() {
+    let src: Vec<f32> = (0..150)
+        .map(|i| match i % 7 {
+            0 => f32::NAN,
+            1 => 1e10,
+            2 => -1e10,
+            _ => i as f32 - 75.5,
+        })
+        .collect();
+    let expected: Vec<usize> = (0..src.len())
+        .filter(|index| !InRange::<i16>::in_range(src[*index]))
+        .collect();
+
+    let mut dst = vec![0i16; src.len()];
+    // The byte after the bitmap is left untouched.
+    let mut invalid = [0xffu8; 20];
+    let count = fast_float_to_integer::checked_convert_slice_bitmap(&src, &mut dst, &mut invalid);
+    assert_eq!(count, expected.len());
+    for (index, float) in src.iter().enumerate() {
+        let marked = invalid[index / 8] & (1 << (index % 8)) != 0;
+        assert_eq!(marked, expected.contains(&index), "{index}");
+        if !marked {
+            assert_eq!(dst[index], *float as i16, "{index}");
+        }
+    }
+    assert_eq!(invalid[18] >> 6, 0);
+    assert_eq!(invalid[19], 0xff);
+
+    #[cfg(feature = "std")]
+    {
+        let mut invalid = vec![usize::MAX];
+        let valid =
+            fast_float_to_integer::checked_convert_slice_indices(&src, &mut dst, &mut invalid);
+        assert!(!valid);
+        assert_eq!(invalid[0], usize::MAX);
+        assert_eq!(invalid[1..], expected);
+        assert!(fast_float_to_integer::checked_convert_slice_indices(
+            &[1.0f32, 2.0],
+            &mut [0u8; 2],
+            &mut invalid
+        ));
+    }
+}
+
+#[cfg(feature = "arbitrary-int")]
+macro_rules! create_arbitrary_int_test {
+    ($name:ident, $interesting_floats_function:ident, $Float:ty, $function:ident, [$($Type:ty),+]) => {
+        #[test]
+        fn $name() {
+            use arbitrary_int::traits::Integer;
+            use fast_float_to_integer::$function;
+
+            for float in $interesting_floats_function().filter(|float| !float.is_nan()) {
+                $(
+                    let expected = (float as i64).clamp(<$Type>::MIN.as_i64(), <$Type>::MAX.as_i64());
+                    assert_eq!($function::<$Type>(float).as_i64(), expected, "{float:?} {}", stringify!($Type));
+                )+
+            }
+            // The result for NaN is unspecified but the type cannot hold an invalid value.
+            $(
+                let _: $Type = $function(<$Float>::NAN);
+            )+
+        }
+    };
+}
+
+#[cfg(feature = "arbitrary-int")]
+create_arbitrary_int_test! {arbitrary_int_f32, interesting_floats_f32, f32, f32_to_arbitrary_int, [
+    arbitrary_int::u1, arbitrary_int::u7, arbitrary_int::u12, arbitrary_int::u24, arbitrary_int::u31, arbitrary_int::u63, u8, u32,
+    arbitrary_int::i1, arbitrary_int::i4, arbitrary_int::i12, arbitrary_int::i31, arbitrary_int::i63, i16, i64
+]}
+#[cfg(feature = "arbitrary-int")]
+create_arbitrary_int_test! {arbitrary_int_f64, interesting_floats_f64, f64, f64_to_arbitrary_int, [
+    arbitrary_int::u1, arbitrary_int::u7, arbitrary_int::u12, arbitrary_int::u24, arbitrary_int::u31, arbitrary_int::u63, u8, u32,
+    arbitrary_int::i1, arbitrary_int::i4, arbitrary_int::i12, arbitrary_int::i31, arbitrary_int::i63, i16, i64
+]}
+
+#[cfg(feature = "itoa")]
+#[test]
+fn write_decimal() {
+    // Longer than one chunk and not a multiple of it. The inputs are in range because the results of the conversion are unspecified otherwise.
+    let src: Vec<f64> = (-150..150)
+        .map(|i| i as f64 * 1.25)
+        .chain([i64::MIN as f64, 1e18, -0.0, 0.5])
+        .collect();
+    let expected: String = src
+        .iter()
+        .map(|float| format!("{},", float.round() as i64))
+        .collect();
+    let mut out = Vec::new();
+    fast_float_to_integer::write_decimal(
+        &src,
+        fast_float_to_integer::f64_to_i64_round,
+        b',',
+        &mut out,
+    )
+    .unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), expected);
+
+    let mut out = Vec::new();
+    fast_float_to_integer::write_decimal(&[-1.5f32, 1e30], |float| float as i128, b'\n', &mut out)
+        .unwrap();
+    assert_eq!(out, b"-1\n1000000015047466219876688855040\n");
+
+    let mut out = Vec::new();
+    fast_float_to_integer::write_decimal(
+        &[] as &[f64],
+        fast_float_to_integer::f64_to_i64,
+        b'\n',
+        &mut out,
+    )
+    .unwrap();
+    assert!(out.is_empty());
+}
+
+macro_rules! create_array_test {
+    ($name:ident, $interesting_floats_function:ident, $convert:ident, $x2:ident, $x3:ident, $x4:ident, $x8:ident, $Float:ty, $Integer:ty) => {
+        #[test]
+        fn $name() {
+            use fast_float_to_integer::{$convert, $x2, $x3, $x4, $x8};
+
+            // Every element is converted at the right position, including out of range inputs.
+            let floats: Vec<$Float> = $interesting_floats_function().collect();
+            for window in floats.windows(8) {
+                let expected: Vec<$Integer> = window.iter().copied().map($convert).collect();
+                assert_eq!($x2([window[0], window[1]]), expected[..2]);
+                assert_eq!($x3([window[0], window[1], window[2]]), expected[..3]);
+                assert_eq!($x4(window[..4].try_into().unwrap()), expected[..4]);
+                assert_eq!($x8(window.try_into().unwrap()), expected[..]);
+                let array: [$Integer; 8] =
+                    fast_float_to_integer::convert_array::<$Float, $Integer, 8>(
+                        window.try_into().unwrap(),
+                    );
+                assert_eq!(array, expected[..]);
+            }
+        }
+    };
+}
+
+create_array_test! {array_f32_i8, interesting_floats_f32, f32_to_i8, f32_to_i8_x2, f32_to_i8_x3, f32_to_i8_x4, f32_to_i8_x8, f32, i8}
+create_array_test! {array_f32_i32, interesting_floats_f32, f32_to_i32, f32_to_i32_x2, f32_to_i32_x3, f32_to_i32_x4, f32_to_i32_x8, f32, i32}
+create_array_test! {array_f32_u64, interesting_floats_f32, f32_to_u64, f32_to_u64_x2, f32_to_u64_x3, f32_to_u64_x4, f32_to_u64_x8, f32, u64}
+create_array_test! {array_f64_u16, interesting_floats_f64, f64_to_u16, f64_to_u16_x2, f64_to_u16_x3, f64_to_u16_x4, f64_to_u16_x8, f64, u16}
+create_array_test! {array_f64_i64, interesting_floats_f64, f64_to_i64, f64_to_i64_x2, f64_to_i64_x3, f64_to_i64_x4, f64_to_i64_x8, f64, i64}
+create_array_test! {array_f64_u128, interesting_floats_f64, f64_to_u128, f64_to_u128_x2, f64_to_u128_x3, f64_to_u128_x4, f64_to_u128_x8, f64, u128}
+
+macro_rules! create_small_test {
+    ($name:ident, $small:ident, $Float:ty, $Integer:ty, $limit:literal) => {
+        #[test]
+        fn $name() {
+            use fast_float_to_integer::$small;
+
+            let limit = (2. as $Float).powi($limit);
+            let floats = [
+                0.,
+                -0.,
+                0.5,
+                1.5,
+                2.5,
+                -0.5,
+                -1.5,
+                -2.5,
+                0.49999997,
+                1. - <$Float>::EPSILON,
+                126.5,
+                -127.5,
+                limit,
+                -limit,
+                limit - 1.,
+                -limit + 0.5,
+            ]
+            .into_iter()
+            .chain((-1000..1000).map(|i| i as $Float * 0.37))
+            .filter(|float| {
+                InRange::<$Integer>::in_range(round_ties_even(*float as f64) as $Float)
+            });
+            for float in floats {
+                let expected = round_ties_even(float as f64) as $Integer;
+                assert_eq!($small(float), expected, "{float}");
+            }
+        }
+    };
+}
+
+create_small_test! {small_f32_i8, f32_to_i8_small, f32, i8, 22}
+create_small_test! {small_f32_i32, f32_to_i32_small, f32, i32, 22}
+create_small_test! {small_f32_u64, f32_to_u64_small, f32, u64, 22}
+create_small_test! {small_f64_i32, f64_to_i32_small, f64, i32, 51}
+create_small_test! {small_f64_i64, f64_to_i64_small, f64, i64, 51}
+create_small_test! {small_f64_u128, f64_to_u128_small, f64, u128, 51}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic = "the float is not small"]
+fn small_too_large() {
+    fast_float_to_integer::f32_to_i32_small(8388608.);
+}
+
+#[test]
+fn self_test() {
+    assert_eq!(fast_float_to_integer::self_test(), Ok(()));
+}
@@ -0,0 +1,39 @@
+// The events of the `trace` feature are emitted once per process, so this test has its own test binary.
+
+#![cfg(feature = "trace")]
+
+use std::sync::Mutex;
+
+use fast_float_to_integer::{f32_to_i32, TraceEvent};
+
+static EVENTS: Mutex<Vec<TraceEvent>> = Mutex::new(Vec::new());
+
+#[test]
+fn trace_events() {
+    fast_float_to_integer::set_trace_hook(|event| EVENTS.lock().unwrap().push(*event));
+
+    let mut dst = [0i32; 64];
+    fast_float_to_integer::convert_slice(&[1.5f32; 64], &mut dst);
+    fast_float_to_integer::convert_slice(&[1.5f32; 64], &mut dst);
+    let events = EVENTS.lock().unwrap().clone();
+    if cfg!(feature = "small-code") {
+        // Every slice takes the scalar path.
+        assert_eq!(events[1..], [TraceEvent::ScalarSlice { len: 64, lanes: 4 }]);
+        return;
+    }
+    assert_eq!(events.len(), 1);
+    let TraceEvent::Selected { target, small_code } = events[0] else {
+        panic!("{:?}", events[0]);
+    };
+    assert!(target.starts_with("target_"));
+    assert_eq!(small_code, cfg!(feature = "small-code"));
+
+    // A slice that is shorter than one kernel call of f32 to i32.
+    let mut dst = [0i32; 1];
+    fast_float_to_integer::convert_slice(&[1.5f32], &mut dst);
+    fast_float_to_integer::convert_slice(&[1.5f32], &mut dst);
+    assert_eq!(dst, [f32_to_i32(1.5)]);
+    let events = EVENTS.lock().unwrap().clone();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[1], TraceEvent::ScalarSlice { len: 1, lanes: 4 });
+}
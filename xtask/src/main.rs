@@ -8,18 +8,43 @@ use std::{
 };
 
 use anyhow::{anyhow, Context, Result};
+use manifest::{Family, BRANCH_FREE, CONVERSIONS, FAMILIES};
 use regex::Regex;
 
+mod manifest;
+
 fn main() -> Result<()> {
-    let command = std::env::args()
+    let command_name = std::env::args()
         .nth(1)
         .context("missing command argument")?;
-    let command = match command.as_str() {
+    // This command does not depend on the targets below.
+    if command_name == "no-std" {
+        return no_std();
+    }
+    if command_name == "msrv" {
+        return msrv();
+    }
+    if command_name == "build-std" {
+        return build_std();
+    }
+    if command_name == "fingerprint" {
+        return fingerprint();
+    }
+    if command_name == "speedup-report" {
+        return speedup_report(std::env::args().nth(2).as_deref());
+    }
+
+    if matches!(command_name.as_str(), "asm" | "all") {
+        check_assembly_toolchain()?;
+    }
+
+    let command = match command_name.as_str() {
         "check" => |target: &Target| check(target, false),
         "clippy" => |target: &Target| check(target, true),
         "target" => |target: &Target| expected_target(target),
         "test" => |target: &Target| qemu_test(target),
         "asm" => |target: &Target| show_asm(target),
+        "size" => |target: &Target| size(target),
         "all" => |target: &Target| {
             check(target, true).context("check")?;
             expected_target(target).context("target")?;
@@ -36,6 +61,35 @@ fn main() -> Result<()> {
         command(target)?;
     }
 
+    if matches!(
+        command_name.as_str(),
+        "check" | "clippy" | "target" | "test" | "all"
+    ) {
+        for target in FEATURE_TARGETS {
+            println!("Handling feature target {}.", target.name);
+            install_rustup_target(target.rust_target).context("install rustup target")?;
+            match command_name.as_str() {
+                "test" if !target.qemu_can_run => (),
+                "all" => {
+                    check(target, true).context("check")?;
+                    expected_target(target).context("target")?;
+                    if target.qemu_can_run {
+                        qemu_test(target).context("test")?;
+                    }
+                }
+                _ => command(target)?,
+            }
+        }
+    }
+
+    if matches!(command_name.as_str(), "asm" | "all") {
+        for target in ASSEMBLY_TARGETS {
+            println!("Handling assembly target {}.", target.name);
+            install_rustup_target(target.rust_target).context("install rustup target")?;
+            show_asm(target).context("asm")?;
+        }
+    }
+
     Ok(())
 }
 
@@ -47,6 +101,10 @@ struct Target {
     qemu: &'static str,
     generate_assembly: bool,
     force_default: bool,
+    /// Whether the tests can run under qemu. qemu does not emulate AVX-512 and there is no cross linker for armv7.
+    qemu_can_run: bool,
+    /// Whether to build the standard library from source with the nightly toolchain. This is needed for target features that change the float ABI, because the prebuilt standard library uses the ABI of the plain target.
+    build_std: bool,
 }
 
 const TARGETS: &[Target] = &[
@@ -58,6 +116,8 @@ const TARGETS: &[Target] = &[
         qemu: "x86_64",
         generate_assembly: true,
         force_default: false,
+        qemu_can_run: true,
+        build_std: false,
     },
     Target {
         name: "x86_64_default",
@@ -67,6 +127,8 @@ const TARGETS: &[Target] = &[
         qemu: "x86_64",
         generate_assembly: true,
         force_default: true,
+        qemu_can_run: true,
+        build_std: false,
     },
     Target {
         name: "x86_sse",
@@ -76,6 +138,8 @@ const TARGETS: &[Target] = &[
         qemu: "i386",
         generate_assembly: true,
         force_default: false,
+        qemu_can_run: true,
+        build_std: false,
     },
     Target {
         name: "default",
@@ -85,6 +149,151 @@ const TARGETS: &[Target] = &[
         qemu: "i386",
         generate_assembly: false,
         force_default: false,
+        qemu_can_run: true,
+        build_std: false,
+    },
+    Target {
+        name: "aarch64_neon",
+        rust_target: "aarch64-unknown-linux-gnu",
+        expected_target_module: "aarch64_neon",
+        feature: "",
+        qemu: "aarch64",
+        generate_assembly: true,
+        force_default: false,
+        qemu_can_run: true,
+        build_std: false,
+    },
+    Target {
+        name: "aarch64_default",
+        rust_target: "aarch64-unknown-linux-gnu",
+        expected_target_module: "default",
+        feature: "",
+        qemu: "aarch64",
+        generate_assembly: true,
+        force_default: true,
+        qemu_can_run: true,
+        build_std: false,
+    },
+];
+
+/// Targets with additional target features like the ones `-Ctarget-cpu=native` enables. They check that the features do not change which target module is active and that the tests still pass. We do not generate assembly for them.
+const FEATURE_TARGETS: &[Target] = &[
+    Target {
+        name: "x86_64_sse4_1",
+        rust_target: "x86_64-unknown-linux-gnu",
+        expected_target_module: "x86_64_sse",
+        feature: "+sse4.1",
+        qemu: "x86_64",
+        generate_assembly: false,
+        force_default: false,
+        qemu_can_run: true,
+        build_std: false,
+    },
+    Target {
+        name: "x86_64_avx",
+        rust_target: "x86_64-unknown-linux-gnu",
+        expected_target_module: "x86_64_sse",
+        feature: "+avx",
+        qemu: "x86_64",
+        generate_assembly: false,
+        force_default: false,
+        qemu_can_run: true,
+        build_std: false,
+    },
+    Target {
+        name: "x86_64_avx2",
+        rust_target: "x86_64-unknown-linux-gnu",
+        expected_target_module: "x86_64_sse",
+        feature: "+avx2",
+        qemu: "x86_64",
+        generate_assembly: false,
+        force_default: false,
+        qemu_can_run: true,
+        build_std: false,
+    },
+    Target {
+        name: "x86_64_avx512",
+        rust_target: "x86_64-unknown-linux-gnu",
+        expected_target_module: "x86_64_sse",
+        feature: "+avx512f",
+        qemu: "x86_64",
+        generate_assembly: false,
+        force_default: false,
+        qemu_can_run: false,
+        build_std: false,
+    },
+    // qemu emulates SVE, so the tests run the SVE kernel of the slice functions.
+    Target {
+        name: "aarch64_sve",
+        rust_target: "aarch64-unknown-linux-gnu",
+        expected_target_module: "aarch64_neon",
+        feature: "+sve",
+        qemu: "aarch64",
+        generate_assembly: false,
+        force_default: false,
+        qemu_can_run: true,
+        build_std: false,
+    },
+    Target {
+        name: "x86_sse4_1",
+        rust_target: "i686-unknown-linux-gnu",
+        expected_target_module: "x86_sse",
+        feature: "+sse4.1",
+        qemu: "i386",
+        generate_assembly: false,
+        force_default: false,
+        qemu_can_run: true,
+        build_std: false,
+    },
+    Target {
+        name: "x86_avx",
+        rust_target: "i686-unknown-linux-gnu",
+        expected_target_module: "x86_sse",
+        feature: "+avx",
+        qemu: "i386",
+        generate_assembly: false,
+        force_default: false,
+        qemu_can_run: true,
+        build_std: false,
+    },
+];
+
+/// Targets for which we only generate assembly. Running the tests needs a cross linker, see "Cross compilation" in the readme. aarch64 has one, see `AARCH64_LINKER`, and is in `TARGETS`.
+const ASSEMBLY_TARGETS: &[Target] = &[Target {
+    name: "armv7_default",
+    rust_target: "armv7-unknown-linux-gnueabihf",
+    expected_target_module: "default",
+    feature: "",
+    qemu: "arm",
+    generate_assembly: true,
+    force_default: false,
+    qemu_can_run: false,
+    build_std: false,
+}];
+
+/// Targets with target features that change the float ABI, like the ones of embedded users who mix hard and soft float libraries. Linking the crate built with these features against the prebuilt standard library gives wrong results, so they build the standard library from source. rustc warns that `soft-float` in `-Ctarget-feature` will become an error. i686 is missing because the standard library does not build for it without SSE. armv7 has no cross linker, see `ASSEMBLY_TARGETS`, so it is only checked.
+const BUILD_STD_TARGETS: &[Target] = &[
+    Target {
+        name: "x86_64_soft_float",
+        rust_target: "x86_64-unknown-linux-gnu",
+        expected_target_module: "default",
+        feature: "-sse,-sse2,+soft-float",
+        qemu: "x86_64",
+        generate_assembly: false,
+        force_default: false,
+        qemu_can_run: true,
+        build_std: true,
+    },
+    Target {
+        name: "armv7_soft_float",
+        rust_target: "armv7-unknown-linux-gnueabihf",
+        expected_target_module: "default",
+        feature: "+soft-float",
+        qemu: "arm",
+        generate_assembly: false,
+        force_default: false,
+        qemu_can_run: false,
+        build_std: true,
     },
 ];
 
@@ -94,11 +303,11 @@ const TARGETS: &[Target] = &[
 fn command_to_string(command: &Command) -> String {
     fn string_is_not_tricky(string: &str) -> bool {
         string.chars().all(|char| {
-            char.is_ascii_alphanumeric() || ['-', '_', '=', '/', '.', '+', ' '].contains(&char)
+            char.is_ascii_alphanumeric() || ['-', '_', '=', '/', '.', '+', ',', ' '].contains(&char)
         })
     }
 
-    fn handle_space(s: &str) -> Cow<str> {
+    fn handle_space(s: &str) -> Cow<'_, str> {
         if s.contains(' ') {
             format!("\"{s}\"").into()
         } else {
@@ -164,10 +373,15 @@ fn install_rustup_target(target: &str) -> Result<()> {
     Ok(())
 }
 
+/// The cross linker of the `gcc-aarch64-linux-gnu` package of Debian and Ubuntu and the sysroot with the libraries of the target that it installs.
+const AARCH64_LINKER: &str = "aarch64-linux-gnu-gcc";
+const AARCH64_SYSROOT: &str = "/usr/aarch64-linux-gnu";
+
 fn cargo_with_target(
     Target {
         rust_target: target,
         feature,
+        build_std,
         ..
     }: &Target,
     subcommand: &str,
@@ -183,6 +397,23 @@ fn cargo_with_target(
     command
         .env("RUSTFLAGS", flags.as_str())
         .args([subcommand, target_arg.as_str()]);
+    // This is not in .cargo/config.toml because it would also apply to native builds on aarch64 hosts.
+    if *target == "aarch64-unknown-linux-gnu" && std::env::consts::ARCH != "aarch64" {
+        command
+            .env(
+                "CARGO_TARGET_AARCH64_UNKNOWN_LINUX_GNU_LINKER",
+                AARCH64_LINKER,
+            )
+            .env(
+                "CARGO_TARGET_AARCH64_UNKNOWN_LINUX_GNU_RUNNER",
+                format!("qemu-aarch64 -L {AARCH64_SYSROOT}"),
+            );
+    }
+    if *build_std {
+        command
+            .env("RUSTUP_TOOLCHAIN", "nightly")
+            .arg("-Zbuild-std");
+    }
     command
 }
 
@@ -191,10 +422,11 @@ fn check(target: &Target, clippy: bool) -> Result<()> {
         true => "clippy",
         false => "check",
     };
+    // These features only add code so we enable them to check more code.
     let features = if target.force_default {
-        "--features=force-default"
+        "--features=std,unstable-targets,expect-finite,force-default"
     } else {
-        "--features="
+        "--features=std,unstable-targets,expect-finite"
     };
     let mut command = cargo_with_target(target, command, &[]);
     command.args([
@@ -211,67 +443,219 @@ fn check(target: &Target, clippy: bool) -> Result<()> {
     Ok(())
 }
 
+/// The number of instructions in the normalized assembly of a function. Labels and directives like the constants are not instructions.
+fn count_instructions(assembly: &str) -> usize {
+    assembly
+        .lines()
+        .filter(|line| line.starts_with('\t') && !line.starts_with("\t."))
+        .count()
+}
+
+/// Whether the assembly has a jump or a call. The call of the x86 PIC thunk, which loads the address of the global offset table, does not depend on the input.
+fn has_branch(assembly: &str) -> bool {
+    /// The branches of aarch64. Conditional branches like `b.ne` start with `b.`.
+    const AARCH64: &[&str] = &["b", "bl", "blr", "br", "cbz", "cbnz", "tbz", "tbnz"];
+
+    assembly
+        .lines()
+        .filter_map(|line| line.strip_prefix('\t'))
+        .any(|instruction| {
+            let mnemonic = instruction.split_whitespace().next().unwrap_or_default();
+            let x86 =
+                (mnemonic.starts_with('j') || mnemonic == "call") && !instruction.ends_with("$pb");
+            x86 || AARCH64.contains(&mnemonic) || mnemonic.starts_with("b.")
+        })
+}
+
+#[test]
+fn has_branch_() {
+    assert!(!has_branch("f:\n\tcvttss2si eax, xmm0\n\tret"));
+    assert!(!has_branch(
+        "f:\n\tcall .L_0$pb\n.L_0$pb:\n\tpop eax\n\tret"
+    ));
+    assert!(has_branch("f:\n\tjbe .L_3\n.L_3:\n\tret"));
+    assert!(has_branch(
+        "f:\n\tcall qword ptr [rip + __fixsfti@GOTPCREL]"
+    ));
+    assert!(!has_branch("f:\n\tfcvtzs w0, s0\n\tbic w0, w0, w1\n\tret"));
+    assert!(has_branch("f:\n\tb.ne .L_3\n.L_3:\n\tret"));
+    assert!(has_branch("f:\n\tcbz w0, .L_3\n.L_3:\n\tret"));
+    assert!(has_branch("f:\n\tb __fixsfti"));
+}
+
 fn show_asm(target: &Target) -> Result<()> {
     if !target.generate_assembly {
         return Ok(());
     }
 
-    let functions = [
-        "f32_to_i8",
-        "f32_to_u8",
-        "f32_to_i16",
-        "f32_to_u16",
-        "f32_to_i32",
-        "f32_to_u32",
-        "f32_to_i64",
-        "f32_to_u64",
-        "f32_to_i128",
-        "f32_to_u128",
-        "f64_to_i8",
-        "f64_to_u8",
-        "f64_to_i16",
-        "f64_to_u16",
-        "f64_to_i32",
-        "f64_to_u32",
-        "f64_to_i64",
-        "f64_to_u64",
-        "f64_to_i128",
-        "f64_to_u128",
-    ];
-
     let mut features = "--features=show-asm".to_owned();
     if target.force_default {
         features.push_str(",force-default");
     }
 
-    for function in functions {
-        let output = run_command(cargo_with_target(target, "asm", &[]).args([
-            // "--quiet", // will be supported in next cargo asm release
-            "--no-color",
-            "--simplify",
-            "--include-constants",
-            "--package=fast-float-to-integer",
-            "--lib",
-            features.as_str(),
-            "--profile=show-asm",
-            function,
-        ]))?;
-        let output = std::str::from_utf8(output.stdout.as_slice()).unwrap();
-        let output = normalize_assembly(output);
+    for family in FAMILIES {
+        for conversion in CONVERSIONS {
+            let function = format!("{conversion}{}", family.suffix);
+            let path = format!("{}::{function}", family.module);
+            let output = assembly(target, features.as_str(), path.as_str(), &[])?;
 
-        let mut path = PathBuf::new();
-        path.push("generated assembly");
-        path.push(target.name);
-        std::fs::create_dir_all(&path).context("create_dir_all")?;
-        path.push(function);
-        std::fs::write(&path, output.as_ref()).context("write generated assembly")?;
+            let mut path = PathBuf::new();
+            path.push("generated assembly");
+            path.push(target.name);
+            path.push(family.directory);
+            std::fs::create_dir_all(&path).context("create_dir_all")?;
+            path.push(function.as_str());
+            std::fs::write(&path, output.as_str()).context("write generated assembly")?;
+
+            check_assembly_structure(target, family, conversion, output.as_str())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Fail if the assembly of the conversion exceeds the instruction budget of the family or has a branch although the manifest lists it in BRANCH_FREE. Unlike the exact assembly, this does not depend much on the compiler version.
+fn check_assembly_structure(
+    target: &Target,
+    family: &Family,
+    conversion: &str,
+    output: &str,
+) -> Result<()> {
+    let function = format!("{conversion}{}", family.suffix);
+    let budget = family
+        .budgets
+        .iter()
+        .find(|(name, _)| *name == target.name)
+        .map(|(_, budget)| *budget)
+        .context("missing budget for target")?;
+    let branch_free = BRANCH_FREE
+        .iter()
+        .find(|(name, _)| *name == target.name)
+        .map(|(_, conversions)| *conversions)
+        .unwrap_or_default();
+
+    let instructions = count_instructions(output);
+    if !conversion.ends_with("128") && instructions > budget {
+        return Err(anyhow!(
+            "{function} has {instructions} instructions, which exceeds the budget of {budget}"
+        ));
+    }
+    if family.branch_free && branch_free.contains(&conversion) && has_branch(output) {
+        return Err(anyhow!(
+            "{function} must be branch free but its assembly has a branch"
+        ));
+    }
+    Ok(())
+}
+
+/// The Rust version that CI pins in .github/workflows/check.yml. Other versions generate different assembly, which then fails the check for changes in the generated assembly.
+const ASSEMBLY_TOOLCHAIN: &str = "1.82";
+
+/// Whether the toolchain that the cargo commands use is `ASSEMBLY_TOOLCHAIN`, and its version.
+fn assembly_toolchain() -> Result<(bool, String)> {
+    let output = run_command(Command::new("rustc").arg("--version"))?;
+    let version = std::str::from_utf8(output.stdout.as_slice()).context("output is not utf8")?;
+    let pinned = version.starts_with(&format!("rustc {ASSEMBLY_TOOLCHAIN}."));
+    Ok((pinned, version.trim().to_owned()))
+}
+
+/// Fail if the toolchain that the cargo commands use is not `ASSEMBLY_TOOLCHAIN`.
+fn check_assembly_toolchain() -> Result<()> {
+    let (pinned, version) = assembly_toolchain()?;
+    if !pinned {
+        return Err(anyhow!(
+            "generate the assembly with Rust {ASSEMBLY_TOOLCHAIN} like CI, not {version}. Set RUSTUP_TOOLCHAIN={ASSEMBLY_TOOLCHAIN}."
+        ));
+    }
+    Ok(())
+}
+
+/// The normalized assembly of the function at `path` in the library built with `features` for the target.
+fn assembly(target: &Target, features: &str, path: &str, rustflags: &[&str]) -> Result<String> {
+    let output = run_command(cargo_with_target(target, "asm", rustflags).args([
+        // "--quiet", // will be supported in next cargo asm release
+        "--no-color",
+        "--simplify",
+        "--include-constants",
+        "--package=fast-float-to-integer",
+        "--lib",
+        features,
+        "--profile=show-asm",
+        path,
+        // The names of one family can be prefixes of the names of another, like `_round` and `_round_ties_even`. The exact name sorts first.
+        "0",
+    ]))?;
+    let output = std::str::from_utf8(output.stdout.as_slice()).context("output is not utf8")?;
+    Ok(normalize_assembly(output).into_owned())
+}
+
+/// The target whose conversions `cargo xtask fingerprint` checks.
+const FINGERPRINT_TARGET: &str = "x86_64_sse";
+
+/// Check the assembly of the scalar conversions of `FINGERPRINT_TARGET` in the current environment. The toolchain is the one that cargo selects and `RUSTFLAGS` is passed on, so users can check that their build gets the optimized code.
+///
+/// With every toolchain the assembly must stay within the instruction budgets and the branch free conversions must not branch, like in `cargo xtask asm`. A wrongly selected fallback fails this. Only the toolchain that CI pins reproduces the committed generated assembly exactly, so only with it the fingerprint, the hash of the assembly, must match the hash of the committed files.
+fn fingerprint() -> Result<()> {
+    let target = TARGETS
+        .iter()
+        .find(|target| target.name == FINGERPRINT_TARGET)
+        .context("missing fingerprint target")?;
+    let family = FAMILIES
+        .iter()
+        .find(|family| family.directory.is_empty())
+        .context("missing scalar family")?;
+    let rustflags = std::env::var("RUSTFLAGS").unwrap_or_default();
+    let rustflags: Vec<&str> = rustflags.split_whitespace().collect();
+    let (pinned, version) = assembly_toolchain()?;
+
+    let mut expected = FNV_OFFSET;
+    let mut actual = FNV_OFFSET;
+    let mut different = Vec::new();
+    for conversion in CONVERSIONS {
+        let function = format!("{conversion}{}", family.suffix);
+        let path = format!("{}::{function}", family.module);
+        let output = assembly(target, "--features=show-asm", path.as_str(), &rustflags)?;
+        check_assembly_structure(target, family, conversion, output.as_str())?;
+        actual = fnv1a(actual, output.as_bytes());
+        if pinned {
+            let mut committed = PathBuf::new();
+            committed.push("generated assembly");
+            committed.push(target.name);
+            committed.push(function.as_str());
+            let committed =
+                std::fs::read_to_string(&committed).context("read generated assembly")?;
+            expected = fnv1a(expected, committed.as_bytes());
+            if output != committed {
+                different.push(function);
+            }
+        }
     }
 
+    println!("The conversions are within their instruction budgets and the branch free ones do not branch.");
+    println!("fingerprint {actual:#018x}");
+    if !pinned {
+        println!("{version} is not Rust {ASSEMBLY_TOOLCHAIN}, which generated the committed assembly, so the fingerprint is not compared.");
+        return Ok(());
+    }
+    println!("expected    {expected:#018x}");
+    if !different.is_empty() {
+        return Err(anyhow!(
+            "the conversions compile to different assembly than the committed one: {different:?}. Run `cargo xtask asm` and look at the diff of the generated assembly."
+        ));
+    }
     Ok(())
 }
 
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100_0000_01b3)
+    })
+}
+
 /// We diff the generated assembly to make sure it doesn't accidentally change. This requires the assembly to be deterministic. By default, some parts of the assembly like labels are not deterministic. This function fixes that.
-fn normalize_assembly(assembly: &str) -> Cow<str> {
+fn normalize_assembly(assembly: &str) -> Cow<'_, str> {
     const REGEX: &str = r"\.L([[:alnum:]]|_)+";
     static RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(REGEX).unwrap());
 
@@ -312,37 +696,440 @@ fn normalize_assembly_() {
     assert_eq!(actual, expected);
 }
 
-fn qemu_test(target: &Target) -> Result<()> {
-    let features = if target.force_default {
-        "--features=force-default"
+/// The features of the test builds of the target.
+fn test_features(target: &Target) -> &'static str {
+    // The soft-float paths do not depend on the target. Test them together with the default implementation so that both paths are covered.
+    if target.force_default {
+        "--features=force-default,soft-float"
     } else {
         "--features="
-    };
-    let output = run_command(cargo_with_target(target, "test", &[]).args([
+    }
+}
+
+/// Build an integration test and return the path of the test binary.
+fn build_test(target: &Target, test: &str, features: &str, release: bool) -> Result<String> {
+    let test_arg = format!("--test={test}");
+    let mut command = cargo_with_target(target, "test", &[]);
+    command.args([
         "--frozen",
         "--no-run",
         "--package=fast-float-to-integer",
-        "--test=test",
+        test_arg.as_str(),
         features,
-    ]))?;
+    ]);
+    if release {
+        command.arg("--release");
+    }
+    let output = run_command(&mut command)?;
     let stderr = std::str::from_utf8(output.stderr.as_slice()).context("output is not utf8")?;
 
+    let prefix = format!("  Executable tests/{test}.rs (");
     let test_binary_path = stderr
         .rsplit('\n')
         .nth(1)
         .context("unexpected output")?
-        .strip_prefix("  Executable tests/test.rs (")
+        .strip_prefix(prefix.as_str())
         .context("unexpected output")?
         .strip_suffix(')')
         .context("unexpected output")?;
+    Ok(test_binary_path.to_owned())
+}
+
+/// A qemu command for the target. The dynamically linked test binaries of aarch64 need the libraries of the cross toolchain.
+fn qemu(target: &Target) -> Command {
+    let mut command = Command::new(format!("qemu-{}", target.qemu));
+    if target.qemu == "aarch64" {
+        command.env("QEMU_LD_PREFIX", AARCH64_SYSROOT);
+    }
+    command
+}
+
+fn qemu_test(target: &Target) -> Result<()> {
+    let test_binary_path = build_test(target, "test", test_features(target), false)?;
+    let mut command = qemu(target);
+    command.args([test_binary_path.as_str(), "--test-threads=1"]);
+    // The standard library calls the C math library of the host, which has the float ABI of the plain target. The srgb test computes its expected values with `powf`.
+    if target.build_std {
+        command.args(["--skip", "srgb"]);
+    }
+    run_command(&mut command)?;
+
+    // The pinned results of the portable-deterministic feature must be the same on every target.
+    let test_binary_path = build_test(target, "test", "--features=portable-deterministic", false)?;
+    run_command(qemu(target).args([
+        test_binary_path.as_str(),
+        "--test-threads=1",
+        "--exact",
+        "portable_deterministic",
+    ]))
+    .context("portable-deterministic")?;
+
+    // The link time check that the conversions cannot panic needs optimizations. Building the test is the check.
+    let no_panic_path =
+        build_test(target, "no_panic", test_features(target), true).context("no panic")?;
+    run_command(qemu(target).args([no_panic_path.as_str(), "--test-threads=1"]))
+        .context("no panic")?;
+
+    // The smoke benchmark needs optimizations to be meaningful.
+    let smoke_benchmark_path = build_test(target, "smoke_benchmark", test_features(target), true)
+        .context("smoke benchmark")?;
+    run_command(qemu(target).args([smoke_benchmark_path.as_str(), "--test-threads=1"]))
+        .context("smoke benchmark")?;
+
+    Ok(())
+}
+
+/// Build the size-consumer binary with the standard conversion, with the library and with the small-code feature and print the size of the .text section. The targets with force_default show the size of the feature.
+fn size(target: &Target) -> Result<()> {
+    let fast_features = if target.force_default {
+        "--features=force-default"
+    } else {
+        "--features="
+    };
+    let small_features = format!("{fast_features},small-code");
+    let variants = [
+        ("standard", "--features=standard"),
+        ("fast", fast_features),
+        ("small", small_features.as_str()),
+    ];
 
-    run_command(
-        Command::new(format!("qemu-{}", target.qemu)).args([test_binary_path, "--test-threads=1"]),
-    )?;
+    let mut standard_size = None;
+    for (name, features) in variants {
+        run_command(cargo_with_target(target, "build", &[]).args([
+            "--quiet",
+            "--release",
+            "--package=size-consumer",
+            features,
+        ]))?;
+        let path = format!("target/{}/release/size-consumer", target.rust_target);
+        let output = run_command(Command::new("size").args(["-A", "-d", path.as_str()]))?;
+        let output = std::str::from_utf8(output.stdout.as_slice()).context("output is not utf8")?;
+        let text_size: i64 = output
+            .lines()
+            .find_map(|line| line.strip_prefix(".text "))
+            .context("missing .text section")?
+            .split_whitespace()
+            .next()
+            .context("unexpected output")?
+            .parse()
+            .context("unexpected output")?;
+        let standard_size = *standard_size.get_or_insert(text_size);
+        println!(
+            "{name}: .text {text_size} bytes ({:+} bytes)",
+            text_size - standard_size
+        );
+    }
 
     Ok(())
 }
 
+/// One conversion of a target in the speedup report.
+struct SpeedupRow {
+    target: &'static str,
+    conversion: &'static str,
+    instructions: usize,
+    /// The instructions of the standard conversion, if the standard conversion has generated assembly for the same Rust target.
+    standard_instructions: Option<usize>,
+    /// The nanoseconds of the fastest smoke benchmark run of the conversion and of the standard conversion, if the target runs natively.
+    measured: Option<(u128, u128)>,
+}
+
+/// Print how the conversions compare to the standard `as` operator per target, as a Markdown table or with the `json` argument as JSON.
+///
+/// The instruction counts come from the committed generated assembly, so run `cargo xtask asm` first. They are deterministic and available for every target with assembly. The standard conversion is the target with `force_default` for the same Rust target. The time measurements come from the smoke benchmark. It only runs on the targets that the host runs natively, because timings under qemu do not reflect the hardware.
+fn speedup_report(format: Option<&str>) -> Result<()> {
+    let json = match format {
+        None | Some("markdown") => false,
+        Some("json") => true,
+        Some(format) => return Err(anyhow!("unknown format {format}")),
+    };
+
+    let read_instructions = |target: &Target, conversion: &str| -> Result<usize> {
+        let mut path = PathBuf::new();
+        path.push("generated assembly");
+        path.push(target.name);
+        path.push(conversion);
+        let assembly = std::fs::read_to_string(&path)
+            .with_context(|| format!("read {}, run `cargo xtask asm` first", path.display()))?;
+        Ok(count_instructions(&assembly))
+    };
+    let host = format!("{}-unknown-linux-gnu", std::env::consts::ARCH);
+    let assembly_targets = || {
+        TARGETS
+            .iter()
+            .chain(ASSEMBLY_TARGETS)
+            .filter(|target| target.generate_assembly)
+    };
+
+    let mut rows = Vec::new();
+    for target in assembly_targets().filter(|target| !target.force_default) {
+        let standard = assembly_targets()
+            .find(|standard| standard.force_default && standard.rust_target == target.rust_target);
+        let measured = if target.rust_target == host {
+            smoke_benchmark_times(target).context("smoke benchmark")?
+        } else {
+            HashMap::new()
+        };
+        for conversion in CONVERSIONS {
+            rows.push(SpeedupRow {
+                target: target.name,
+                conversion,
+                instructions: read_instructions(target, conversion)?,
+                standard_instructions: standard
+                    .map(|standard| read_instructions(standard, conversion))
+                    .transpose()?,
+                measured: measured.get(*conversion).copied(),
+            });
+        }
+    }
+
+    let mut output = String::new();
+    if json {
+        output.push_str("[\n");
+        for (index, row) in rows.iter().enumerate() {
+            let optional = |value: Option<String>| value.unwrap_or_else(|| "null".to_owned());
+            write!(
+                &mut output,
+                "  {{\"target\": \"{}\", \"conversion\": \"{}\", \"instructions\": {}, \"standard_instructions\": {}, \"nanoseconds\": {}, \"standard_nanoseconds\": {}}}",
+                row.target,
+                row.conversion,
+                row.instructions,
+                optional(row.standard_instructions.map(|count| count.to_string())),
+                optional(row.measured.map(|(fast, _)| fast.to_string())),
+                optional(row.measured.map(|(_, standard)| standard.to_string())),
+            )
+            .unwrap();
+            output.push_str(if index + 1 < rows.len() { ",\n" } else { "\n" });
+        }
+        output.push_str("]\n");
+    } else {
+        let mut previous_target = "";
+        for row in &rows {
+            if row.target != previous_target {
+                previous_target = row.target;
+                write!(
+                    &mut output,
+                    "\n## {}\n\n| conversion | instructions | standard instructions | measured speedup |\n| --- | --- | --- | --- |\n",
+                    row.target
+                )
+                .unwrap();
+            }
+            let standard_instructions = row
+                .standard_instructions
+                .map_or_else(|| "-".to_owned(), |count| count.to_string());
+            let speedup = row.measured.map_or_else(
+                || "-".to_owned(),
+                |(fast, standard)| format!("{:.2}x", standard as f64 / fast.max(1) as f64),
+            );
+            writeln!(
+                &mut output,
+                "| {} | {} | {} | {} |",
+                row.conversion, row.instructions, standard_instructions, speedup
+            )
+            .unwrap();
+        }
+    }
+    print!("{}", output.trim_start());
+    Ok(())
+}
+
+/// Run the smoke benchmark natively and return the times of the conversion and of the standard conversion per conversion name.
+fn smoke_benchmark_times(target: &Target) -> Result<HashMap<String, (u128, u128)>> {
+    let path = build_test(target, "smoke_benchmark", test_features(target), true)?;
+    let output = run_command(Command::new(path).args(["--test-threads=1", "--nocapture"]))?;
+    let stdout = std::str::from_utf8(output.stdout.as_slice()).context("output is not utf8")?;
+    let mut times = HashMap::new();
+    for line in stdout.lines() {
+        // The test harness prints the name of the test on the same line.
+        let Some((_, line)) = line.split_once("speedup-report ") else {
+            continue;
+        };
+        let mut fields = line.split_whitespace();
+        let mut next = || fields.next().context("unexpected output");
+        let conversion = next()?.to_owned();
+        let fast = next()?.parse().context("unexpected output")?;
+        let standard = next()?.parse().context("unexpected output")?;
+        times.insert(conversion, (fast, standard));
+    }
+    Ok(times)
+}
+
+/// Features that need the standard library. Every other feature must keep the crate no_std.
+const STD_FEATURES: &[&str] = &["std", "rayon", "trace", "itoa"];
+
+/// Targets without the standard library. They abort on panic, so building for them also checks that no feature needs unwinding.
+const NO_STD_TARGETS: &[&str] = &["thumbv7em-none-eabihf", "x86_64-unknown-none"];
+
+/// The features in the `[features]` section of Cargo.toml together with the Rust version that the comment above the feature requires with "Needs Rust 1.xx.".
+fn manifest_features(manifest: &str) -> Result<Vec<(&str, Option<&str>)>> {
+    let mut features = Vec::new();
+    let mut version = None;
+    let section = manifest
+        .split_once("[features]")
+        .context("missing features section")?
+        .1
+        .lines()
+        .take_while(|line| !line.starts_with('['));
+    for line in section {
+        if let Some(comment) = line.strip_prefix('#') {
+            // The comments can contain " = " too.
+            if let Some((_, rest)) = comment.split_once("Needs Rust ") {
+                let end = rest
+                    .find(|char: char| !char.is_ascii_digit() && char != '.')
+                    .unwrap_or(rest.len());
+                version = Some(rest[..end].trim_end_matches('.'));
+            }
+        } else if let Some((name, _)) = line.split_once(" = ") {
+            features.push((name.trim(), version.take()));
+        }
+    }
+    Ok(features)
+}
+
+#[test]
+fn manifest_features_() {
+    let manifest = "[features]\n# Needs the standard library.\nstd = [  ]\n# Uses `#[attribute = \"value\"]`. Needs Rust 1.89.\nnew = [  ]\nold = [ \"std\" ]\n\n[profile]\nx = 1";
+    assert_eq!(
+        manifest_features(manifest).unwrap(),
+        [("std", None), ("new", Some("1.89")), ("old", None)]
+    );
+}
+
+/// The minor version of a Rust version like "1.71".
+fn minor_version(version: &str) -> Result<u32> {
+    version
+        .strip_prefix("1.")
+        .and_then(|minor| minor.parse().ok())
+        .with_context(|| format!("unexpected Rust version {version}"))
+}
+
+/// Check the library with the minimum supported Rust version and with the newer versions that some features need.
+///
+/// Every toolchain checks the library without features, which is the core that conservative users rely on, and with every feature that the toolchain supports. A feature that needs a newer version than the minimum must say so in its comment in Cargo.toml with "Needs Rust 1.xx.". The check with the minimum version fails if it does not.
+fn msrv() -> Result<()> {
+    let manifest = std::fs::read_to_string("Cargo.toml").context("read Cargo.toml")?;
+    let msrv = manifest
+        .lines()
+        .find_map(|line| line.strip_prefix("rust-version = "))
+        .context("missing rust-version")?
+        .trim_matches('"');
+    let msrv = minor_version(msrv)?;
+    // The minor version that every feature needs.
+    let features = manifest_features(&manifest)?
+        .into_iter()
+        .map(|(name, version)| {
+            Ok((
+                name,
+                version.map(minor_version).transpose()?.unwrap_or(msrv),
+            ))
+        })
+        .collect::<Result<Vec<(&str, u32)>>>()?;
+
+    let mut versions: Vec<u32> = features.iter().map(|(_, version)| *version).collect();
+    versions.push(msrv);
+    versions.sort_unstable();
+    versions.dedup();
+
+    for version in versions {
+        let toolchain = format!("1.{version}");
+        println!("Handling Rust {toolchain}.");
+        run_command(Command::new("rustup").args([
+            "--quiet",
+            "toolchain",
+            "install",
+            toolchain.as_str(),
+            "--profile=minimal",
+        ]))
+        .context("install rustup toolchain")?;
+        let toolchain_arg = format!("+{toolchain}");
+        let enabled: Vec<&str> = features
+            .iter()
+            .filter(|(_, needs)| *needs <= version)
+            .map(|(name, _)| *name)
+            .collect();
+        for features_arg in [
+            "--features=".to_owned(),
+            format!("--features={}", enabled.join(",")),
+        ] {
+            run_command(Command::new("cargo").args([
+                toolchain_arg.as_str(),
+                "check",
+                "--quiet",
+                "--frozen",
+                "--package=fast-float-to-integer",
+                "--all-targets",
+                "--no-default-features",
+                features_arg.as_str(),
+            ]))
+            .with_context(|| format!("Rust {toolchain} {features_arg}"))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Build the library for targets without the standard library with each of the features that do not need it and with all of them together.
+///
+/// Building every combination would take thousands of builds. A feature that needs the standard library or unwinding already fails on its own.
+fn no_std() -> Result<()> {
+    let manifest = std::fs::read_to_string("Cargo.toml").context("read Cargo.toml")?;
+    let features: Vec<&str> = manifest_features(&manifest)?
+        .into_iter()
+        .map(|(name, _)| name)
+        .filter(|name| !STD_FEATURES.contains(name))
+        .collect();
+    let combinations: Vec<Vec<&str>> = std::iter::once(Vec::new())
+        .chain(features.iter().map(|feature| vec![*feature]))
+        .chain(std::iter::once(features.clone()))
+        .collect();
+
+    for target in NO_STD_TARGETS {
+        println!("Handling target {target}.");
+        install_rustup_target(target).context("install rustup target")?;
+        for enabled in &combinations {
+            let features_arg = format!("--features={}", enabled.join(","));
+            let target_arg = format!("--target={target}");
+            run_command(Command::new("cargo").args([
+                "build",
+                "--quiet",
+                "--frozen",
+                "--package=fast-float-to-integer",
+                "--lib",
+                "--no-default-features",
+                target_arg.as_str(),
+                features_arg.as_str(),
+            ]))
+            .with_context(|| format!("features: {enabled:?}"))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Check, select the target module and test the targets of `BUILD_STD_TARGETS`.
+fn build_std() -> Result<()> {
+    run_command(Command::new("rustup").args([
+        "--quiet",
+        "component",
+        "add",
+        "rust-src",
+        "--toolchain=nightly",
+    ]))
+    .context("install rust-src")?;
+    for target in BUILD_STD_TARGETS {
+        println!("Handling build-std target {}.", target.name);
+        // The other commands use --frozen, which cannot download the dependencies of the standard library.
+        run_command(&mut cargo_with_target(target, "fetch", &[]))
+            .context("fetch standard library dependencies")?;
+        check(target, false).context("check")?;
+        if target.qemu_can_run {
+            expected_target(target).context("target")?;
+            qemu_test(target).context("test")?;
+        }
+    }
+    Ok(())
+}
+
 fn expected_target(target: &Target) -> Result<()> {
     let features = if target.force_default {
         "--features=force-default"
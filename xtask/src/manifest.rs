@@ -0,0 +1,261 @@
+// The public functions of the library that the xtask and the tests need to know by name. This file has no dependencies so that the tests can include it with `include!`.
+//
+// When you add a family of functions to the library, add it here. `cargo xtask asm` then generates its assembly and checks its budget.
+
+/// The conversion functions in the order of the declaration. The API families below add a suffix to these names.
+pub const CONVERSIONS: &[&str] = &[
+    "f32_to_i8",
+    "f32_to_u8",
+    "f32_to_i16",
+    "f32_to_u16",
+    "f32_to_i32",
+    "f32_to_u32",
+    "f32_to_i64",
+    "f32_to_u64",
+    "f32_to_i128",
+    "f32_to_u128",
+    "f64_to_i8",
+    "f64_to_u8",
+    "f64_to_i16",
+    "f64_to_u16",
+    "f64_to_i32",
+    "f64_to_u32",
+    "f64_to_i64",
+    "f64_to_u64",
+    "f64_to_i128",
+    "f64_to_u128",
+];
+
+/// A family of public functions whose assembly we generate.
+pub struct Family {
+    /// The subdirectory of the target's directory in "generated assembly". Empty for the target's directory itself.
+    pub directory: &'static str,
+    /// The path of the module that defines the functions. cargo-show-asm matches substrings of the full path, so the name alone could match the functions of other families.
+    pub module: &'static str,
+    /// Appended to the names in CONVERSIONS.
+    pub suffix: &'static str,
+    /// The maximal number of instructions of a function per target name. The 128 bit conversions are exempt because they call into compiler_builtins. Exceeding the budget fails the command. This catches codegen regressions that are easy to miss in the diff of the assembly.
+    pub budgets: &'static [(&'static str, usize)],
+    /// Whether the conversions in BRANCH_FREE must not have branches in this family.
+    pub branch_free: bool,
+}
+
+pub const FAMILIES: &[Family] = &[
+    Family {
+        directory: "",
+        module: "fast_float_to_integer",
+        suffix: "",
+        budgets: &[
+            ("x86_64_sse", 10),
+            ("x86_64_default", 18),
+            ("x86_sse", 40),
+            ("aarch64_neon", 4),
+            ("aarch64_default", 4),
+            ("armv7_default", 24),
+        ],
+        branch_free: true,
+    },
+    Family {
+        directory: "clamp_nearest",
+        module: "fast_float_to_integer::clamp_nearest",
+        suffix: "_clamp_nearest",
+        budgets: &[
+            ("x86_64_sse", 16),
+            ("x86_64_default", 16),
+            ("x86_sse", 20),
+            ("aarch64_neon", 20),
+            ("aarch64_default", 20),
+            ("armv7_default", 18),
+        ],
+        branch_free: false,
+    },
+    Family {
+        directory: "rounding",
+        module: "fast_float_to_integer::rounding",
+        suffix: "_floor",
+        budgets: &[
+            ("x86_64_sse", 22),
+            ("x86_64_default", 22),
+            ("x86_sse", 26),
+            ("aarch64_neon", 2),
+            ("aarch64_default", 13),
+            ("armv7_default", 16),
+        ],
+        branch_free: false,
+    },
+    Family {
+        directory: "rounding",
+        module: "fast_float_to_integer::rounding",
+        suffix: "_ceil",
+        budgets: &[
+            ("x86_64_sse", 23),
+            ("x86_64_default", 23),
+            ("x86_sse", 26),
+            ("aarch64_neon", 2),
+            ("aarch64_default", 12),
+            ("armv7_default", 16),
+        ],
+        branch_free: false,
+    },
+    Family {
+        directory: "rounding",
+        module: "fast_float_to_integer::rounding",
+        suffix: "_round",
+        budgets: &[
+            ("x86_64_sse", 25),
+            ("x86_64_default", 25),
+            ("x86_sse", 34),
+            ("aarch64_neon", 2),
+            ("aarch64_default", 18),
+            ("armv7_default", 25),
+        ],
+        branch_free: false,
+    },
+    Family {
+        directory: "rounding",
+        module: "fast_float_to_integer::rounding",
+        suffix: "_round_ties_even",
+        budgets: &[
+            ("x86_64_sse", 11),
+            ("x86_64_default", 11),
+            ("x86_sse", 21),
+            ("aarch64_neon", 2),
+            ("aarch64_default", 16),
+            ("armv7_default", 14),
+        ],
+        branch_free: false,
+    },
+    Family {
+        directory: "array",
+        module: "fast_float_to_integer::array",
+        suffix: "_x2",
+        budgets: &[
+            ("x86_64_sse", 22),
+            ("x86_64_default", 36),
+            ("x86_sse", 79),
+            ("aarch64_neon", 7),
+            ("aarch64_default", 7),
+            ("armv7_default", 41),
+        ],
+        branch_free: true,
+    },
+    Family {
+        directory: "array",
+        module: "fast_float_to_integer::array",
+        suffix: "_x3",
+        budgets: &[
+            ("x86_64_sse", 30),
+            ("x86_64_default", 49),
+            ("x86_sse", 114),
+            ("aarch64_neon", 10),
+            ("aarch64_default", 10),
+            ("armv7_default", 58),
+        ],
+        branch_free: true,
+    },
+    Family {
+        directory: "array",
+        module: "fast_float_to_integer::array",
+        suffix: "_x4",
+        budgets: &[
+            ("x86_64_sse", 39),
+            ("x86_64_default", 63),
+            ("x86_sse", 148),
+            ("aarch64_neon", 15),
+            ("aarch64_default", 15),
+            ("armv7_default", 76),
+        ],
+        branch_free: true,
+    },
+    Family {
+        directory: "array",
+        module: "fast_float_to_integer::array",
+        suffix: "_x8",
+        budgets: &[
+            ("x86_64_sse", 79),
+            ("x86_64_default", 127),
+            ("x86_sse", 285),
+            ("aarch64_neon", 28),
+            ("aarch64_default", 28),
+            ("armv7_default", 162),
+        ],
+        branch_free: true,
+    },
+    Family {
+        directory: "small",
+        module: "fast_float_to_integer::small",
+        suffix: "_small",
+        budgets: &[
+            ("x86_64_sse", 5),
+            ("x86_64_default", 5),
+            ("x86_sse", 12),
+            ("aarch64_neon", 8),
+            ("aarch64_default", 8),
+            ("armv7_default", 7),
+        ],
+        branch_free: true,
+    },
+];
+
+/// The conversions of the `ct` module per target name. Their assembly in the families with `branch_free` must not have branches. Only the specialized targets give this guarantee.
+pub const BRANCH_FREE: &[(&str, &[&str])] = &[
+    (
+        "x86_64_sse",
+        &[
+            "f32_to_i8",
+            "f32_to_u8",
+            "f32_to_i16",
+            "f32_to_u16",
+            "f32_to_i32",
+            "f32_to_u32",
+            "f64_to_i8",
+            "f64_to_u8",
+            "f64_to_i16",
+            "f64_to_u16",
+            "f64_to_i32",
+            "f64_to_u32",
+            "f32_to_i64",
+            "f32_to_u64",
+            "f64_to_i64",
+            "f64_to_u64",
+        ],
+    ),
+    (
+        "aarch64_neon",
+        &[
+            "f32_to_i8",
+            "f32_to_u8",
+            "f32_to_i16",
+            "f32_to_u16",
+            "f32_to_i32",
+            "f32_to_u32",
+            "f64_to_i8",
+            "f64_to_u8",
+            "f64_to_i16",
+            "f64_to_u16",
+            "f64_to_i32",
+            "f64_to_u32",
+            "f32_to_i64",
+            "f32_to_u64",
+            "f64_to_i64",
+            "f64_to_u64",
+        ],
+    ),
+    (
+        "x86_sse",
+        &[
+            "f32_to_i8",
+            "f32_to_u8",
+            "f32_to_i16",
+            "f32_to_u16",
+            "f32_to_i32",
+            "f32_to_u32",
+            "f64_to_i8",
+            "f64_to_u8",
+            "f64_to_i16",
+            "f64_to_u16",
+            "f64_to_i32",
+            "f64_to_u32",
+        ],
+    ),
+];